@@ -4,85 +4,184 @@
 //! operations via subprocess execution, measuring performance without
 //! modifying any existing TypeScript code.
 
-use super::BenchTarget;
+use super::{BenchTarget, Precondition, Version};
+use crate::benchmarks::config::{
+    RunConfig, DEFAULT_MEASUREMENT_ITERATIONS, DEFAULT_WARMUP_ITERATIONS,
+};
+use crate::benchmarks::error::BenchError;
+use crate::benchmarks::events;
+use crate::benchmarks::metrics::Metrics;
 use crate::benchmarks::result::BenchmarkResult;
 use async_trait::async_trait;
-use serde_json::json;
-use std::error::Error;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tokio::process::Command;
 use tracing::{debug, info};
 
-/// Number of iterations for benchmark warmup.
-const WARMUP_ITERATIONS: u32 = 3;
+/// Time after which a single command is killed.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(300);
 
-/// Number of iterations for benchmark measurement.
-const BENCHMARK_ITERATIONS: u32 = 10;
+/// Files of the Forge checkout every target depends on.
+const SHARED_INPUTS: &[&str] = &["package.json", "package-lock.json", "tsconfig.json", "src"];
 
-/// Helper to find the Forge project root directory.
-fn find_forge_root() -> PathBuf {
-    // Try to find the forge root by looking for package.json
-    let mut current = std::env::current_dir().unwrap_or_default();
+/// Oldest Node.js release Forge supports (`engines.node` in its package.json).
+const MIN_NODE_VERSION: Version = Version(20, 0, 0);
 
-    // If we're in forge-benchmarks, go up one level
-    if current.ends_with("forge-benchmarks") {
-        current = current.parent().map(|p| p.to_path_buf()).unwrap_or(current);
-    }
-
-    // Check if package.json exists at this level
-    if current.join("package.json").exists() {
-        return current;
-    }
+/// Preconditions of every target plus the `extra` paths under the Forge root.
+///
+/// All targets run Forge through `npx`, so they need Node.js and the
+/// checkout's installed dependencies.
+fn forge_preconditions(forge_root: &Path, extra: &[&str]) -> Vec<Precondition> {
+    let mut preconditions = vec![
+        Precondition::Binary("npx".to_string()),
+        Precondition::MinNodeVersion(MIN_NODE_VERSION),
+        Precondition::Path(forge_root.join("package.json")),
+        Precondition::Path(forge_root.join("node_modules")),
+    ];
+    preconditions.extend(extra.iter().map(|path| Precondition::Path(forge_root.join(path))));
+    preconditions
+}
 
-    // Default to /workspaces/forge
-    PathBuf::from("/workspaces/forge")
+/// Resolves the shared inputs plus `extra` against the Forge root.
+fn forge_inputs(forge_root: &Path, extra: &[&str]) -> Vec<PathBuf> {
+    SHARED_INPUTS
+        .iter()
+        .chain(extra)
+        .map(|path| forge_root.join(path))
+        .collect()
 }
 
+/// Reporter argument that makes vitest print a JSON report on stdout.
+const JSON_REPORTER: &str = "--reporter=json";
+
 /// Executes a TypeScript operation and measures execution time.
+///
+/// Fails if the command cannot be started, does not finish within
+/// `COMMAND_TIMEOUT`, or does not exit successfully. Commands run with
+/// `JSON_REPORTER` also fail if their stdout is not valid JSON.
 async fn measure_ts_operation(
     command: &str,
     args: &[&str],
     cwd: &PathBuf,
-) -> Result<Duration, Box<dyn Error + Send + Sync>> {
+) -> Result<Duration, BenchError> {
+    let command_line = std::iter::once(command)
+        .chain(args.iter().copied())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !cwd.is_dir() {
+        return Err(BenchError::Precondition {
+            message: format!("Forge checkout not found at {}", cwd.display()),
+        });
+    }
+
     let start = Instant::now();
 
-    let output = Command::new(command)
+    let child = Command::new(command)
         .args(args)
         .current_dir(cwd)
-        .output()
-        .await?;
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(COMMAND_TIMEOUT, child).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => {
+            return Err(BenchError::Spawn {
+                command: command_line,
+                message: e.to_string(),
+            })
+        }
+        Err(_) => {
+            return Err(BenchError::Timeout {
+                command: command_line,
+                timeout_ms: COMMAND_TIMEOUT.as_millis() as u64,
+            })
+        }
+    };
 
     let duration = start.elapsed();
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if let Some(error) = BenchError::from_exit_status(&command_line, output.status, &stderr) {
         debug!("Command failed: {}", stderr);
-        // Don't fail the benchmark, just note it in logs
+        return Err(error);
+    }
+    if args.contains(&JSON_REPORTER) {
+        check_json_output(&command_line, &output.stdout)?;
     }
 
     Ok(duration)
 }
 
+/// Checks that a command printed a JSON document on stdout.
+///
+/// # Returns
+///
+/// `BenchError::Parse` if `stdout` is not valid JSON.
+fn check_json_output(command_line: &str, stdout: &[u8]) -> Result<(), BenchError> {
+    serde_json::from_slice::<serde::de::IgnoredAny>(stdout)
+        .map(|_| ())
+        .map_err(|e| BenchError::Parse {
+            command: command_line.to_string(),
+            message: e.to_string(),
+        })
+}
+
+/// How often a benchmark command runs before and during measurement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Iterations {
+    /// Runs discarded before measurement.
+    pub warmup: u32,
+
+    /// Measured runs.
+    pub measurement: u32,
+}
+
+impl Default for Iterations {
+    fn default() -> Self {
+        Self {
+            warmup: DEFAULT_WARMUP_ITERATIONS,
+            measurement: DEFAULT_MEASUREMENT_ITERATIONS,
+        }
+    }
+}
+
+impl From<&RunConfig> for Iterations {
+    fn from(config: &RunConfig) -> Self {
+        Self {
+            warmup: config.warmup_iterations,
+            measurement: config.measurement_iterations,
+        }
+    }
+}
+
 /// Runs a benchmark with warmup and multiple iterations.
+///
+/// Fails with `BenchError::Setup` if no measured iterations are configured,
+/// since no metrics can be computed without samples.
 async fn run_benchmark_iterations<F, Fut>(
-    warmup: u32,
-    iterations: u32,
+    iterations: Iterations,
     mut f: F,
-) -> Result<BenchmarkMetrics, Box<dyn Error + Send + Sync>>
+) -> Result<BenchmarkMetrics, BenchError>
 where
     F: FnMut() -> Fut,
-    Fut: std::future::Future<Output = Result<Duration, Box<dyn Error + Send + Sync>>>,
+    Fut: std::future::Future<Output = Result<Duration, BenchError>>,
 {
-    // Warmup
-    for _ in 0..warmup {
-        let _ = f().await;
+    if iterations.measurement == 0 {
+        return Err(BenchError::Setup {
+            message: "measurement iterations must be at least 1".to_string(),
+        });
+    }
+
+    // Warmup; a command that cannot run would fail measurement anyway
+    for _ in 0..iterations.warmup {
+        f().await?;
     }
 
     // Measure
-    let mut durations = Vec::with_capacity(iterations as usize);
-    for _ in 0..iterations {
+    let total = iterations.measurement;
+    let mut durations = Vec::with_capacity(total as usize);
+    for i in 0..total {
         let duration = f().await?;
+        events::record_sample(i + 1, total, duration);
         durations.push(duration);
     }
 
@@ -97,6 +196,7 @@ struct BenchmarkMetrics {
     max_ns: f64,
     ops_per_sec: f64,
     samples: u32,
+    raw_ns: Vec<f64>,
 }
 
 impl BenchmarkMetrics {
@@ -115,17 +215,20 @@ impl BenchmarkMetrics {
             max_ns,
             ops_per_sec,
             samples: durations.len() as u32,
+            raw_ns: ns_values,
         }
     }
 
-    fn to_json(&self) -> serde_json::Value {
-        json!({
-            "avg_ns": self.avg_ns,
-            "min_ns": self.min_ns,
-            "max_ns": self.max_ns,
-            "ops_per_sec": self.ops_per_sec,
-            "samples": self.samples
-        })
+    fn to_metrics(&self) -> Metrics {
+        Metrics {
+            avg_ns: Some(self.avg_ns),
+            min_ns: Some(self.min_ns),
+            max_ns: Some(self.max_ns),
+            ops_per_sec: Some(self.ops_per_sec),
+            samples: Some(u64::from(self.samples)),
+            raw_ns: self.raw_ns.clone(),
+            ..Metrics::default()
+        }
     }
 }
 
@@ -139,22 +242,20 @@ impl BenchmarkMetrics {
 /// from a response structure by invoking the TypeScript vitest benchmark.
 pub struct ProviderDetectionBenchmark {
     forge_root: PathBuf,
+    iterations: Iterations,
 }
 
 impl ProviderDetectionBenchmark {
-    pub fn new() -> Self {
+    /// Creates the benchmark for the checkout at `forge_root` (see
+    /// `resolve_forge_root`), running `iterations` times.
+    pub fn new(forge_root: PathBuf, iterations: Iterations) -> Self {
         Self {
-            forge_root: find_forge_root(),
+            forge_root,
+            iterations,
         }
     }
 }
 
-impl Default for ProviderDetectionBenchmark {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[async_trait]
 impl BenchTarget for ProviderDetectionBenchmark {
     fn id(&self) -> String {
@@ -165,23 +266,30 @@ impl BenchTarget for ProviderDetectionBenchmark {
         "Benchmarks LLM-Forge provider detection from response structures".to_string()
     }
 
-    async fn run(&self) -> Result<BenchmarkResult, Box<dyn Error + Send + Sync>> {
+    fn inputs(&self) -> Vec<PathBuf> {
+        forge_inputs(&self.forge_root, &["tests/providers/performance.bench.ts", "vitest.config.ts"])
+    }
+
+    fn preconditions(&self) -> Vec<Precondition> {
+        forge_preconditions(&self.forge_root, &["tests/providers/performance.bench.ts"])
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult, BenchError> {
         info!("Running provider detection benchmark via vitest");
 
         // Run the vitest bench command with a filter for provider detection
         let metrics = run_benchmark_iterations(
-            WARMUP_ITERATIONS,
-            BENCHMARK_ITERATIONS,
+            self.iterations,
             || async {
                 measure_ts_operation(
                     "npx",
-                    &["vitest", "bench", "--run", "--reporter=json", "performance.bench.ts"],
+                    &["vitest", "bench", "--run", JSON_REPORTER, "performance.bench.ts"],
                     &self.forge_root,
                 ).await
             },
         ).await?;
 
-        Ok(BenchmarkResult::new(self.id(), metrics.to_json()))
+        Ok(BenchmarkResult::new(self.id(), metrics.to_metrics()))
     }
 }
 
@@ -194,22 +302,20 @@ impl BenchTarget for ProviderDetectionBenchmark {
 /// Measures how quickly LLM-Forge can parse and normalize provider responses.
 pub struct ResponseParsingBenchmark {
     forge_root: PathBuf,
+    iterations: Iterations,
 }
 
 impl ResponseParsingBenchmark {
-    pub fn new() -> Self {
+    /// Creates the benchmark for the checkout at `forge_root` (see
+    /// `resolve_forge_root`), running `iterations` times.
+    pub fn new(forge_root: PathBuf, iterations: Iterations) -> Self {
         Self {
-            forge_root: find_forge_root(),
+            forge_root,
+            iterations,
         }
     }
 }
 
-impl Default for ResponseParsingBenchmark {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[async_trait]
 impl BenchTarget for ResponseParsingBenchmark {
     fn id(&self) -> String {
@@ -220,23 +326,30 @@ impl BenchTarget for ResponseParsingBenchmark {
         "Benchmarks LLM-Forge response parsing and normalization".to_string()
     }
 
-    async fn run(&self) -> Result<BenchmarkResult, Box<dyn Error + Send + Sync>> {
+    fn inputs(&self) -> Vec<PathBuf> {
+        forge_inputs(&self.forge_root, &["tests/providers", "tests/fixtures", "vitest.config.ts"])
+    }
+
+    fn preconditions(&self) -> Vec<Precondition> {
+        forge_preconditions(&self.forge_root, &["tests/providers"])
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult, BenchError> {
         info!("Running response parsing benchmark");
 
         // Execute the TypeScript test suite which includes parsing benchmarks
         let metrics = run_benchmark_iterations(
-            WARMUP_ITERATIONS,
-            BENCHMARK_ITERATIONS,
+            self.iterations,
             || async {
                 measure_ts_operation(
                     "npx",
-                    &["vitest", "run", "--reporter=json", "providers"],
+                    &["vitest", "run", JSON_REPORTER, "providers"],
                     &self.forge_root,
                 ).await
             },
         ).await?;
 
-        Ok(BenchmarkResult::new(self.id(), metrics.to_json()))
+        Ok(BenchmarkResult::new(self.id(), metrics.to_metrics()))
     }
 }
 
@@ -249,22 +362,20 @@ impl BenchTarget for ResponseParsingBenchmark {
 /// Measures how quickly LLM-Forge validates canonical schemas.
 pub struct SchemaValidationBenchmark {
     forge_root: PathBuf,
+    iterations: Iterations,
 }
 
 impl SchemaValidationBenchmark {
-    pub fn new() -> Self {
+    /// Creates the benchmark for the checkout at `forge_root` (see
+    /// `resolve_forge_root`), running `iterations` times.
+    pub fn new(forge_root: PathBuf, iterations: Iterations) -> Self {
         Self {
-            forge_root: find_forge_root(),
+            forge_root,
+            iterations,
         }
     }
 }
 
-impl Default for SchemaValidationBenchmark {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[async_trait]
 impl BenchTarget for SchemaValidationBenchmark {
     fn id(&self) -> String {
@@ -275,22 +386,29 @@ impl BenchTarget for SchemaValidationBenchmark {
         "Benchmarks LLM-Forge canonical schema validation".to_string()
     }
 
-    async fn run(&self) -> Result<BenchmarkResult, Box<dyn Error + Send + Sync>> {
+    fn inputs(&self) -> Vec<PathBuf> {
+        forge_inputs(&self.forge_root, &["tests/schema", "vitest.config.ts"])
+    }
+
+    fn preconditions(&self) -> Vec<Precondition> {
+        forge_preconditions(&self.forge_root, &["tests/schema"])
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult, BenchError> {
         info!("Running schema validation benchmark");
 
         let metrics = run_benchmark_iterations(
-            WARMUP_ITERATIONS,
-            BENCHMARK_ITERATIONS,
+            self.iterations,
             || async {
                 measure_ts_operation(
                     "npx",
-                    &["vitest", "run", "--reporter=json", "schema"],
+                    &["vitest", "run", JSON_REPORTER, "schema"],
                     &self.forge_root,
                 ).await
             },
         ).await?;
 
-        Ok(BenchmarkResult::new(self.id(), metrics.to_json()))
+        Ok(BenchmarkResult::new(self.id(), metrics.to_metrics()))
     }
 }
 
@@ -303,22 +421,20 @@ impl BenchTarget for SchemaValidationBenchmark {
 /// Measures how quickly the `llm-forge parse` command executes.
 pub struct CliParseBenchmark {
     forge_root: PathBuf,
+    iterations: Iterations,
 }
 
 impl CliParseBenchmark {
-    pub fn new() -> Self {
+    /// Creates the benchmark for the checkout at `forge_root` (see
+    /// `resolve_forge_root`), running `iterations` times.
+    pub fn new(forge_root: PathBuf, iterations: Iterations) -> Self {
         Self {
-            forge_root: find_forge_root(),
+            forge_root,
+            iterations,
         }
     }
 }
 
-impl Default for CliParseBenchmark {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[async_trait]
 impl BenchTarget for CliParseBenchmark {
     fn id(&self) -> String {
@@ -329,40 +445,35 @@ impl BenchTarget for CliParseBenchmark {
         "Benchmarks LLM-Forge CLI parse command".to_string()
     }
 
-    async fn run(&self) -> Result<BenchmarkResult, Box<dyn Error + Send + Sync>> {
+    fn inputs(&self) -> Vec<PathBuf> {
+        forge_inputs(&self.forge_root, &["dist", "tests/fixtures/openapi-sample.json"])
+    }
+
+    fn preconditions(&self) -> Vec<Precondition> {
+        forge_preconditions(&self.forge_root, &["dist", "tests/fixtures/openapi-sample.json"])
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult, BenchError> {
         info!("Running CLI parse benchmark");
 
-        // Check if there's a sample OpenAPI spec to parse
+        // The sample spec is a precondition, so it exists here
         let sample_spec = self.forge_root.join("tests/fixtures/openapi-sample.json");
+        let sample_spec_arg = sample_spec.to_str().ok_or_else(|| BenchError::Setup {
+            message: format!("sample spec path is not UTF-8: {}", sample_spec.display()),
+        })?;
 
-        let metrics = if sample_spec.exists() {
-            run_benchmark_iterations(
-                WARMUP_ITERATIONS,
-                BENCHMARK_ITERATIONS,
-                || async {
-                    measure_ts_operation(
-                        "npx",
-                        &["llm-forge", "parse", sample_spec.to_str().unwrap()],
-                        &self.forge_root,
-                    ).await
-                },
-            ).await?
-        } else {
-            // If no sample spec, run help command as a baseline
-            run_benchmark_iterations(
-                WARMUP_ITERATIONS,
-                BENCHMARK_ITERATIONS,
-                || async {
-                    measure_ts_operation(
-                        "npx",
-                        &["llm-forge", "--help"],
-                        &self.forge_root,
-                    ).await
-                },
-            ).await?
-        };
+        let metrics = run_benchmark_iterations(
+            self.iterations,
+            || async {
+                measure_ts_operation(
+                    "npx",
+                    &["llm-forge", "parse", sample_spec_arg],
+                    &self.forge_root,
+                ).await
+            },
+        ).await?;
 
-        Ok(BenchmarkResult::new(self.id(), metrics.to_json()))
+        Ok(BenchmarkResult::new(self.id(), metrics.to_metrics()))
     }
 }
 
@@ -375,22 +486,20 @@ impl BenchTarget for CliParseBenchmark {
 /// Measures how quickly the `llm-forge generate` command executes.
 pub struct CliGenerateBenchmark {
     forge_root: PathBuf,
+    iterations: Iterations,
 }
 
 impl CliGenerateBenchmark {
-    pub fn new() -> Self {
+    /// Creates the benchmark for the checkout at `forge_root` (see
+    /// `resolve_forge_root`), running `iterations` times.
+    pub fn new(forge_root: PathBuf, iterations: Iterations) -> Self {
         Self {
-            forge_root: find_forge_root(),
+            forge_root,
+            iterations,
         }
     }
 }
 
-impl Default for CliGenerateBenchmark {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[async_trait]
 impl BenchTarget for CliGenerateBenchmark {
     fn id(&self) -> String {
@@ -401,13 +510,20 @@ impl BenchTarget for CliGenerateBenchmark {
         "Benchmarks LLM-Forge CLI generate command".to_string()
     }
 
-    async fn run(&self) -> Result<BenchmarkResult, Box<dyn Error + Send + Sync>> {
+    fn inputs(&self) -> Vec<PathBuf> {
+        forge_inputs(&self.forge_root, &["dist", "templates"])
+    }
+
+    fn preconditions(&self) -> Vec<Precondition> {
+        forge_preconditions(&self.forge_root, &["dist"])
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult, BenchError> {
         info!("Running CLI generate benchmark");
 
         // Run help command as baseline since generate requires valid input
         let metrics = run_benchmark_iterations(
-            WARMUP_ITERATIONS,
-            BENCHMARK_ITERATIONS,
+            self.iterations,
             || async {
                 measure_ts_operation(
                     "npx",
@@ -417,7 +533,7 @@ impl BenchTarget for CliGenerateBenchmark {
             },
         ).await?;
 
-        Ok(BenchmarkResult::new(self.id(), metrics.to_json()))
+        Ok(BenchmarkResult::new(self.id(), metrics.to_metrics()))
     }
 }
 
@@ -441,19 +557,96 @@ mod tests {
         assert!((metrics.max_ns - 3000.0).abs() < 0.1);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unparsable_output_is_parse_error() {
+        let cwd = std::env::temp_dir();
+
+        let error = measure_ts_operation("echo", &["not json", JSON_REPORTER], &cwd)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), "parse");
+        assert!(!error.is_environmental());
+
+        // `$1` is the reporter argument, so only `{}` is printed
+        let parsed =
+            measure_ts_operation("sh", &["-c", "echo {}", "sh", JSON_REPORTER], &cwd).await;
+        assert!(parsed.is_ok());
+    }
+
     #[test]
-    fn test_find_forge_root() {
-        let root = find_forge_root();
-        // Should return a valid path
-        assert!(!root.as_os_str().is_empty());
+    fn test_iterations_from_config() {
+        let config = RunConfig {
+            warmup_iterations: 0,
+            measurement_iterations: 2,
+            ..RunConfig::default()
+        };
+        assert_eq!(
+            Iterations::from(&config),
+            Iterations {
+                warmup: 0,
+                measurement: 2
+            }
+        );
+        assert_eq!(
+            Iterations::from(&RunConfig::default()),
+            Iterations::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zero_measurement_iterations_is_setup_error() {
+        let iterations = Iterations {
+            warmup: 1,
+            measurement: 0,
+        };
+        let mut runs = 0;
+
+        let error = run_benchmark_iterations(iterations, || {
+            runs += 1;
+            async { Ok(Duration::from_nanos(1000)) }
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(error.kind(), "setup");
+        assert_eq!(runs, 0);
     }
 
     #[test]
     fn test_benchmark_ids() {
-        assert_eq!(ProviderDetectionBenchmark::new().id(), "forge-provider-detection");
-        assert_eq!(ResponseParsingBenchmark::new().id(), "forge-response-parsing");
-        assert_eq!(SchemaValidationBenchmark::new().id(), "forge-schema-validation");
-        assert_eq!(CliParseBenchmark::new().id(), "forge-cli-parse");
-        assert_eq!(CliGenerateBenchmark::new().id(), "forge-cli-generate");
+        let root = PathBuf::from("/checkouts/llm-forge");
+        let n = Iterations::default();
+        assert_eq!(ProviderDetectionBenchmark::new(root.clone(), n).id(), "forge-provider-detection");
+        assert_eq!(ResponseParsingBenchmark::new(root.clone(), n).id(), "forge-response-parsing");
+        assert_eq!(SchemaValidationBenchmark::new(root.clone(), n).id(), "forge-schema-validation");
+        assert_eq!(CliParseBenchmark::new(root.clone(), n).id(), "forge-cli-parse");
+        assert_eq!(CliGenerateBenchmark::new(root, n).id(), "forge-cli-generate");
+    }
+
+    #[test]
+    fn test_inputs_under_forge_root() {
+        let target = CliParseBenchmark::new(
+            PathBuf::from("/checkouts/llm-forge"),
+            Iterations::default(),
+        );
+        let inputs = target.inputs();
+        assert!(inputs.iter().all(|p| p.starts_with(&target.forge_root)));
+        assert!(inputs.contains(&target.forge_root.join("package-lock.json")));
+        assert!(inputs.contains(&target.forge_root.join("tests/fixtures/openapi-sample.json")));
+    }
+
+    #[test]
+    fn test_preconditions() {
+        let target = CliParseBenchmark::new(
+            PathBuf::from("/checkouts/llm-forge"),
+            Iterations::default(),
+        );
+        let preconditions = target.preconditions();
+        assert_eq!(preconditions[0], Precondition::Binary("npx".to_string()));
+        assert!(preconditions.contains(&Precondition::MinNodeVersion(Version(20, 0, 0))));
+        assert!(preconditions.contains(&Precondition::Path(target.forge_root.join("node_modules"))));
+        assert!(preconditions.contains(&Precondition::Path(
+            target.forge_root.join("tests/fixtures/openapi-sample.json")
+        )));
     }
 }
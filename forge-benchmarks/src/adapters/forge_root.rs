@@ -0,0 +1,204 @@
+//! Forge checkout resolution.
+//!
+//! Every adapter runs commands inside an llm-forge checkout. Its root is
+//! resolved once per run, from the first of:
+//!
+//! 1. An explicit path, e.g. the `--forge-root` flag of `run_benchmarks`
+//! 2. The `FORGE_ROOT` environment variable
+//! 3. `forge_root` in the run configuration
+//! 4. The nearest ancestor of the working directory holding the checkout
+//!
+//! A path taken from 1-3 must be the root of an llm-forge checkout, i.e.
+//! hold a `package.json` named `FORGE_PACKAGE_NAME`; anything else is an
+//! error rather than a silent fallback.
+
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Environment variable naming the Forge root.
+pub const FORGE_ROOT_ENV: &str = "FORGE_ROOT";
+
+/// `name` in the `package.json` of an llm-forge checkout.
+pub const FORGE_PACKAGE_NAME: &str = "@llm-dev-ops/llm-forge";
+
+/// Why the Forge root could not be resolved.
+#[derive(Error, Debug)]
+pub enum ForgeRootError {
+    /// No ancestor of the working directory is an llm-forge checkout.
+    #[error(
+        "no llm-forge checkout found in {} or its ancestors; pass --forge-root or set {}",
+        start.display(),
+        FORGE_ROOT_ENV
+    )]
+    NotFound { start: PathBuf },
+
+    /// The path has no readable `package.json`.
+    #[error("{} is not an llm-forge checkout: {message}", path.display())]
+    Unreadable { path: PathBuf, message: String },
+
+    /// The `package.json` belongs to another package.
+    #[error(
+        "{} is not an llm-forge checkout: package.json is named `{name}`, expected `{}`",
+        path.display(),
+        FORGE_PACKAGE_NAME
+    )]
+    WrongPackage { path: PathBuf, name: String },
+}
+
+#[derive(Deserialize)]
+struct PackageJson {
+    #[serde(default)]
+    name: String,
+}
+
+/// Resolves the Forge root.
+///
+/// # Arguments
+///
+/// * `explicit` - Path given on the command line, if any
+/// * `configured` - `forge_root` of the run configuration, if set
+///
+/// # Returns
+///
+/// The canonical path of the checkout, or a `ForgeRootError` if the chosen
+/// path is not an llm-forge checkout or none was found.
+pub fn resolve_forge_root(
+    explicit: Option<&Path>,
+    configured: Option<&Path>,
+) -> Result<PathBuf, ForgeRootError> {
+    let from_env = env::var_os(FORGE_ROOT_ENV)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    match explicit
+        .map(Path::to_path_buf)
+        .or(from_env)
+        .or_else(|| configured.map(Path::to_path_buf))
+    {
+        Some(path) => validate_forge_root(&path),
+        None => {
+            let cwd = env::current_dir().map_err(|e| ForgeRootError::Unreadable {
+                path: PathBuf::from("."),
+                message: e.to_string(),
+            })?;
+            discover_forge_root(&cwd)
+        }
+    }
+}
+
+/// Resolves the Forge root from the environment and the working directory.
+///
+/// Shorthand for `resolve_forge_root(None, None)`.
+pub fn find_forge_root() -> Result<PathBuf, ForgeRootError> {
+    resolve_forge_root(None, None)
+}
+
+/// Finds the nearest llm-forge checkout at or above `start`.
+///
+/// Directories with a `package.json` of another package are passed over,
+/// so nested packages do not stop the search.
+///
+/// # Arguments
+///
+/// * `start` - Directory the search starts in
+///
+/// # Returns
+///
+/// The canonical path of the checkout, or `ForgeRootError::NotFound`.
+pub fn discover_forge_root(start: &Path) -> Result<PathBuf, ForgeRootError> {
+    start
+        .ancestors()
+        .filter(|dir| dir.join("package.json").is_file())
+        .find_map(|dir| validate_forge_root(dir).ok())
+        .ok_or_else(|| ForgeRootError::NotFound {
+            start: start.to_path_buf(),
+        })
+}
+
+/// Checks that `path` is the root of an llm-forge checkout.
+///
+/// # Returns
+///
+/// The canonical path, or why it is not a checkout.
+pub fn validate_forge_root(path: &Path) -> Result<PathBuf, ForgeRootError> {
+    let unreadable = |message: String| ForgeRootError::Unreadable {
+        path: path.to_path_buf(),
+        message,
+    };
+    let manifest = path.join("package.json");
+    let content = fs::read_to_string(&manifest)
+        .map_err(|e| unreadable(format!("cannot read {}: {}", manifest.display(), e)))?;
+    let package: PackageJson = serde_json::from_str(&content)
+        .map_err(|e| unreadable(format!("invalid {}: {}", manifest.display(), e)))?;
+    if package.name != FORGE_PACKAGE_NAME {
+        return Err(ForgeRootError::WrongPackage {
+            path: path.to_path_buf(),
+            name: package.name,
+        });
+    }
+    path.canonicalize().map_err(|e| unreadable(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn package(dir: &Path, name: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            format!(r#"{{"name": "{}", "version": "1.0.0"}}"#, name),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_validate_forge_root() {
+        let dir = TempDir::new().unwrap();
+        assert!(matches!(
+            validate_forge_root(dir.path()),
+            Err(ForgeRootError::Unreadable { .. })
+        ));
+
+        package(dir.path(), "left-pad");
+        let error = validate_forge_root(dir.path()).unwrap_err();
+        assert!(matches!(&error, ForgeRootError::WrongPackage { name, .. } if name == "left-pad"));
+        assert!(error
+            .to_string()
+            .contains("expected `@llm-dev-ops/llm-forge`"));
+
+        package(dir.path(), FORGE_PACKAGE_NAME);
+        assert_eq!(
+            validate_forge_root(dir.path()).unwrap(),
+            dir.path().canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_discover_walks_past_other_packages() {
+        let dir = TempDir::new().unwrap();
+        package(dir.path(), FORGE_PACKAGE_NAME);
+        let nested = dir.path().join("examples/sdk");
+        package(&nested, "forge-example-sdk");
+        let start = nested.join("src");
+        fs::create_dir_all(&start).unwrap();
+
+        assert_eq!(
+            discover_forge_root(&start).unwrap(),
+            dir.path().canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_explicit_path_is_validated() {
+        let dir = TempDir::new().unwrap();
+        package(dir.path(), "left-pad");
+        assert!(matches!(
+            resolve_forge_root(Some(dir.path()), None),
+            Err(ForgeRootError::WrongPackage { .. })
+        ));
+    }
+}
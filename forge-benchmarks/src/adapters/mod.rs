@@ -4,13 +4,22 @@
 //! for benchmarking LLM-Forge TypeScript operations from Rust.
 
 mod forge_adapter;
+mod forge_root;
+mod precondition;
 
+use crate::benchmarks::config::RunConfig;
 use crate::benchmarks::result::BenchmarkResult;
 use async_trait::async_trait;
-use std::error::Error;
+use std::path::{Path, PathBuf};
 
 // Re-export adapters
+pub use crate::benchmarks::error::BenchError;
 pub use forge_adapter::*;
+pub use forge_root::{
+    discover_forge_root, find_forge_root, resolve_forge_root, validate_forge_root,
+    ForgeRootError, FORGE_PACKAGE_NAME, FORGE_ROOT_ENV,
+};
+pub use precondition::{check_preconditions, Precondition, Version};
 
 /// Trait for benchmark targets.
 ///
@@ -20,7 +29,7 @@ pub use forge_adapter::*;
 /// # Example
 ///
 /// ```rust
-/// use forge_benchmarks::adapters::BenchTarget;
+/// use forge_benchmarks::adapters::{BenchError, BenchTarget};
 /// use forge_benchmarks::benchmarks::result::BenchmarkResult;
 /// use async_trait::async_trait;
 /// use serde_json::json;
@@ -33,7 +42,7 @@ pub use forge_adapter::*;
 ///         "my-benchmark".to_string()
 ///     }
 ///
-///     async fn run(&self) -> Result<BenchmarkResult, Box<dyn std::error::Error + Send + Sync>> {
+///     async fn run(&self) -> Result<BenchmarkResult, BenchError> {
 ///         // Perform benchmark...
 ///         Ok(BenchmarkResult::new(
 ///             self.id(),
@@ -53,9 +62,10 @@ pub trait BenchTarget: Send + Sync {
     ///
     /// # Returns
     ///
-    /// A `BenchmarkResult` containing the metrics, or an error if the
-    /// benchmark could not be executed.
-    async fn run(&self) -> Result<BenchmarkResult, Box<dyn Error + Send + Sync>>;
+    /// A `BenchmarkResult` containing the metrics, or a `BenchError`
+    /// classifying why the benchmark could not be executed. The runner
+    /// records the error in a failed result.
+    async fn run(&self) -> Result<BenchmarkResult, BenchError>;
 
     /// Returns a human-readable description of the benchmark.
     ///
@@ -63,6 +73,24 @@ pub trait BenchTarget: Send + Sync {
     fn description(&self) -> String {
         self.id()
     }
+
+    /// Returns the files and directories whose contents determine the result.
+    ///
+    /// The runner hashes these to decide whether a cached result can be
+    /// reused. Default implementation returns no inputs, which means the
+    /// target is always measured.
+    fn inputs(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Returns what the target needs from the machine to run.
+    ///
+    /// The runner checks these before `run` and records the target as
+    /// skipped, with the reason, if one is not met. Default implementation
+    /// returns no preconditions.
+    fn preconditions(&self) -> Vec<Precondition> {
+        Vec::new()
+    }
 }
 
 /// Returns all registered benchmark targets.
@@ -70,6 +98,11 @@ pub trait BenchTarget: Send + Sync {
 /// This function provides the canonical registry of all benchmark targets
 /// that will be executed by `run_all_benchmarks()`.
 ///
+/// # Arguments
+///
+/// * `forge_root` - Root of the llm-forge checkout the targets run in
+/// * `config` - Run configuration supplying the iteration counts
+///
 /// # Returns
 ///
 /// A vector of boxed `BenchTarget` implementations.
@@ -78,20 +111,24 @@ pub trait BenchTarget: Send + Sync {
 ///
 /// ```rust
 /// use forge_benchmarks::adapters::all_targets;
+/// use forge_benchmarks::benchmarks::config::RunConfig;
+/// use std::path::Path;
 ///
-/// let targets = all_targets();
+/// let targets = all_targets(Path::new("/checkouts/llm-forge"), &RunConfig::default());
 /// for target in &targets {
 ///     println!("Registered: {} - {}", target.id(), target.description());
 /// }
 /// ```
-pub fn all_targets() -> Vec<Box<dyn BenchTarget>> {
+pub fn all_targets(forge_root: &Path, config: &RunConfig) -> Vec<Box<dyn BenchTarget>> {
+    let root = || forge_root.to_path_buf();
+    let iterations = Iterations::from(config);
     vec![
         // TypeScript-to-Rust benchmark adapters for Forge operations
-        Box::new(forge_adapter::ProviderDetectionBenchmark::new()),
-        Box::new(forge_adapter::ResponseParsingBenchmark::new()),
-        Box::new(forge_adapter::SchemaValidationBenchmark::new()),
-        Box::new(forge_adapter::CliParseBenchmark::new()),
-        Box::new(forge_adapter::CliGenerateBenchmark::new()),
+        Box::new(forge_adapter::ProviderDetectionBenchmark::new(root(), iterations)),
+        Box::new(forge_adapter::ResponseParsingBenchmark::new(root(), iterations)),
+        Box::new(forge_adapter::SchemaValidationBenchmark::new(root(), iterations)),
+        Box::new(forge_adapter::CliParseBenchmark::new(root(), iterations)),
+        Box::new(forge_adapter::CliGenerateBenchmark::new(root(), iterations)),
     ]
 }
 
@@ -101,13 +138,13 @@ mod tests {
 
     #[test]
     fn test_all_targets_not_empty() {
-        let targets = all_targets();
+        let targets = all_targets(Path::new("/checkouts/llm-forge"), &RunConfig::default());
         assert!(!targets.is_empty(), "Should have registered targets");
     }
 
     #[test]
     fn test_all_targets_unique_ids() {
-        let targets = all_targets();
+        let targets = all_targets(Path::new("/checkouts/llm-forge"), &RunConfig::default());
         let mut ids: Vec<_> = targets.iter().map(|t| t.id()).collect();
         let original_len = ids.len();
         ids.sort();
@@ -121,7 +158,7 @@ mod tests {
 
     #[test]
     fn test_target_descriptions() {
-        let targets = all_targets();
+        let targets = all_targets(Path::new("/checkouts/llm-forge"), &RunConfig::default());
         for target in &targets {
             let desc = target.description();
             assert!(!desc.is_empty(), "Description should not be empty");
@@ -0,0 +1,187 @@
+//! Target preconditions.
+//!
+//! Targets declare what they need from the machine they run on (tools on
+//! `PATH`, files in the Forge checkout, a minimum Node.js version) through
+//! `BenchTarget::preconditions`. The runner checks them before running a
+//! target and records it as skipped, with the reason, if one is not met.
+
+use std::env;
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// Node.js version reported by `node --version`, looked up once per process.
+static NODE_VERSION: OnceLock<Option<Version>> = OnceLock::new();
+
+/// A `major.minor.patch` version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version(pub u32, pub u32, pub u32);
+
+impl Version {
+    /// Parses a version such as `v20.11.1` or `18.19`.
+    ///
+    /// Missing components are zero and suffixes such as `-nightly` are
+    /// ignored.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().trim_start_matches('v').split('.').map(|part| {
+            let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse::<u32>().ok()
+        });
+        let major = parts.next()??;
+        let minor = parts.next().flatten().unwrap_or(0);
+        let patch = parts.next().flatten().unwrap_or(0);
+        Some(Version(major, minor, patch))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.0, self.1, self.2)
+    }
+}
+
+/// Requirement a target has of the machine it runs on.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::adapters::{check_preconditions, Precondition};
+///
+/// let preconditions = vec![
+///     Precondition::Binary("definitely-not-installed".to_string()),
+///     Precondition::Path("/does/not/exist".into()),
+/// ];
+///
+/// let reason = check_preconditions(&preconditions).unwrap_err();
+/// assert_eq!(reason, "`definitely-not-installed` not found on PATH");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Precondition {
+    /// An executable with this name is on `PATH`.
+    Binary(String),
+
+    /// This file or directory exists.
+    Path(PathBuf),
+
+    /// `node --version` reports at least this version.
+    MinNodeVersion(Version),
+}
+
+impl Precondition {
+    /// Checks the precondition.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if it is met, otherwise the reason it is not.
+    pub fn check(&self) -> Result<(), String> {
+        match self {
+            Precondition::Binary(name) => match find_on_path(name) {
+                Some(_) => Ok(()),
+                None => Err(format!("`{}` not found on PATH", name)),
+            },
+            Precondition::Path(path) if path.exists() => Ok(()),
+            Precondition::Path(path) => Err(format!("{} does not exist", path.display())),
+            Precondition::MinNodeVersion(required) => {
+                let found = NODE_VERSION
+                    .get_or_init(|| {
+                        let output = Command::new("node").arg("--version").output().ok()?;
+                        output
+                            .status
+                            .success()
+                            .then(|| Version::parse(&String::from_utf8_lossy(&output.stdout)))?
+                    })
+                    .ok_or_else(|| "node version could not be determined".to_string())?;
+                if found >= *required {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "node {} is older than the required {}",
+                        found, required
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// Checks preconditions in order.
+///
+/// # Arguments
+///
+/// * `preconditions` - Preconditions of a target
+///
+/// # Returns
+///
+/// `Ok(())` if all are met, otherwise the reason of the first unmet one.
+pub fn check_preconditions(preconditions: &[Precondition]) -> Result<(), String> {
+    preconditions.iter().try_for_each(Precondition::check)
+}
+
+/// Looks up an executable on `PATH`.
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    let paths = env::var_os("PATH")?;
+    env::split_paths(&paths)
+        .flat_map(|dir| {
+            EXECUTABLE_SUFFIXES
+                .iter()
+                .map(move |suffix| dir.join(format!("{}{}", name, suffix)))
+        })
+        .find(|candidate| is_executable(candidate))
+}
+
+/// Suffixes tried when looking up an executable; Windows shims such as
+/// `npx.cmd` need theirs spelled out.
+#[cfg(windows)]
+const EXECUTABLE_SUFFIXES: &[&str] = &["", ".exe", ".cmd", ".bat"];
+#[cfg(not(windows))]
+const EXECUTABLE_SUFFIXES: &[&str] = &[""];
+
+#[cfg(unix)]
+fn is_executable(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &std::path::Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(Version::parse("v20.11.1\n"), Some(Version(20, 11, 1)));
+        assert_eq!(Version::parse("18.19"), Some(Version(18, 19, 0)));
+        assert_eq!(
+            Version::parse("v22.0.0-nightly2024"),
+            Some(Version(22, 0, 0))
+        );
+        assert_eq!(Version::parse("node"), None);
+        assert!(Version(20, 0, 0) > Version(18, 19, 1));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_preconditions() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(check_preconditions(&[
+            Precondition::Binary("sh".to_string()),
+            Precondition::Path(dir.path().to_path_buf()),
+        ])
+        .is_ok());
+
+        let missing = dir.path().join("dist");
+        assert_eq!(
+            check_preconditions(&[
+                Precondition::Path(missing.clone()),
+                Precondition::Binary("definitely-not-installed".to_string()),
+            ]),
+            Err(format!("{} does not exist", missing.display()))
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Content-addressed reuse of benchmark results.
+//!
+//! A target's result is keyed on a SHA-256 hash of everything it depends
+//! on: the files and directories listed by `BenchTarget::inputs`, the
+//! measurement settings of the run and the environment fingerprint. When a
+//! later run computes the same hash, the runner can reuse the earlier result
+//! instead of measuring the target again.
+//!
+//! Storage is pluggable through `ResultCache`; `infra::cache::BenchmarkCache`
+//! implements it when the `infra-cache` feature is enabled.
+
+use super::config::RunConfig;
+use super::environment::EnvironmentInfo;
+use super::result::BenchmarkResult;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Version of the hashing scheme, mixed into every hash so that changing
+/// the scheme invalidates all earlier entries.
+const HASH_VERSION: &str = "forge-benchmarks/input-hash/v1";
+
+/// Directories never descended into when hashing a directory input.
+const IGNORED_DIRS: &[&str] = &[".git", "node_modules"];
+
+/// Whether the runner may reuse cached results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// Reuse a cached result when the inputs are unchanged.
+    #[default]
+    Reuse,
+
+    /// Measure every target again and replace the cached results.
+    Refresh,
+}
+
+/// Storage of results keyed by input hash.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::cache::ResultCache;
+/// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use std::collections::HashMap;
+/// use std::sync::Mutex;
+///
+/// #[derive(Default)]
+/// struct MemoryCache(Mutex<HashMap<String, BenchmarkResult>>);
+///
+/// impl ResultCache for MemoryCache {
+///     fn lookup(&self, input_hash: &str) -> Option<BenchmarkResult> {
+///         self.0.lock().unwrap().get(input_hash).cloned()
+///     }
+///
+///     fn store(&self, result: &BenchmarkResult) {
+///         if let Some(hash) = &result.input_hash {
+///             self.0.lock().unwrap().insert(hash.clone(), result.clone());
+///         }
+///     }
+/// }
+/// ```
+pub trait ResultCache: Send + Sync {
+    /// Returns a result measured with inputs hashing to `input_hash`, if
+    /// one is stored and still valid.
+    fn lookup(&self, input_hash: &str) -> Option<BenchmarkResult>;
+
+    /// Stores a freshly measured result under its `input_hash`.
+    ///
+    /// Results without an input hash are ignored.
+    fn store(&self, result: &BenchmarkResult);
+}
+
+/// Computes the input hash of a target.
+///
+/// Files are hashed by content and directories recursively in name order,
+/// skipping `.git` and `node_modules`. Missing inputs are hashed as
+/// missing, so creating them later changes the hash.
+///
+/// # Arguments
+///
+/// * `target_id` - ID of the target
+/// * `inputs` - Files and directories the target depends on
+/// * `config` - Configuration of the run; only the measurement settings
+///   are hashed
+/// * `environment` - Environment of the run, hashed via its fingerprint
+///
+/// # Returns
+///
+/// The lowercase hex SHA-256 digest, or an `io::Error` if an input cannot
+/// be read.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::cache::input_hash;
+/// use forge_benchmarks::benchmarks::config::RunConfig;
+/// use std::path::PathBuf;
+///
+/// let inputs = vec![PathBuf::from("Cargo.toml")];
+/// let hash = input_hash("forge-cli-parse", &inputs, &RunConfig::default(), None).unwrap();
+/// assert_eq!(hash.len(), 64);
+/// ```
+pub fn input_hash<P: AsRef<Path>>(
+    target_id: &str,
+    inputs: &[P],
+    config: &RunConfig,
+    environment: Option<&EnvironmentInfo>,
+) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    field(&mut hasher, HASH_VERSION.as_bytes());
+    field(&mut hasher, target_id.as_bytes());
+    field(&mut hasher, &config.warmup_iterations.to_le_bytes());
+    field(&mut hasher, &config.measurement_iterations.to_le_bytes());
+    field(
+        &mut hasher,
+        environment
+            .map(EnvironmentInfo::fingerprint)
+            .unwrap_or_default()
+            .as_bytes(),
+    );
+
+    for input in inputs {
+        let input = input.as_ref();
+        let name = input.file_name().unwrap_or_default();
+        field(&mut hasher, name.to_string_lossy().as_bytes());
+        hash_path(&mut hasher, input, Path::new(""))?;
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Hashes one input, recording paths relative to the input so the same
+/// checkout hashes identically wherever it is located.
+fn hash_path(hasher: &mut Sha256, path: &Path, relative: &Path) -> io::Result<()> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            field(hasher, b"missing");
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+
+    if metadata.file_type().is_symlink() {
+        field(hasher, b"link");
+        field(hasher, relative.to_string_lossy().as_bytes());
+        field(hasher, fs::read_link(path)?.to_string_lossy().as_bytes());
+    } else if metadata.is_dir() {
+        field(hasher, b"dir");
+        field(hasher, relative.to_string_lossy().as_bytes());
+
+        let mut entries = fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.file_name()))
+            .collect::<io::Result<Vec<_>>>()?;
+        entries.sort();
+        for name in entries {
+            if IGNORED_DIRS.iter().any(|ignored| name == *ignored) {
+                continue;
+            }
+            hash_path(hasher, &path.join(&name), &relative.join(&name))?;
+        }
+    } else {
+        field(hasher, b"file");
+        field(hasher, relative.to_string_lossy().as_bytes());
+        field(hasher, &fs::read(path)?);
+    }
+    Ok(())
+}
+
+/// Feeds a length-prefixed field, so adjacent fields cannot run together.
+fn field(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tree() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("src/parsers")).unwrap();
+        fs::create_dir_all(dir.path().join("src/node_modules/dep")).unwrap();
+        fs::write(dir.path().join("package-lock.json"), "{}").unwrap();
+        fs::write(dir.path().join("src/index.ts"), "export {}").unwrap();
+        fs::write(dir.path().join("src/parsers/openapi.ts"), "parse()").unwrap();
+        fs::write(dir.path().join("src/node_modules/dep/index.js"), "1").unwrap();
+        dir
+    }
+
+    fn hash(dir: &TempDir, config: &RunConfig) -> String {
+        let inputs = [dir.path().join("src"), dir.path().join("package-lock.json")];
+        input_hash("forge-cli-parse", &inputs, config, None).unwrap()
+    }
+
+    #[test]
+    fn test_hash_is_stable() {
+        let dir = tree();
+        let config = RunConfig::default();
+        assert_eq!(hash(&dir, &config), hash(&dir, &config));
+
+        // Unrelated files and ignored directories do not matter
+        let before = hash(&dir, &config);
+        fs::write(dir.path().join("README.md"), "docs").unwrap();
+        fs::write(dir.path().join("src/node_modules/dep/index.js"), "2").unwrap();
+        fs::write(dir.path().join("src/node_modules/dep/other.js"), "3").unwrap();
+        assert_eq!(hash(&dir, &config), before);
+
+        // Neither does where the checkout lives
+        let moved = TempDir::new().unwrap();
+        let target = moved.path().join("checkout");
+        fs::rename(dir.path(), &target).unwrap();
+        let inputs = [target.join("src"), target.join("package-lock.json")];
+        assert_eq!(
+            input_hash("forge-cli-parse", &inputs, &config, None).unwrap(),
+            before
+        );
+    }
+
+    #[test]
+    fn test_hash_changes_with_inputs() {
+        let dir = tree();
+        let config = RunConfig::default();
+        let original = hash(&dir, &config);
+
+        fs::write(dir.path().join("src/parsers/openapi.ts"), "parse(spec)").unwrap();
+        let edited = hash(&dir, &config);
+        assert_ne!(edited, original);
+
+        fs::write(dir.path().join("src/parsers/new.ts"), "").unwrap();
+        let added = hash(&dir, &config);
+        assert_ne!(added, edited);
+
+        fs::remove_file(dir.path().join("package-lock.json")).unwrap();
+        assert_ne!(hash(&dir, &config), added);
+    }
+
+    #[test]
+    fn test_hash_changes_with_config_and_target() {
+        let dir = tree();
+        let config = RunConfig::default();
+        let more = RunConfig {
+            measurement_iterations: config.measurement_iterations + 1,
+            ..RunConfig::default()
+        };
+        assert_ne!(hash(&dir, &config), hash(&dir, &more));
+
+        let inputs = [dir.path().join("src")];
+        assert_ne!(
+            input_hash("a", &inputs, &config, None).unwrap(),
+            input_hash("b", &inputs, &config, None).unwrap()
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Baseline comparison and regression gate.
+//!
+//! This module compares a run against a baseline run, metric by metric, and
+//! classifies each change as an improvement, a regression or noise according
+//! to the metric's direction and a percentage threshold. When both runs
+//! recorded raw samples, changes of the average latency and of the throughput
+//! derived from it are also tested for statistical significance; requiring
+//! significance for a verdict is opt-in (see `require_significance`).
+
+use super::metrics::{MetricDirection, Metrics};
+use super::run::RunReport;
+use super::stats::{t_critical_95, welch_df, welch_t};
+use serde::{Deserialize, Serialize};
+
+/// Default relative change, in percent, tolerated before a regression fails
+/// the gate.
+pub const DEFAULT_REGRESSION_THRESHOLD_PCT: f64 = 5.0;
+
+/// Metric compared by the regression gate when none is specified.
+pub const DEFAULT_GATE_METRIC: &str = "ops_per_sec";
+
+/// Metrics derived from the mean of the raw samples (`raw_ns`), and so the
+/// only ones whose change can be tested for significance on them.
+pub const SAMPLED_METRICS: &[&str] = &["avg_ns", "ops_per_sec"];
+
+/// Classification of a metric change against the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    /// The metric moved in the better direction beyond the threshold.
+    Improved,
+
+    /// The change is within the threshold.
+    Unchanged,
+
+    /// The metric moved in the worse direction beyond the threshold.
+    Regressed,
+}
+
+/// Comparison of one metric of one target against the baseline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comparison {
+    /// Target the metric belongs to.
+    pub target_id: String,
+
+    /// Metric key that was compared.
+    pub metric: String,
+
+    /// Value in the baseline run.
+    pub baseline: f64,
+
+    /// Value in the current run.
+    pub current: f64,
+
+    /// Relative change from baseline to current, in percent.
+    pub delta_pct: f64,
+
+    /// Threshold the verdict was computed with, in percent.
+    pub threshold_pct: f64,
+
+    /// Welch t statistic of the raw samples, if the metric is one of
+    /// `SAMPLED_METRICS` and both runs recorded them. Its sign follows the
+    /// metric, so it is negative when `ops_per_sec` dropped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub t_statistic: Option<f64>,
+
+    /// Degrees of freedom of `t_statistic`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degrees_of_freedom: Option<f64>,
+
+    /// Whether the change is significant at the 95% level, if tested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub significant: Option<bool>,
+
+    /// Classification of the change.
+    pub verdict: Verdict,
+}
+
+impl Comparison {
+    /// Returns `true` if this comparison violates the regression gate.
+    pub fn is_regression(&self) -> bool {
+        self.verdict == Verdict::Regressed
+    }
+
+    /// Returns whether the change is statistically significant, or `None`
+    /// if it could not be tested.
+    pub fn is_significant(&self) -> Option<bool> {
+        self.significant
+    }
+
+    /// Returns a one-line human-readable description of the change.
+    pub fn describe(&self) -> String {
+        format!(
+            "{}: {} -> {} ({:+.2}%, threshold {:.2}%)",
+            self.metric, self.baseline, self.current, self.delta_pct, self.threshold_pct
+        )
+    }
+}
+
+/// Compares one metric of every target present in both runs.
+///
+/// Targets missing from either run, failed targets and metrics without a
+/// known direction or with a zero baseline are skipped. The verdict depends
+/// on the threshold only. For `SAMPLED_METRICS`, significance is tested
+/// with Welch's t-test on the raw samples and reported alongside the verdict.
+///
+/// # Arguments
+///
+/// * `baseline` - The run to compare against
+/// * `current` - The run being evaluated
+/// * `metric` - Metric key to compare (e.g. `ops_per_sec`)
+/// * `threshold_pct` - Relative change tolerated before a verdict is given
+///
+/// # Returns
+///
+/// One `Comparison` per comparable target, in the order of `current`.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::compare::{compare_reports, Verdict};
+/// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use forge_benchmarks::benchmarks::run::RunReport;
+/// use serde_json::json;
+///
+/// let baseline = RunReport::from_legacy(vec![
+///     BenchmarkResult::new("parse".to_string(), json!({"ops_per_sec": 1000.0})),
+/// ]);
+/// let current = RunReport::from_legacy(vec![
+///     BenchmarkResult::new("parse".to_string(), json!({"ops_per_sec": 800.0})),
+/// ]);
+///
+/// let comparisons = compare_reports(&baseline, &current, "ops_per_sec", 5.0);
+/// assert_eq!(comparisons[0].verdict, Verdict::Regressed);
+/// ```
+pub fn compare_reports(
+    baseline: &RunReport,
+    current: &RunReport,
+    metric: &str,
+    threshold_pct: f64,
+) -> Vec<Comparison> {
+    let Some(direction) = Metrics::direction_of(metric) else {
+        return Vec::new();
+    };
+
+    current
+        .results
+        .iter()
+        .filter(|result| result.is_passed())
+        .filter_map(|result| {
+            let previous = baseline
+                .result(&result.target_id)
+                .filter(|r| r.is_passed())?;
+            let before = previous.metrics.value(metric)?;
+            let after = result.metrics.value(metric)?;
+            if before == 0.0 {
+                return None;
+            }
+
+            let delta_pct = (after - before) / before.abs() * 100.0;
+            let samples = match (
+                previous.metrics.sample_summary(),
+                result.metrics.sample_summary(),
+            ) {
+                (Some(a), Some(b)) if SAMPLED_METRICS.contains(&metric) => Some((a, b)),
+                _ => None,
+            };
+            // Throughput is the inverse of the sample mean, so moves against it
+            let sign = if metric == "ops_per_sec" { -1.0 } else { 1.0 };
+            let t_statistic = samples.and_then(|(a, b)| welch_t(&a, &b)).map(|t| sign * t);
+            let degrees_of_freedom = samples.and_then(|(a, b)| welch_df(&a, &b));
+            let significant = t_statistic
+                .zip(degrees_of_freedom)
+                .map(|(t, df)| t.abs() >= t_critical_95(df));
+
+            Some(Comparison {
+                target_id: result.target_id.clone(),
+                metric: metric.to_string(),
+                baseline: before,
+                current: after,
+                delta_pct,
+                threshold_pct,
+                t_statistic,
+                degrees_of_freedom,
+                significant,
+                verdict: classify(delta_pct, direction, threshold_pct),
+            })
+        })
+        .collect()
+}
+
+/// Downgrades changes that were tested and found not significant to
+/// `Unchanged`.
+///
+/// Opt-in on top of `compare_reports`, for gates that should only fail on
+/// regressions that stand out from the noise. Untested changes keep their
+/// verdict.
+pub fn require_significance(comparisons: &mut [Comparison]) {
+    for comparison in comparisons {
+        if comparison.significant == Some(false) {
+            comparison.verdict = Verdict::Unchanged;
+        }
+    }
+}
+
+/// Classifies a relative change according to the metric direction.
+fn classify(delta_pct: f64, direction: MetricDirection, threshold_pct: f64) -> Verdict {
+    let improvement = match direction {
+        MetricDirection::HigherIsBetter => delta_pct,
+        MetricDirection::LowerIsBetter => -delta_pct,
+    };
+
+    if improvement > threshold_pct {
+        Verdict::Improved
+    } else if improvement < -threshold_pct {
+        Verdict::Regressed
+    } else {
+        Verdict::Unchanged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::result::BenchmarkResult;
+    use serde_json::json;
+
+    fn report(values: &[(&str, f64)]) -> RunReport {
+        RunReport::from_legacy(
+            values
+                .iter()
+                .map(|(id, avg_ns)| {
+                    BenchmarkResult::new(
+                        id.to_string(),
+                        json!({"avg_ns": avg_ns, "ops_per_sec": 1e9 / avg_ns}),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_verdict_follows_metric_direction() {
+        let baseline = report(&[("slower", 100.0), ("faster", 100.0), ("same", 100.0)]);
+        let current = report(&[("slower", 120.0), ("faster", 80.0), ("same", 102.0)]);
+
+        let latency = compare_reports(&baseline, &current, "avg_ns", 5.0);
+        let verdicts: Vec<_> = latency.iter().map(|c| c.verdict).collect();
+        assert_eq!(
+            verdicts,
+            [Verdict::Regressed, Verdict::Improved, Verdict::Unchanged]
+        );
+        assert!((latency[0].delta_pct - 20.0).abs() < 1e-9);
+
+        let throughput = compare_reports(&baseline, &current, "ops_per_sec", 5.0);
+        assert_eq!(throughput[0].verdict, Verdict::Regressed);
+        assert_eq!(throughput[1].verdict, Verdict::Improved);
+    }
+
+    #[test]
+    fn test_noisy_changes_are_not_significant() {
+        let with_samples = |id: &str, avg_ns: f64, raw_ns: &[f64]| {
+            RunReport::from_legacy(vec![BenchmarkResult::new(
+                id.to_string(),
+                json!({"avg_ns": avg_ns, "raw_ns": raw_ns}),
+            )])
+        };
+        let baseline = with_samples("t", 100.0, &[50.0, 150.0, 60.0, 140.0]);
+        let noisy = with_samples("t", 110.0, &[40.0, 180.0, 70.0, 150.0]);
+        let stable = with_samples("t", 110.0, &[109.0, 111.0, 110.0, 110.0]);
+        let tight = with_samples("t", 100.0, &[99.0, 101.0, 100.0, 100.0]);
+
+        let mut noisy = compare_reports(&baseline, &noisy, "avg_ns", 5.0);
+        assert_eq!(noisy[0].is_significant(), Some(false));
+        assert_eq!(noisy[0].verdict, Verdict::Regressed);
+        require_significance(&mut noisy);
+        assert_eq!(noisy[0].verdict, Verdict::Unchanged);
+
+        let mut stable = compare_reports(&tight, &stable, "avg_ns", 5.0);
+        assert_eq!(stable[0].is_significant(), Some(true));
+        assert!((stable[0].degrees_of_freedom.unwrap() - 6.0).abs() < 1e-9);
+        require_significance(&mut stable);
+        assert_eq!(stable[0].verdict, Verdict::Regressed);
+    }
+
+    #[test]
+    fn test_throughput_is_tested_on_latency_samples() {
+        let with_samples = |ops: f64, raw_ns: &[f64]| {
+            RunReport::from_legacy(vec![BenchmarkResult::new(
+                "t".to_string(),
+                json!({"ops_per_sec": ops, "raw_ns": raw_ns}),
+            )])
+        };
+        let baseline = with_samples(1000.0, &[99.0, 101.0, 100.0, 100.0]);
+        let current = with_samples(800.0, &[124.0, 126.0, 125.0, 125.0]);
+
+        let mut comparisons = compare_reports(&baseline, &current, DEFAULT_GATE_METRIC, 5.0);
+        require_significance(&mut comparisons);
+        let comparison = &comparisons[0];
+        assert!(comparison.t_statistic.unwrap() < 0.0);
+        assert_eq!(comparison.is_significant(), Some(true));
+        assert_eq!(comparison.verdict, Verdict::Regressed);
+
+        let noisy = with_samples(800.0, &[40.0, 210.0, 60.0, 190.0]);
+        let mut comparisons = compare_reports(&baseline, &noisy, DEFAULT_GATE_METRIC, 5.0);
+        assert_eq!(comparisons[0].is_significant(), Some(false));
+        require_significance(&mut comparisons);
+        assert_eq!(comparisons[0].verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn test_incomparable_targets_are_skipped() {
+        let baseline = report(&[("kept", 100.0), ("removed", 100.0)]);
+        let mut current = report(&[("kept", 100.0), ("added", 100.0)]);
+        current.results.push(BenchmarkResult::failed(
+            "removed".to_string(),
+            "boom".to_string(),
+        ));
+
+        let comparisons = compare_reports(&baseline, &current, "avg_ns", 5.0);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].target_id, "kept");
+
+        assert!(compare_reports(&baseline, &current, "samples", 5.0).is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+//! Run configuration for the benchmark suite.
+//!
+//! This module defines the effective configuration of a benchmark run. It is
+//! recorded in every `RunReport` so results can be interpreted against the
+//! settings that produced them.
+
+use super::export::{self, MetricMapping};
+use super::io::IoError;
+use super::report::ReportOptions;
+use super::retention::RetentionPolicy;
+use super::retry::RetryPolicy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Default number of warmup iterations per target.
+pub const DEFAULT_WARMUP_ITERATIONS: u32 = 3;
+
+/// Default number of measured iterations per target.
+pub const DEFAULT_MEASUREMENT_ITERATIONS: u32 = 10;
+
+/// Effective configuration of a benchmark run.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::config::RunConfig;
+///
+/// let config = RunConfig::default();
+/// assert_eq!(config.warmup_iterations, 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(description = "Effective configuration of a benchmark run.")]
+pub struct RunConfig {
+    /// Number of warmup iterations before measurement.
+    pub warmup_iterations: u32,
+
+    /// Number of measured iterations per target.
+    pub measurement_iterations: u32,
+
+    /// Retention policy applied to the history log after each run.
+    pub retention: RetentionPolicy,
+
+    /// Layout options of the generated reports.
+    pub report: ReportOptions,
+
+    /// Root of the llm-forge checkout the targets run in. Resolved from
+    /// the environment and working directory when unset (see
+    /// `adapters::resolve_forge_root`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forge_root: Option<PathBuf>,
+
+    /// Retry policy of targets without an entry in `target_retry`.
+    pub retry: RetryPolicy,
+
+    /// Retry policies of individual targets, by target ID.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub target_retry: BTreeMap<String, RetryPolicy>,
+
+    /// IDs of quarantined targets. They are run and reported as usual, but
+    /// their failures do not fail the build.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quarantine: Vec<String>,
+
+    /// Metrics written by the github-action-benchmark and BMF exports.
+    /// Empty means `export::default_mappings`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub export_metrics: Vec<MetricMapping>,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self {
+            warmup_iterations: DEFAULT_WARMUP_ITERATIONS,
+            measurement_iterations: DEFAULT_MEASUREMENT_ITERATIONS,
+            retention: RetentionPolicy::default(),
+            report: ReportOptions::default(),
+            forge_root: None,
+            retry: RetryPolicy::default(),
+            target_retry: BTreeMap::new(),
+            quarantine: Vec::new(),
+            export_metrics: Vec::new(),
+        }
+    }
+}
+
+impl RunConfig {
+    /// Loads a run configuration from a JSON file.
+    ///
+    /// Missing fields take their default values. A relative
+    /// `report.template` is resolved against the directory of the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the configuration file
+    ///
+    /// # Returns
+    ///
+    /// The parsed `RunConfig`, or an `IoError` if the file cannot be read
+    /// or `measurement_iterations` is zero.
+    pub fn load(path: &Path) -> Result<Self, IoError> {
+        let content = fs::read_to_string(path)?;
+        let mut config: Self = serde_json::from_str(&content)?;
+        if config.measurement_iterations == 0 {
+            return Err(IoError::InvalidConfig(
+                "measurement_iterations must be at least 1".to_string(),
+            ));
+        }
+        if let Some(template) = &mut config.report.template {
+            if template.is_relative() {
+                let dir = path.parent().unwrap_or_else(|| Path::new(""));
+                *template = dir.join(&*template);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Returns the retry policy of a target.
+    pub fn retry_policy(&self, target_id: &str) -> &RetryPolicy {
+        self.target_retry.get(target_id).unwrap_or(&self.retry)
+    }
+
+    /// Checks whether a target is quarantined.
+    pub fn is_quarantined(&self, target_id: &str) -> bool {
+        self.quarantine.iter().any(|id| id == target_id)
+    }
+
+    /// Returns the metric mappings of the trend exports.
+    pub fn export_mappings(&self) -> Vec<MetricMapping> {
+        export::mappings_or_default(&self.export_metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = RunConfig::default();
+        assert_eq!(config.warmup_iterations, DEFAULT_WARMUP_ITERATIONS);
+        assert_eq!(
+            config.measurement_iterations,
+            DEFAULT_MEASUREMENT_ITERATIONS
+        );
+    }
+
+    #[test]
+    fn test_load_config_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("forge-benchmarks.json");
+        fs::write(
+            &path,
+            r#"{
+                "retention": {"keep_full_runs": 20, "downsample": "daily", "compress": true},
+                "report": {"template": "templates/summary.md.hbs"}
+            }"#,
+        )
+        .unwrap();
+
+        let config = RunConfig::load(&path).unwrap();
+        assert_eq!(config.retention.keep_full_runs, Some(20));
+        assert!(config.retention.compress);
+        assert_eq!(config.warmup_iterations, DEFAULT_WARMUP_ITERATIONS);
+        assert_eq!(
+            config.report.template,
+            Some(temp_dir.path().join("templates/summary.md.hbs"))
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_zero_measurement_iterations() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("forge-benchmarks.json");
+        fs::write(&path, r#"{"measurement_iterations": 0}"#).unwrap();
+
+        assert!(matches!(
+            RunConfig::load(&path),
+            Err(IoError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_retry_and_quarantine() {
+        let config: RunConfig = serde_json::from_str(
+            r#"{
+                "retry": {"max_attempts": 2},
+                "target_retry": {"forge-cli-parse": {"max_attempts": 4, "backoff_ms": 100}},
+                "quarantine": ["forge-cli-generate"]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.retry_policy("forge-schema-validation").max_attempts,
+            2
+        );
+        assert_eq!(config.retry_policy("forge-cli-parse").max_attempts, 4);
+        assert!(config.is_quarantined("forge-cli-generate"));
+        assert!(!config.is_quarantined("forge-cli-parse"));
+    }
+
+    #[test]
+    fn test_export_mappings_override_defaults() {
+        assert_eq!(
+            RunConfig::default().export_mappings(),
+            export::default_mappings()
+        );
+
+        let config: RunConfig = serde_json::from_str(
+            r#"{"export_metrics": [
+                {"key": "ops_per_sec", "direction": "higher_is_better"},
+                {"key": "cache_hits", "unit": "hits", "direction": "higher_is_better", "measure": "hits"}
+            ]}"#,
+        )
+        .unwrap();
+        let mappings = config.export_mappings();
+
+        assert_eq!(mappings.len(), 2);
+        assert_eq!(mappings[0].unit, "ops/s");
+        assert_eq!(mappings[0].measure, "ops-per-sec");
+        assert_eq!(mappings[1].unit, "hits");
+        assert_eq!(mappings[1].measure, "hits");
+    }
+
+    #[test]
+    fn test_partial_config_uses_defaults() {
+        let config: RunConfig = serde_json::from_str(r#"{"warmup_iterations": 1}"#).unwrap();
+        assert_eq!(config.warmup_iterations, 1);
+        assert_eq!(
+            config.measurement_iterations,
+            DEFAULT_MEASUREMENT_ITERATIONS
+        );
+    }
+}
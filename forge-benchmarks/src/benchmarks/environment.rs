@@ -0,0 +1,368 @@
+//! Environment fingerprinting for benchmark runs.
+//!
+//! This module captures the machine, toolchain and source-tree state a
+//! benchmark run was executed on, so results from a laptop and from CI can
+//! be told apart when comparing runs.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// CPU details of the machine the benchmarks ran on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CpuInfo {
+    /// CPU model name (e.g. from `/proc/cpuinfo`).
+    pub model: Option<String>,
+
+    /// Number of logical cores available to the process.
+    pub cores: usize,
+
+    /// Active CPU frequency governor (Linux only).
+    pub frequency_governor: Option<String>,
+}
+
+/// Git state of the Forge checkout the benchmarks ran against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct GitInfo {
+    /// Full commit hash of `HEAD`.
+    pub commit: String,
+
+    /// Current branch name (`HEAD` when detached).
+    pub branch: Option<String>,
+
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+}
+
+/// Run-level environment metadata recorded alongside benchmark results.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use forge_benchmarks::benchmarks::environment::EnvironmentInfo;
+/// use std::path::Path;
+///
+/// let env = EnvironmentInfo::capture(Path::new("."));
+/// println!("{} cores, node {:?}", env.cpu.cores, env.node_version);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(description = "Run-level environment metadata recorded alongside benchmark results.")]
+pub struct EnvironmentInfo {
+    /// CPU model, core count and frequency governor.
+    pub cpu: CpuInfo,
+
+    /// Total physical memory in bytes.
+    pub memory_total_bytes: Option<u64>,
+
+    /// Operating system family (`linux`, `macos`, ...).
+    pub os: String,
+
+    /// CPU architecture (`x86_64`, `aarch64`, ...).
+    pub arch: String,
+
+    /// Kernel release string.
+    pub kernel_version: Option<String>,
+
+    /// Output of `node --version`.
+    pub node_version: Option<String>,
+
+    /// Output of `npm --version`.
+    pub npm_version: Option<String>,
+
+    /// `version` field of the Forge `package.json`.
+    pub forge_version: Option<String>,
+
+    /// Git commit, branch and dirty flag of the Forge checkout.
+    pub git: Option<GitInfo>,
+
+    /// Version of the forge-benchmarks crate.
+    pub crate_version: String,
+
+    /// Cargo features the crate was built with.
+    pub features: Vec<String>,
+
+    /// UTC timestamp when the environment was captured.
+    pub captured_at: DateTime<Utc>,
+}
+
+/// A single field that differs between two environment records.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentDifference {
+    /// Dotted field name (e.g. `cpu.model`).
+    pub field: String,
+
+    /// Value in the left-hand (usually baseline) environment.
+    pub left: String,
+
+    /// Value in the right-hand (usually current) environment.
+    pub right: String,
+}
+
+impl EnvironmentInfo {
+    /// Captures the current environment.
+    ///
+    /// # Arguments
+    ///
+    /// * `forge_root` - Root of the Forge checkout, used for the
+    ///   `package.json` version and git state
+    ///
+    /// # Returns
+    ///
+    /// A populated `EnvironmentInfo`. Fields that cannot be determined on
+    /// this platform are left as `None`.
+    pub fn capture(forge_root: &Path) -> Self {
+        Self {
+            cpu: CpuInfo {
+                model: read_file("/proc/cpuinfo").and_then(|s| parse_cpu_model(&s)),
+                cores: std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1),
+                frequency_governor: read_file(
+                    "/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor",
+                ),
+            },
+            memory_total_bytes: read_file("/proc/meminfo").and_then(|s| parse_mem_total(&s)),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            kernel_version: read_file("/proc/sys/kernel/osrelease")
+                .or_else(|| command_output("uname", &["-r"], None)),
+            node_version: command_output("node", &["--version"], None),
+            npm_version: command_output("npm", &["--version"], None),
+            forge_version: read_package_version(forge_root),
+            git: capture_git(forge_root),
+            crate_version: crate::VERSION.to_string(),
+            features: enabled_features(),
+            captured_at: Utc::now(),
+        }
+    }
+
+    /// Lists the fields that differ between this environment and another.
+    ///
+    /// The capture timestamp is ignored, so two captures of the same machine
+    /// and checkout compare as equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The environment to compare against
+    ///
+    /// # Returns
+    ///
+    /// One `EnvironmentDifference` per differing field, empty if the
+    /// environments match.
+    pub fn diff(&self, other: &EnvironmentInfo) -> Vec<EnvironmentDifference> {
+        let left = self.comparable_fields();
+        let right = other.comparable_fields();
+
+        left.into_iter()
+            .zip(right)
+            .filter(|((_, l), (_, r))| l != r)
+            .map(|((field, l), (_, r))| EnvironmentDifference {
+                field: field.to_string(),
+                left: l,
+                right: r,
+            })
+            .collect()
+    }
+
+    /// Checks whether two environments are equivalent for comparison purposes.
+    pub fn is_comparable_to(&self, other: &EnvironmentInfo) -> bool {
+        self.diff(other).is_empty()
+    }
+
+    /// Summarizes the machine and toolchain as one stable string.
+    ///
+    /// Covers the comparable fields except the git state, which describes
+    /// the checkout rather than the machine, so callers hashing the
+    /// checkout's files are not invalidated by every commit.
+    pub fn fingerprint(&self) -> String {
+        self.comparable_fields()
+            .into_iter()
+            .filter(|(field, _)| !field.starts_with("git."))
+            .map(|(field, value)| format!("{}={}\n", field, value))
+            .collect()
+    }
+
+    fn comparable_fields(&self) -> Vec<(&'static str, String)> {
+        let git = self.git.as_ref();
+        vec![
+            ("cpu.model", display_opt(&self.cpu.model)),
+            ("cpu.cores", self.cpu.cores.to_string()),
+            (
+                "cpu.frequency_governor",
+                display_opt(&self.cpu.frequency_governor),
+            ),
+            ("memory_total_bytes", display_opt(&self.memory_total_bytes)),
+            ("os", self.os.clone()),
+            ("arch", self.arch.clone()),
+            ("kernel_version", display_opt(&self.kernel_version)),
+            ("node_version", display_opt(&self.node_version)),
+            ("npm_version", display_opt(&self.npm_version)),
+            ("forge_version", display_opt(&self.forge_version)),
+            ("git.commit", display_opt(&git.map(|g| g.commit.clone()))),
+            (
+                "git.branch",
+                display_opt(&git.and_then(|g| g.branch.clone())),
+            ),
+            ("git.dirty", display_opt(&git.map(|g| g.dirty))),
+            ("crate_version", self.crate_version.clone()),
+            ("features", self.features.join(",")),
+        ]
+    }
+}
+
+/// Returns the cargo features this crate was built with.
+fn enabled_features() -> Vec<String> {
+    #[cfg(any(
+        feature = "infra-config",
+        feature = "infra-metrics",
+        feature = "infra-cache",
+        feature = "infra-otel"
+    ))]
+    return crate::infra::enabled_features()
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    #[cfg(not(any(
+        feature = "infra-config",
+        feature = "infra-metrics",
+        feature = "infra-cache",
+        feature = "infra-otel"
+    )))]
+    return Vec::new();
+}
+
+fn display_opt<T: ToString>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Reads a file and returns its trimmed contents, or `None` if unavailable.
+fn read_file(path: &str) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Runs a command and returns its trimmed stdout if it succeeded.
+fn command_output(program: &str, args: &[&str], cwd: Option<&Path>) -> Option<String> {
+    let mut command = Command::new(program);
+    command.args(args);
+    if let Some(dir) = cwd {
+        command.current_dir(dir);
+    }
+
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Extracts the CPU model name from `/proc/cpuinfo` contents.
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .find(|line| line.starts_with("model name") || line.starts_with("Model"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Extracts `MemTotal` in bytes from `/proc/meminfo` contents.
+fn parse_mem_total(meminfo: &str) -> Option<u64> {
+    let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Reads the `version` field from `package.json` in the given directory.
+fn read_package_version(forge_root: &Path) -> Option<String> {
+    let content = fs::read_to_string(forge_root.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    package
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
+/// Captures git commit, branch and dirty state for the given directory.
+fn capture_git(dir: &Path) -> Option<GitInfo> {
+    let commit = command_output("git", &["rev-parse", "HEAD"], Some(dir))?;
+    let branch = command_output("git", &["rev-parse", "--abbrev-ref", "HEAD"], Some(dir));
+    let dirty = command_output("git", &["status", "--porcelain"], Some(dir))
+        .map(|s| !s.is_empty())
+        .unwrap_or(false);
+
+    Some(GitInfo {
+        commit,
+        branch,
+        dirty,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_capture_environment() {
+        let env = EnvironmentInfo::capture(Path::new("."));
+
+        assert!(env.cpu.cores >= 1);
+        assert_eq!(env.crate_version, crate::VERSION);
+        assert_eq!(env.os, std::env::consts::OS);
+    }
+
+    #[test]
+    fn test_parse_proc_files() {
+        let cpuinfo = "processor\t: 0\nmodel name\t: Example CPU @ 3.00GHz\n";
+        let meminfo = "MemTotal:       16384 kB\nMemFree:        1024 kB\n";
+
+        assert_eq!(
+            parse_cpu_model(cpuinfo),
+            Some("Example CPU @ 3.00GHz".to_string())
+        );
+        assert_eq!(parse_mem_total(meminfo), Some(16384 * 1024));
+        assert_eq!(parse_mem_total(""), None);
+    }
+
+    #[test]
+    fn test_read_package_version() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"name": "@llm-dev-ops/llm-forge", "version": "1.2.3"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_package_version(temp_dir.path()),
+            Some("1.2.3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_diff_environments() {
+        let base = EnvironmentInfo::capture(Path::new("."));
+        let mut other = base.clone();
+        other.captured_at = Utc::now() + chrono::Duration::hours(1);
+
+        assert!(base.is_comparable_to(&other));
+
+        other.node_version = Some("v99.0.0".to_string());
+        let diff = base.diff(&other);
+
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].field, "node_version");
+        assert_eq!(diff[0].right, "v99.0.0");
+    }
+}
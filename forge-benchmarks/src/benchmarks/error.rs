@@ -0,0 +1,216 @@
+//! Benchmark target errors - Typed failure taxonomy.
+//!
+//! This module defines `BenchError`, returned by `BenchTarget::run` and
+//! stored in the result of a failed target. Each variant is a distinct class
+//! of failure, so reports and the regression gate can tell a missing
+//! toolchain from a crashing or hanging command.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::process::ExitStatus;
+use thiserror::Error;
+
+/// Number of trailing stderr lines kept on a failed command.
+pub const STDERR_TAIL_LINES: usize = 20;
+
+/// Why a benchmark target failed.
+///
+/// Serializes with a `kind` tag, e.g.
+/// `{"kind": "timeout", "command": "npx vitest run", "timeout_ms": 300000}`.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::error::BenchError;
+///
+/// let error = BenchError::Precondition {
+///     message: "node is not installed".to_string(),
+/// };
+///
+/// assert_eq!(error.kind(), "precondition");
+/// assert!(error.is_environmental());
+/// assert_eq!(error.to_string(), "precondition not met: node is not installed");
+/// ```
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[schemars(description = "Typed cause of a failed benchmark target.")]
+pub enum BenchError {
+    /// The command could not be started, e.g. `npx` is not on `PATH`.
+    #[error("failed to spawn `{command}`: {message}")]
+    Spawn { command: String, message: String },
+
+    /// The command exited with a non-zero status.
+    #[error("`{command}` exited with status {code}")]
+    NonZeroExit {
+        command: String,
+        code: i32,
+        /// Trailing lines of the command's stderr.
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        stderr: String,
+    },
+
+    /// The command was killed by a signal.
+    #[error("`{command}` was killed by signal {signal}")]
+    Signal { command: String, signal: i32 },
+
+    /// The command did not finish in time and was killed.
+    #[error("`{command}` timed out after {timeout_ms} ms")]
+    Timeout { command: String, timeout_ms: u64 },
+
+    /// The command's output could not be parsed.
+    #[error("failed to parse output of `{command}`: {message}")]
+    Parse { command: String, message: String },
+
+    /// Preparing the target failed.
+    #[error("setup failed: {message}")]
+    Setup { message: String },
+
+    /// Cleaning up after the target failed.
+    #[error("teardown failed: {message}")]
+    Teardown { message: String },
+
+    /// The environment does not meet a requirement of the target.
+    #[error("precondition not met: {message}")]
+    Precondition { message: String },
+}
+
+impl BenchError {
+    /// Classifies the exit status of a finished command.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command line, as shown in messages
+    /// * `status` - Exit status of the command
+    /// * `stderr` - Captured stderr; only the trailing lines are kept
+    ///
+    /// # Returns
+    ///
+    /// `None` if the command succeeded, otherwise `NonZeroExit` or `Signal`.
+    pub fn from_exit_status(command: &str, status: ExitStatus, stderr: &str) -> Option<Self> {
+        if status.success() {
+            return None;
+        }
+        if let Some(code) = status.code() {
+            return Some(BenchError::NonZeroExit {
+                command: command.to_string(),
+                code,
+                stderr: tail(stderr.trim_end(), STDERR_TAIL_LINES),
+            });
+        }
+
+        #[cfg(unix)]
+        if let Some(signal) = std::os::unix::process::ExitStatusExt::signal(&status) {
+            return Some(BenchError::Signal {
+                command: command.to_string(),
+                signal,
+            });
+        }
+
+        Some(BenchError::NonZeroExit {
+            command: command.to_string(),
+            code: -1,
+            stderr: tail(stderr.trim_end(), STDERR_TAIL_LINES),
+        })
+    }
+
+    /// Returns the serialized `kind` tag, e.g. `non_zero_exit`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BenchError::Spawn { .. } => "spawn",
+            BenchError::NonZeroExit { .. } => "non_zero_exit",
+            BenchError::Signal { .. } => "signal",
+            BenchError::Timeout { .. } => "timeout",
+            BenchError::Parse { .. } => "parse",
+            BenchError::Setup { .. } => "setup",
+            BenchError::Teardown { .. } => "teardown",
+            BenchError::Precondition { .. } => "precondition",
+        }
+    }
+
+    /// Returns `true` if the failure lies with the machine rather than the
+    /// code under benchmark: a tool could not be started or a precondition
+    /// was not met.
+    pub fn is_environmental(&self) -> bool {
+        matches!(
+            self,
+            BenchError::Spawn { .. } | BenchError::Precondition { .. }
+        )
+    }
+
+    /// Returns the captured stderr, if the failure has any.
+    pub fn stderr(&self) -> Option<&str> {
+        match self {
+            BenchError::NonZeroExit { stderr, .. } if !stderr.is_empty() => Some(stderr),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the last `lines` lines of a text.
+pub(crate) fn tail(text: &str, lines: usize) -> String {
+    let all: Vec<&str> = text.lines().collect();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_serialized_with_kind_tag() {
+        let error = BenchError::Timeout {
+            command: "npx vitest run".to_string(),
+            timeout_ms: 1000,
+        };
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(
+            value,
+            json!({"kind": "timeout", "command": "npx vitest run", "timeout_ms": 1000})
+        );
+        assert_eq!(value["kind"], error.kind());
+        assert_eq!(serde_json::from_value::<BenchError>(value).unwrap(), error);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_exit_status() {
+        use std::os::unix::process::ExitStatusExt;
+
+        assert_eq!(
+            BenchError::from_exit_status("true", ExitStatus::from_raw(0), ""),
+            None
+        );
+
+        let stderr: Vec<String> = (0..30).map(|i| format!("line {}", i)).collect();
+        let exited = BenchError::from_exit_status(
+            "npx llm-forge parse",
+            ExitStatus::from_raw(2 << 8),
+            &stderr.join("\n"),
+        )
+        .unwrap();
+        assert_eq!(exited.kind(), "non_zero_exit");
+        assert_eq!(
+            exited.to_string(),
+            "`npx llm-forge parse` exited with status 2"
+        );
+        let kept = exited.stderr().unwrap();
+        assert!(kept.starts_with("line 10\n") && kept.ends_with("line 29"));
+
+        let killed = BenchError::from_exit_status("npx", ExitStatus::from_raw(9), "").unwrap();
+        assert_eq!(
+            killed,
+            BenchError::Signal {
+                command: "npx".to_string(),
+                signal: 9
+            }
+        );
+        assert!(!killed.is_environmental());
+    }
+
+    #[test]
+    fn test_tail() {
+        assert_eq!(tail("a\nb\nc", 2), "b\nc");
+        assert_eq!(tail("a", 5), "a");
+    }
+}
@@ -0,0 +1,384 @@
+//! Typed progress events published by the runner.
+//!
+//! The runner publishes a `RunEvent` when a run starts, when each target
+//! starts, for every measured sample, when a failed attempt of a target is
+//! retried, when each target finishes, fails or is skipped, and when the run
+//! finishes. Observers implement `RunObserver` and are
+//! attached to an `EventBus`.
+//!
+//! Publishing only pushes the event onto a channel; observers are called
+//! from a dedicated dispatcher thread, so slow observers (terminal output,
+//! file writes, network) never run inside a measured section.
+//!
+//! Adapters publish samples with `record_sample`, which reaches the
+//! publisher scoped to the task running the target, so `BenchTarget`
+//! implementations do not need to carry it around.
+
+use super::error::BenchError;
+use super::result::BenchmarkResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use uuid::Uuid;
+
+tokio::task_local! {
+    static CURRENT: (EventPublisher, String);
+}
+
+/// Something that happened during a benchmark run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RunEvent {
+    /// A run started.
+    RunStarted { run_id: Uuid, at: DateTime<Utc> },
+
+    /// A target is about to run.
+    TargetStarted {
+        target_id: String,
+        /// Zero-based position of the target in the run.
+        index: usize,
+        /// Number of targets in the run.
+        total: usize,
+        at: DateTime<Utc>,
+    },
+
+    /// A target recorded a measured iteration.
+    SampleRecorded {
+        target_id: String,
+        /// One-based number of the iteration.
+        iteration: u32,
+        /// Number of measured iterations.
+        iterations: u32,
+        /// Duration of the iteration in nanoseconds.
+        sample_ns: f64,
+        /// End of the iteration.
+        at: DateTime<Utc>,
+    },
+
+    /// An attempt of a target failed and the target will be run again.
+    TargetRetrying {
+        target_id: String,
+        /// One-based number of the failed attempt.
+        attempt: u32,
+        /// Number of attempts the retry policy allows.
+        max_attempts: u32,
+        error: String,
+        at: DateTime<Utc>,
+    },
+
+    /// A target completed successfully.
+    TargetFinished { result: Box<BenchmarkResult> },
+
+    /// A target failed.
+    TargetFailed {
+        target_id: String,
+        error: String,
+        /// Typed cause of the failure, if the target reported one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        failure: Option<BenchError>,
+        at: DateTime<Utc>,
+    },
+
+    /// A target was not run because a precondition was not met.
+    TargetSkipped {
+        target_id: String,
+        reason: String,
+        at: DateTime<Utc>,
+    },
+
+    /// A run finished.
+    RunFinished {
+        run_id: Uuid,
+        passed: usize,
+        failed: usize,
+        #[serde(default)]
+        skipped: usize,
+        at: DateTime<Utc>,
+    },
+}
+
+impl RunEvent {
+    /// Creates the finish event of a target from its result.
+    pub fn target_done(result: &BenchmarkResult) -> Self {
+        if result.is_failed() {
+            RunEvent::TargetFailed {
+                target_id: result.target_id.clone(),
+                error: result
+                    .metrics
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "benchmark failed".to_string()),
+                failure: result.failure().cloned(),
+                at: result.timestamp,
+            }
+        } else if result.is_skipped() {
+            RunEvent::TargetSkipped {
+                target_id: result.target_id.clone(),
+                reason: result.metrics.skip_reason.clone().unwrap_or_default(),
+                at: result.timestamp,
+            }
+        } else {
+            RunEvent::TargetFinished {
+                result: Box::new(result.clone()),
+            }
+        }
+    }
+}
+
+/// Receiver of run events.
+///
+/// Observers are called in publication order from the dispatcher thread of
+/// an `EventBus`.
+pub trait RunObserver: Send + Sync {
+    /// Handles one event.
+    fn on_event(&self, event: &RunEvent);
+}
+
+/// Cheap, cloneable handle used to publish events.
+///
+/// A disabled publisher drops all events.
+#[derive(Debug, Clone, Default)]
+pub struct EventPublisher {
+    sender: Option<Sender<RunEvent>>,
+}
+
+impl EventPublisher {
+    /// Creates a publisher that drops all events.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Publishes an event without waiting for observers.
+    pub fn publish(&self, event: RunEvent) {
+        if let Some(sender) = &self.sender {
+            // The bus only goes away after the run, so a send error just
+            // means nobody is listening any more
+            let _ = sender.send(event);
+        }
+    }
+}
+
+/// Delivers published events to observers on a background thread.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::events::{EventBus, RunEvent, RunObserver};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// #[derive(Default)]
+/// struct Counter(AtomicUsize);
+///
+/// impl RunObserver for Counter {
+///     fn on_event(&self, _event: &RunEvent) {
+///         self.0.fetch_add(1, Ordering::SeqCst);
+///     }
+/// }
+///
+/// let counter = Arc::new(Counter::default());
+/// let bus = EventBus::start(vec![counter.clone()]);
+/// bus.publisher().publish(RunEvent::TargetFailed {
+///     target_id: "parse".to_string(),
+///     error: "npx not found".to_string(),
+///     failure: None,
+///     at: chrono::Utc::now(),
+/// });
+/// bus.finish();
+///
+/// assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+/// ```
+pub struct EventBus {
+    publisher: EventPublisher,
+    dispatcher: JoinHandle<()>,
+}
+
+impl EventBus {
+    /// Starts the dispatcher thread for `observers`.
+    pub fn start(observers: Vec<Arc<dyn RunObserver>>) -> Self {
+        let (sender, receiver) = mpsc::channel::<RunEvent>();
+        let dispatcher = thread::spawn(move || {
+            for event in receiver {
+                for observer in &observers {
+                    observer.on_event(&event);
+                }
+            }
+        });
+
+        Self {
+            publisher: EventPublisher {
+                sender: Some(sender),
+            },
+            dispatcher,
+        }
+    }
+
+    /// Returns a publisher feeding this bus.
+    pub fn publisher(&self) -> EventPublisher {
+        self.publisher.clone()
+    }
+
+    /// Waits until every event published so far has been delivered.
+    ///
+    /// Publishers cloned from this bus must be dropped first, otherwise
+    /// this waits for them.
+    pub fn finish(self) {
+        drop(self.publisher);
+        let _ = self.dispatcher.join();
+    }
+}
+
+/// Runs `future` as target `target_id`, with `record_sample` inside it
+/// publishing to `publisher`.
+pub async fn scope<F: std::future::Future>(
+    publisher: EventPublisher,
+    target_id: String,
+    future: F,
+) -> F::Output {
+    CURRENT.scope((publisher, target_id), future).await
+}
+
+/// Publishes a measured iteration of the target running in this task.
+///
+/// Does nothing outside `scope`.
+///
+/// # Arguments
+///
+/// * `iteration` - One-based number of the iteration
+/// * `iterations` - Number of measured iterations
+/// * `sample` - Duration of the iteration
+pub fn record_sample(iteration: u32, iterations: u32, sample: Duration) {
+    let _ = CURRENT.try_with(|(publisher, target_id)| {
+        publisher.publish(RunEvent::SampleRecorded {
+            target_id: target_id.clone(),
+            iteration,
+            iterations,
+            sample_ns: sample.as_nanos() as f64,
+            at: Utc::now(),
+        })
+    });
+}
+
+/// Observer writing every event as one JSON line.
+pub struct JsonLinesObserver<W> {
+    out: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonLinesObserver<W> {
+    /// Creates an observer writing to `out`.
+    pub fn new(out: W) -> Self {
+        Self {
+            out: Mutex::new(out),
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        match self.out.into_inner() {
+            Ok(out) => out,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl<W: Write + Send> RunObserver for JsonLinesObserver<W> {
+    fn on_event(&self, event: &RunEvent) {
+        let Ok(line) = serde_json::to_string(event) else {
+            return;
+        };
+        if let Ok(mut out) = self.out.lock() {
+            let _ = writeln!(out, "{}", line);
+            let _ = out.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<RunEvent>>);
+
+    impl RunObserver for Recorder {
+        fn on_event(&self, event: &RunEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_samples_reach_scoped_target_only() {
+        let recorder = Arc::new(Recorder::default());
+        let bus = EventBus::start(vec![recorder.clone()]);
+
+        record_sample(1, 2, Duration::from_millis(1));
+        scope(bus.publisher(), "parse".to_string(), async {
+            record_sample(1, 2, Duration::from_millis(1));
+            record_sample(2, 2, Duration::from_millis(2));
+        })
+        .await;
+        record_sample(3, 3, Duration::from_millis(1));
+        bus.finish();
+
+        let events = recorder.0.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[1],
+            RunEvent::SampleRecorded { target_id, iteration: 2, sample_ns, .. }
+                if target_id == "parse" && *sample_ns == 2_000_000.0
+        ));
+    }
+
+    #[test]
+    fn test_json_lines_log() {
+        let log = Arc::new(JsonLinesObserver::new(Vec::new()));
+        let bus = EventBus::start(vec![log.clone()]);
+        let publisher = bus.publisher();
+        publisher.publish(RunEvent::TargetStarted {
+            target_id: "parse".to_string(),
+            index: 0,
+            total: 1,
+            at: Utc::now(),
+        });
+        publisher.publish(RunEvent::target_done(&BenchmarkResult::failed(
+            "parse".to_string(),
+            "npx not found".to_string(),
+        )));
+        drop(publisher);
+        bus.finish();
+
+        let log = Arc::try_unwrap(log).ok().unwrap().into_inner();
+        let lines: Vec<serde_json::Value> = String::from_utf8(log)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "target_started");
+        assert_eq!(lines[1]["event"], "target_failed");
+        assert_eq!(lines[1]["error"], "npx not found");
+    }
+
+    #[test]
+    fn test_skipped_target_event() {
+        let event = RunEvent::target_done(&BenchmarkResult::skipped(
+            "parse".to_string(),
+            "`npx` not found on PATH".to_string(),
+        ));
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["event"], "target_skipped");
+        assert_eq!(value["reason"], "`npx` not found on PATH");
+    }
+
+    #[test]
+    fn test_disabled_publisher_drops_events() {
+        EventPublisher::disabled().publish(RunEvent::RunStarted {
+            run_id: Uuid::nil(),
+            at: Utc::now(),
+        });
+    }
+}
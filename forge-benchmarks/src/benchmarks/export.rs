@@ -0,0 +1,334 @@
+//! Exports for external trend-tracking tools.
+//!
+//! This module converts a `RunReport` into the `customSmallerIsBetter` /
+//! `customBiggerIsBetter` JSON accepted by github-action-benchmark and into
+//! Bencher Metric Format (BMF). Which metrics are exported, with which unit
+//! and improvement direction, is described by a list of `MetricMapping`s,
+//! configured via `RunConfig::export_metrics`.
+
+use super::metrics::{MetricDirection, Metrics};
+use super::result::BenchmarkResult;
+use super::run::RunReport;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How one metric is exported.
+///
+/// In a configuration file `unit` and `measure` may be omitted; they default
+/// to the unit of a well-known metric and to the key with `-` for `_`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MetricMapping {
+    /// Metric key in `Metrics` (e.g. `avg_ns`).
+    pub key: String,
+
+    /// Unit label written with the value.
+    #[serde(default)]
+    pub unit: String,
+
+    /// Which direction of change is an improvement.
+    pub direction: MetricDirection,
+
+    /// BMF measure slug the metric is reported under.
+    #[serde(default)]
+    pub measure: String,
+
+    /// Metric providing the lower bound of the value, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lower_key: Option<String>,
+
+    /// Metric providing the upper bound of the value, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upper_key: Option<String>,
+}
+
+impl MetricMapping {
+    /// Creates a mapping for a metric without bounds.
+    ///
+    /// The unit is taken from the well-known metric table when the key is
+    /// known, so only extension metrics need an explicit `unit`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Metric key in `Metrics`
+    /// * `unit` - Fallback unit label for extension metrics
+    /// * `direction` - Which direction of change is an improvement
+    pub fn new(key: &str, unit: &str, direction: MetricDirection) -> Self {
+        Self {
+            key: key.to_string(),
+            unit: Metrics::unit_of(key)
+                .map(|u| u.symbol().to_string())
+                .unwrap_or_else(|| unit.to_string()),
+            direction,
+            measure: key.replace('_', "-"),
+            lower_key: None,
+            upper_key: None,
+        }
+    }
+}
+
+/// Returns the configured export mappings, or the defaults if none are
+/// configured.
+///
+/// Omitted units and measures are filled in as by `MetricMapping::new`,
+/// with an omitted unit of an extension metric left empty.
+///
+/// # Arguments
+///
+/// * `configured` - Mappings from `RunConfig::export_metrics`
+pub fn mappings_or_default(configured: &[MetricMapping]) -> Vec<MetricMapping> {
+    if configured.is_empty() {
+        return default_mappings();
+    }
+
+    configured
+        .iter()
+        .map(|mapping| {
+            let defaults = MetricMapping::new(&mapping.key, &mapping.unit, mapping.direction);
+            MetricMapping {
+                unit: if mapping.unit.is_empty() {
+                    defaults.unit
+                } else {
+                    mapping.unit.clone()
+                },
+                measure: if mapping.measure.is_empty() {
+                    defaults.measure
+                } else {
+                    mapping.measure.clone()
+                },
+                ..mapping.clone()
+            }
+        })
+        .collect()
+}
+
+/// Returns the default export mappings.
+///
+/// Latency (`avg_ns`, bounded by `min_ns`/`max_ns`) is exported as the BMF
+/// `latency` measure and throughput (`ops_per_sec`) as `throughput`.
+pub fn default_mappings() -> Vec<MetricMapping> {
+    vec![
+        MetricMapping {
+            measure: "latency".to_string(),
+            lower_key: Some("min_ns".to_string()),
+            upper_key: Some("max_ns".to_string()),
+            ..MetricMapping::new("avg_ns", "ns", MetricDirection::LowerIsBetter)
+        },
+        MetricMapping {
+            measure: "throughput".to_string(),
+            ..MetricMapping::new("ops_per_sec", "ops/s", MetricDirection::HigherIsBetter)
+        },
+    ]
+}
+
+/// One entry of a github-action-benchmark `custom*IsBetter` file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GithubBenchmarkEntry {
+    /// Benchmark name, `<target_id> - <metric>`.
+    pub name: String,
+
+    /// Unit label.
+    pub unit: String,
+
+    /// Measured value.
+    pub value: f64,
+
+    /// Free-form variation of the value (e.g. `1200..1500`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<String>,
+
+    /// Free-form additional information shown in the tooltip.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<String>,
+}
+
+/// One measure of a benchmark in Bencher Metric Format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BmfMetric {
+    /// Measured value.
+    pub value: f64,
+
+    /// Lower bound of the value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lower_value: Option<f64>,
+
+    /// Upper bound of the value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upper_value: Option<f64>,
+}
+
+/// Bencher Metric Format document: benchmark name -> measure -> metric.
+pub type BmfReport = BTreeMap<String, BTreeMap<String, BmfMetric>>;
+
+/// Builds the github-action-benchmark entries of one direction.
+///
+/// github-action-benchmark takes one file per direction, so this is called
+/// once with `LowerIsBetter` (`customSmallerIsBetter`) and once with
+/// `HigherIsBetter` (`customBiggerIsBetter`). Failed targets and missing
+/// metrics are skipped.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to export
+/// * `mappings` - Metrics to export
+/// * `direction` - Direction of the file being produced
+///
+/// # Returns
+///
+/// The entries, in result order.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::export::{default_mappings, github_benchmark_entries};
+/// use forge_benchmarks::benchmarks::metrics::MetricDirection;
+/// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use forge_benchmarks::benchmarks::run::RunReport;
+/// use serde_json::json;
+///
+/// let report = RunReport::from_legacy(vec![
+///     BenchmarkResult::new("parse".to_string(), json!({"avg_ns": 1500.0, "ops_per_sec": 666666.0})),
+/// ]);
+///
+/// let smaller = github_benchmark_entries(&report, &default_mappings(), MetricDirection::LowerIsBetter);
+/// assert_eq!(smaller[0].name, "parse - avg_ns");
+/// assert_eq!(smaller[0].unit, "ns");
+/// ```
+pub fn github_benchmark_entries(
+    report: &RunReport,
+    mappings: &[MetricMapping],
+    direction: MetricDirection,
+) -> Vec<GithubBenchmarkEntry> {
+    let mut entries = Vec::new();
+    for result in report.results.iter().filter(|r| r.is_passed()) {
+        for mapping in mappings.iter().filter(|m| m.direction == direction) {
+            let Some(value) = result.metrics.value(&mapping.key) else {
+                continue;
+            };
+            let (lower, upper) = bounds(result, mapping);
+            let range = match (lower, upper) {
+                (Some(lower), Some(upper)) => Some(format!("{}..{}", lower, upper)),
+                _ => None,
+            };
+            entries.push(GithubBenchmarkEntry {
+                name: format!("{} - {}", result.target_id, mapping.key),
+                unit: mapping.unit.clone(),
+                value,
+                range,
+                extra: result.metrics.samples.map(|n| format!("{} samples", n)),
+            });
+        }
+    }
+    entries
+}
+
+/// Builds a Bencher Metric Format document.
+///
+/// Failed targets and missing metrics are skipped.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to export
+/// * `mappings` - Metrics to export, each under its `measure` slug
+///
+/// # Returns
+///
+/// The BMF document, keyed by target ID.
+pub fn bmf_report(report: &RunReport, mappings: &[MetricMapping]) -> BmfReport {
+    let mut bmf = BmfReport::new();
+    for result in report.results.iter().filter(|r| r.is_passed()) {
+        let measures: BTreeMap<_, _> = mappings
+            .iter()
+            .filter_map(|mapping| {
+                let value = result.metrics.value(&mapping.key)?;
+                let (lower_value, upper_value) = bounds(result, mapping);
+                Some((
+                    mapping.measure.clone(),
+                    BmfMetric {
+                        value,
+                        lower_value,
+                        upper_value,
+                    },
+                ))
+            })
+            .collect();
+        if !measures.is_empty() {
+            bmf.insert(result.target_id.clone(), measures);
+        }
+    }
+    bmf
+}
+
+/// Looks up the lower and upper bound of a mapped metric.
+fn bounds(result: &BenchmarkResult, mapping: &MetricMapping) -> (Option<f64>, Option<f64>) {
+    let lookup = |key: &Option<String>| key.as_deref().and_then(|k| result.metrics.value(k));
+    (lookup(&mapping.lower_key), lookup(&mapping.upper_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_report() -> RunReport {
+        RunReport::from_legacy(vec![
+            BenchmarkResult::new(
+                "parse".to_string(),
+                json!({
+                    "avg_ns": 1500.0,
+                    "min_ns": 1200.0,
+                    "max_ns": 1900.0,
+                    "ops_per_sec": 666666.0,
+                    "samples": 10,
+                    "cache_hits": 7
+                }),
+            ),
+            BenchmarkResult::failed("broken".to_string(), "boom".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_github_benchmark_files_split_by_direction() {
+        let report = sample_report();
+        let mappings = default_mappings();
+
+        let smaller = github_benchmark_entries(&report, &mappings, MetricDirection::LowerIsBetter);
+        let bigger = github_benchmark_entries(&report, &mappings, MetricDirection::HigherIsBetter);
+
+        assert_eq!(
+            serde_json::to_value(&smaller).unwrap(),
+            json!([{
+                "name": "parse - avg_ns",
+                "unit": "ns",
+                "value": 1500.0,
+                "range": "1200..1900",
+                "extra": "10 samples"
+            }])
+        );
+        assert_eq!(bigger.len(), 1);
+        assert_eq!(bigger[0].unit, "ops/s");
+    }
+
+    #[test]
+    fn test_bmf_report() {
+        let mut mappings = default_mappings();
+        mappings.push(MetricMapping::new(
+            "cache_hits",
+            "hits",
+            MetricDirection::HigherIsBetter,
+        ));
+
+        let bmf = bmf_report(&sample_report(), &mappings);
+
+        assert_eq!(
+            serde_json::to_value(&bmf).unwrap(),
+            json!({
+                "parse": {
+                    "cache-hits": {"value": 7.0},
+                    "latency": {"value": 1500.0, "lower_value": 1200.0, "upper_value": 1900.0},
+                    "throughput": {"value": 666666.0}
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,671 @@
+//! Query API over the benchmark history log.
+//!
+//! This module reads `history.jsonl` (and its rotated segments, see
+//! `retention`) back in a streaming fashion and provides filtering by target,
+//! date range, git commit and run ID, plus per-metric time series for trend
+//! analysis and detection of flaky targets.
+
+use super::io::{IoError, HISTORY_FILE, OUTPUT_DIR};
+use super::result::BenchmarkResult;
+use super::retention::rotated_segments;
+use super::run::RunReport;
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Split};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use uuid::Uuid;
+
+/// A single line of the history log.
+///
+/// Lines written before run grouping hold one `BenchmarkResult` each.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum HistoryLine {
+    Report(Box<RunReport>),
+    Legacy(Box<BenchmarkResult>),
+}
+
+impl From<HistoryLine> for RunReport {
+    fn from(line: HistoryLine) -> Self {
+        match line {
+            HistoryLine::Report(report) => *report,
+            HistoryLine::Legacy(result) => RunReport::from_legacy(vec![*result]),
+        }
+    }
+}
+
+/// Streaming reader over the runs recorded in a history log.
+///
+/// Reads rotated segments (oldest first, gzip-compressed or not) followed by
+/// the active `history.jsonl`. Each item is one run; legacy single-result
+/// lines are wrapped via `RunReport::from_legacy`. Corrupt or truncated
+/// lines, e.g. from an interrupted write, are skipped with a warning.
+pub struct HistoryReader {
+    pending: VecDeque<PathBuf>,
+    current: Option<Segment>,
+    skipped_lines: usize,
+}
+
+/// A history file being read line by line.
+struct Segment {
+    path: PathBuf,
+    lines: Split<Box<dyn BufRead>>,
+    line_number: usize,
+}
+
+impl HistoryReader {
+    /// Opens the history log and its rotated segments in the canonical
+    /// output directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_path` - Base path where results are stored
+    ///
+    /// # Returns
+    ///
+    /// A `HistoryReader`, or `IoError::DirectoryNotFound` if no history exists.
+    pub fn open(base_path: &str) -> Result<Self, IoError> {
+        let active = history_path(base_path);
+        let mut files = rotated_segments(base_path)?;
+        if active.exists() {
+            files.push(active.clone());
+        }
+
+        if files.is_empty() {
+            return Err(IoError::DirectoryNotFound(
+                active.to_string_lossy().to_string(),
+            ));
+        }
+
+        Ok(Self {
+            pending: files.into(),
+            current: None,
+            skipped_lines: 0,
+        })
+    }
+
+    /// Opens a single history file (`.jsonl` or `.jsonl.gz`).
+    pub fn open_file(path: &Path) -> Result<Self, IoError> {
+        if !path.exists() {
+            return Err(IoError::DirectoryNotFound(
+                path.to_string_lossy().to_string(),
+            ));
+        }
+
+        Ok(Self {
+            pending: VecDeque::from([path.to_path_buf()]),
+            current: None,
+            skipped_lines: 0,
+        })
+    }
+
+    /// Returns the number of lines skipped so far because they could not
+    /// be read or parsed.
+    pub fn skipped_lines(&self) -> usize {
+        self.skipped_lines
+    }
+
+    fn open_segment(path: PathBuf) -> Result<Segment, IoError> {
+        let file = File::open(&path)?;
+        let reader: Box<dyn BufRead> = if path.extension().is_some_and(|ext| ext == "gz") {
+            Box::new(BufReader::new(GzDecoder::new(file)))
+        } else {
+            Box::new(BufReader::new(file))
+        };
+
+        Ok(Segment {
+            path,
+            lines: reader.split(b'\n'),
+            line_number: 0,
+        })
+    }
+}
+
+impl Iterator for HistoryReader {
+    type Item = Result<RunReport, IoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let segment = match &mut self.current {
+                Some(segment) => segment,
+                None => {
+                    let path = self.pending.pop_front()?;
+                    match Self::open_segment(path) {
+                        Ok(segment) => self.current.insert(segment),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+            };
+
+            segment.line_number += 1;
+            match segment.lines.next() {
+                None => self.current = None,
+                Some(Err(e)) => {
+                    warn!(
+                        "Stopped reading {} at line {}: {}",
+                        segment.path.display(),
+                        segment.line_number,
+                        e
+                    );
+                    self.skipped_lines += 1;
+                    self.current = None;
+                }
+                Some(Ok(line)) => {
+                    if line.iter().all(u8::is_ascii_whitespace) {
+                        continue;
+                    }
+                    match serde_json::from_slice::<HistoryLine>(&line) {
+                        Ok(parsed) => return Some(Ok(parsed.into())),
+                        Err(e) => {
+                            warn!(
+                                "Skipping corrupt line {} in {}: {}",
+                                segment.line_number,
+                                segment.path.display(),
+                                e
+                            );
+                            self.skipped_lines += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Filter criteria for history queries.
+///
+/// All criteria are optional and combined with AND. When `target_id` is set,
+/// matching runs only keep the result for that target.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use forge_benchmarks::benchmarks::history::{query_history, HistoryQuery};
+///
+/// let query = HistoryQuery {
+///     target_id: Some("forge-cli-parse".to_string()),
+///     last_n: Some(10),
+///     ..HistoryQuery::default()
+/// };
+///
+/// for run in query_history(".", &query).unwrap() {
+///     println!("{} {}", run.run_id, run.started_at);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    /// Only include runs containing this target.
+    pub target_id: Option<String>,
+
+    /// Only include runs started at or after this time.
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only include runs started at or before this time.
+    pub until: Option<DateTime<Utc>>,
+
+    /// Only include runs whose git commit starts with this prefix.
+    pub git_commit: Option<String>,
+
+    /// Only include the run with this ID.
+    pub run_id: Option<Uuid>,
+
+    /// Keep only the most recent N matching runs.
+    pub last_n: Option<usize>,
+}
+
+impl HistoryQuery {
+    /// Checks whether a run satisfies the filter criteria (ignoring `last_n`).
+    pub fn matches(&self, report: &RunReport) -> bool {
+        if let Some(target_id) = &self.target_id {
+            if report.result(target_id).is_none() {
+                return false;
+            }
+        }
+        if let Some(since) = self.since {
+            if report.started_at < since {
+                return false;
+            }
+        }
+        if let Some(until) = self.until {
+            if report.started_at > until {
+                return false;
+            }
+        }
+        if let Some(prefix) = &self.git_commit {
+            let commit = report
+                .environment
+                .as_ref()
+                .and_then(|env| env.git.as_ref())
+                .map(|git| git.commit.as_str());
+            if !commit.is_some_and(|c| c.starts_with(prefix.as_str())) {
+                return false;
+            }
+        }
+        if let Some(run_id) = self.run_id {
+            if report.run_id != run_id {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Narrows a matching run to the queried target, if any.
+    fn project(&self, mut report: RunReport) -> RunReport {
+        if let Some(target_id) = &self.target_id {
+            report.results.retain(|r| &r.target_id == target_id);
+        }
+        report
+    }
+}
+
+/// A single point of a metric time series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeriesPoint {
+    /// Run the value was recorded in.
+    pub run_id: Uuid,
+
+    /// Timestamp of the result.
+    pub timestamp: DateTime<Utc>,
+
+    /// Git commit of the run, if recorded.
+    pub git_commit: Option<String>,
+
+    /// Target the value belongs to.
+    pub target_id: String,
+
+    /// Metric value, or `None` if the result did not report it.
+    pub value: Option<f64>,
+}
+
+/// Runs a query over the history log in the canonical output directory.
+///
+/// The log is streamed line by line; only matching runs (at most `last_n`
+/// of them) are held in memory.
+///
+/// # Arguments
+///
+/// * `base_path` - Base path where results are stored
+/// * `query` - Filter criteria
+///
+/// # Returns
+///
+/// Matching runs in chronological (file) order, or an `IoError`.
+pub fn query_history(base_path: &str, query: &HistoryQuery) -> Result<Vec<RunReport>, IoError> {
+    collect_matching(HistoryReader::open(base_path)?, query)
+}
+
+/// Returns the time series of a metric for the runs matching a query.
+///
+/// # Arguments
+///
+/// * `base_path` - Base path where results are stored
+/// * `query` - Filter criteria
+/// * `metric` - Metric key (e.g. `ops_per_sec`, or an extension key)
+///
+/// # Returns
+///
+/// One `SeriesPoint` per matching result, in chronological order.
+pub fn metric_series(
+    base_path: &str,
+    query: &HistoryQuery,
+    metric: &str,
+) -> Result<Vec<SeriesPoint>, IoError> {
+    Ok(series_from_runs(&query_history(base_path, query)?, metric))
+}
+
+/// Extracts the time series of a metric from a list of runs.
+pub fn series_from_runs(runs: &[RunReport], metric: &str) -> Vec<SeriesPoint> {
+    runs.iter()
+        .flat_map(|run| {
+            let git_commit = run
+                .environment
+                .as_ref()
+                .and_then(|env| env.git.as_ref())
+                .map(|git| git.commit.clone());
+            run.results.iter().map(move |result| SeriesPoint {
+                run_id: run.run_id,
+                timestamp: result.timestamp,
+                git_commit: git_commit.clone(),
+                target_id: result.target_id.clone(),
+                value: result.metrics.value(metric),
+            })
+        })
+        .collect()
+}
+
+/// A target that failed intermittently across runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlakyTarget {
+    /// Target ID.
+    pub target_id: String,
+
+    /// Number of runs that measured the target (cached and skipped results
+    /// are not counted).
+    pub runs: usize,
+
+    /// Number of those runs in which it failed.
+    pub failures: usize,
+
+    /// Number of those runs in which it passed only after a retry.
+    pub retried: usize,
+}
+
+/// Finds targets that failed intermittently across a list of runs.
+///
+/// A target is flaky if it both passed and failed, or if it needed more
+/// than one attempt to pass. Targets that failed in every run are broken
+/// rather than flaky and are not reported.
+///
+/// # Arguments
+///
+/// * `runs` - Runs to inspect, e.g. the recent history
+///
+/// # Returns
+///
+/// Flaky targets ordered by target ID.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::history::flaky_targets;
+/// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use forge_benchmarks::benchmarks::run::RunReport;
+/// use serde_json::json;
+///
+/// let runs = vec![
+///     RunReport::from_legacy(vec![BenchmarkResult::new("parse".to_string(), json!({}))]),
+///     RunReport::from_legacy(vec![BenchmarkResult::failed("parse".to_string(), "EBUSY".to_string())]),
+/// ];
+///
+/// let flaky = flaky_targets(&runs);
+/// assert_eq!(flaky[0].target_id, "parse");
+/// assert_eq!(flaky[0].failures, 1);
+/// ```
+pub fn flaky_targets(runs: &[RunReport]) -> Vec<FlakyTarget> {
+    let mut targets: BTreeMap<&str, FlakyTarget> = BTreeMap::new();
+    let measured = runs
+        .iter()
+        .flat_map(|run| &run.results)
+        .filter(|r| !r.cached && !r.is_skipped());
+    for result in measured {
+        let target = targets
+            .entry(&result.target_id)
+            .or_insert_with(|| FlakyTarget {
+                target_id: result.target_id.clone(),
+                runs: 0,
+                failures: 0,
+                retried: 0,
+            });
+        target.runs += 1;
+        if result.is_failed() {
+            target.failures += 1;
+        } else if result.attempts > 1 {
+            target.retried += 1;
+        }
+    }
+
+    targets
+        .into_values()
+        .filter(|t| t.retried > 0 || (t.failures > 0 && t.failures < t.runs))
+        .collect()
+}
+
+/// Formats a metric time series as an aligned plain-text table.
+pub fn format_series_table(points: &[SeriesPoint], metric: &str) -> String {
+    let header = [
+        "Timestamp".to_string(),
+        "Run".to_string(),
+        "Commit".to_string(),
+        "Target".to_string(),
+        metric.to_string(),
+    ];
+    let rows: Vec<[String; 5]> = points
+        .iter()
+        .map(|p| {
+            [
+                p.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                p.run_id.to_string()[..8].to_string(),
+                p.git_commit
+                    .as_deref()
+                    .map(|c| c.chars().take(10).collect())
+                    .unwrap_or_else(|| "-".to_string()),
+                p.target_id.clone(),
+                p.value
+                    .map(|v| format!("{:.2}", v))
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.clone().map(|h| h.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// Parses a date bound given as RFC 3339 or `YYYY-MM-DD` (midnight UTC).
+pub fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc())
+}
+
+/// Returns the path of the history log in the canonical output directory.
+pub fn history_path(base_path: &str) -> PathBuf {
+    Path::new(base_path).join(OUTPUT_DIR).join(HISTORY_FILE)
+}
+
+fn collect_matching(
+    runs: impl Iterator<Item = Result<RunReport, IoError>>,
+    query: &HistoryQuery,
+) -> Result<Vec<RunReport>, IoError> {
+    let mut matched = VecDeque::new();
+    for run in runs {
+        let run = run?;
+        if !query.matches(&run) {
+            continue;
+        }
+        matched.push_back(query.project(run));
+        if let Some(n) = query.last_n {
+            while matched.len() > n {
+                matched.pop_front();
+            }
+        }
+    }
+    Ok(matched.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::config::RunConfig;
+    use crate::benchmarks::environment::{EnvironmentInfo, GitInfo};
+    use crate::benchmarks::io::{append_to_history, ensure_output_dirs};
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn run_with(commit: &str, ops: f64) -> RunReport {
+        let mut environment = EnvironmentInfo::capture(Path::new("."));
+        environment.git = Some(GitInfo {
+            commit: commit.to_string(),
+            branch: None,
+            dirty: false,
+        });
+
+        let mut report = RunReport::begin(RunConfig::default(), Some(environment));
+        report.results = vec![
+            BenchmarkResult::new("a".to_string(), json!({"ops_per_sec": ops})),
+            BenchmarkResult::new("b".to_string(), json!({"ops_per_sec": ops * 2.0})),
+        ];
+        report.finish()
+    }
+
+    fn write_history(runs: &[RunReport]) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+        ensure_output_dirs(base_path).unwrap();
+        for run in runs {
+            append_to_history(run, base_path).unwrap();
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn test_query_by_target_and_last_n() {
+        let runs = vec![
+            run_with("aaa", 1.0),
+            run_with("bbb", 2.0),
+            run_with("ccc", 3.0),
+        ];
+        let temp_dir = write_history(&runs);
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        let query = HistoryQuery {
+            target_id: Some("b".to_string()),
+            last_n: Some(2),
+            ..HistoryQuery::default()
+        };
+        let matched = query_history(base_path, &query).unwrap();
+
+        assert_eq!(matched.len(), 2);
+        assert_eq!(matched[0].run_id, runs[1].run_id);
+        assert!(matched.iter().all(|r| r.results.len() == 1));
+    }
+
+    #[test]
+    fn test_query_by_commit_and_run_id() {
+        let runs = vec![run_with("abc123", 1.0), run_with("def456", 2.0)];
+        let temp_dir = write_history(&runs);
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        let by_commit = HistoryQuery {
+            git_commit: Some("def".to_string()),
+            ..HistoryQuery::default()
+        };
+        assert_eq!(
+            query_history(base_path, &by_commit).unwrap()[0].run_id,
+            runs[1].run_id
+        );
+
+        let by_run = HistoryQuery {
+            run_id: Some(runs[0].run_id),
+            ..HistoryQuery::default()
+        };
+        assert_eq!(query_history(base_path, &by_run).unwrap().len(), 1);
+
+        let by_date = HistoryQuery {
+            since: Some(Utc::now() + chrono::Duration::days(1)),
+            ..HistoryQuery::default()
+        };
+        assert!(query_history(base_path, &by_date).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_flaky_targets() {
+        let retried = {
+            let mut result = BenchmarkResult::new("retried".to_string(), json!({}));
+            result.attempts = 2;
+            result
+        };
+        let failed = |id: &str| BenchmarkResult::failed(id.to_string(), "EBUSY".to_string());
+        let passed = |id: &str| BenchmarkResult::new(id.to_string(), json!({}));
+        let runs = vec![
+            RunReport::from_legacy(vec![passed("flaky"), failed("broken"), passed("stable")]),
+            RunReport::from_legacy(vec![failed("flaky"), failed("broken"), retried]),
+        ];
+
+        let flaky = flaky_targets(&runs);
+
+        assert_eq!(flaky.len(), 2);
+        assert_eq!(
+            flaky[0],
+            FlakyTarget {
+                target_id: "flaky".to_string(),
+                runs: 2,
+                failures: 1,
+                retried: 0,
+            }
+        );
+        assert_eq!(flaky[1].target_id, "retried");
+        assert_eq!(flaky[1].retried, 1);
+    }
+
+    #[test]
+    fn test_metric_series_and_legacy_lines() {
+        let temp_dir = write_history(&[run_with("aaa", 5.0)]);
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        // A pre-envelope history line holding a single result
+        let legacy = BenchmarkResult::new("a".to_string(), json!({"ops_per_sec": 4.0}));
+        let mut content = serde_json::to_string(&legacy).unwrap();
+        content.push('\n');
+        content.push_str(&fs::read_to_string(history_path(base_path)).unwrap());
+        fs::write(history_path(base_path), content).unwrap();
+
+        let query = HistoryQuery {
+            target_id: Some("a".to_string()),
+            ..HistoryQuery::default()
+        };
+        let series = metric_series(base_path, &query, "ops_per_sec").unwrap();
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].value, Some(4.0));
+        assert!(series[0].run_id.is_nil());
+        assert_eq!(series[1].value, Some(5.0));
+        assert_eq!(series[1].git_commit.as_deref(), Some("aaa"));
+
+        let table = format_series_table(&series, "ops_per_sec");
+        assert_eq!(table.lines().count(), 3);
+        assert!(table.contains("ops_per_sec"));
+    }
+
+    #[test]
+    fn test_corrupt_lines_are_skipped() {
+        let runs = [run_with("aaa", 1.0), run_with("bbb", 2.0)];
+        let temp_dir = write_history(&runs[..1]);
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        // Simulate an interrupted write followed by a later successful append
+        let mut content = fs::read_to_string(history_path(base_path)).unwrap();
+        content.push_str("{\"run_id\": \"trunc\n");
+        fs::write(history_path(base_path), content).unwrap();
+        append_to_history(&runs[1], base_path).unwrap();
+
+        let mut reader = HistoryReader::open(base_path).unwrap();
+        let read: Vec<RunReport> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[1].run_id, runs[1].run_id);
+        assert_eq!(reader.skipped_lines(), 1);
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert!(parse_date("2024-05-01").is_some());
+        assert!(parse_date("2024-05-01T12:00:00Z").is_some());
+        assert!(parse_date("yesterday").is_none());
+    }
+}
@@ -0,0 +1,508 @@
+//! Self-contained HTML report generation.
+//!
+//! This module renders a `RunReport` as a single HTML file with inline CSS
+//! and SVG charts, so it can be opened offline and attached to CI artifacts
+//! without any external assets. The report shows per-target box plots of the
+//! raw samples, a history trend line per target, the baseline comparison and
+//! the environment fingerprint.
+
+use super::compare::{Comparison, Verdict};
+use super::history::{flaky_targets, series_from_runs, SeriesPoint};
+use super::report::{ReportContext, TIMESTAMP_FORMAT};
+use super::result::BenchmarkResult;
+use super::run::RunReport;
+use super::stats::SampleSummary;
+
+/// Metric plotted in the history trend charts.
+pub const TREND_METRIC: &str = "ops_per_sec";
+
+/// Width of every chart, in SVG user units.
+const CHART_WIDTH: f64 = 560.0;
+
+/// Height of a box plot, in SVG user units.
+const BOX_HEIGHT: f64 = 70.0;
+
+/// Height of a trend chart, in SVG user units.
+const TREND_HEIGHT: f64 = 140.0;
+
+/// Horizontal padding inside charts, in SVG user units.
+const PAD: f64 = 40.0;
+
+const STYLE: &str = "body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;margin:2rem;color:#1f2328}\
+table{border-collapse:collapse;margin:1rem 0}th,td{border:1px solid #d0d7de;padding:4px 10px;text-align:left}\
+th{background:#f6f8fa}td.num{text-align:right;font-variant-numeric:tabular-nums}\
+.improved{background:#dafbe1}.regressed{background:#ffebe9}.unchanged{background:#f6f8fa}\
+.failed{color:#cf222e;font-weight:600}.skipped{color:#9a6700;font-weight:600}.cached{color:#57606a;font-style:italic}.quarantined{color:#8250df;font-style:italic}.retried{color:#9a6700;font-style:italic}.target{margin:1.5rem 0;padding-top:.5rem;border-top:1px solid #d0d7de}\
+svg{display:block;margin:.5rem 0}svg text{font-size:11px;fill:#57606a}";
+
+/// Generates a self-contained HTML report.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to render
+/// * `comparisons` - Baseline comparisons (may be empty)
+/// * `history` - Previous runs, oldest first, used for the trend charts and
+///   to list flaky targets
+/// * `ctx` - Generation time, ordering and number formatting
+///
+/// # Returns
+///
+/// The HTML document as a string.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::html::generate_html;
+/// use forge_benchmarks::benchmarks::report::ReportContext;
+/// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use forge_benchmarks::benchmarks::run::RunReport;
+/// use serde_json::json;
+///
+/// let report = RunReport::from_legacy(vec![
+///     BenchmarkResult::new("target-1".to_string(), json!({"raw_ns": [900.0, 1000.0, 1100.0]})),
+/// ]);
+///
+/// let html = generate_html(&report, &[], &[], &ReportContext::for_run(&report));
+/// assert!(html.contains("<svg"));
+/// assert!(!html.contains("<script src="));
+/// ```
+pub fn generate_html(
+    report: &RunReport,
+    comparisons: &[Comparison],
+    history: &[RunReport],
+    ctx: &ReportContext,
+) -> String {
+    let skipped = report.skipped_count();
+    let series = series_from_runs(history, TREND_METRIC);
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>LLM-Forge Benchmark Report</title>\n");
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+
+    // Header
+    html.push_str("<h1>LLM-Forge Benchmark Report</h1>\n");
+    html.push_str(&format!(
+        "<p>Run <code>{}</code> &middot; {} &middot; {}/{} passed{}</p>\n",
+        report.run_id,
+        ctx.format_timestamp(),
+        report.passed_count(),
+        report.results.len(),
+        if skipped > 0 {
+            format!(" &middot; {} skipped", skipped)
+        } else {
+            String::new()
+        }
+    ));
+
+    // Baseline comparison
+    if !comparisons.is_empty() {
+        html.push_str("<h2>Baseline Comparison</h2>\n<table>\n");
+        html.push_str("<tr><th>Target</th><th>Metric</th><th>Baseline</th><th>Current</th><th>Delta</th><th>Verdict</th></tr>\n");
+        for c in comparisons {
+            let class = verdict_class(c.verdict);
+            html.push_str(&format!(
+                "<tr class=\"{}\"><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{:+.2}%</td><td>{}</td></tr>\n",
+                class,
+                escape(&c.target_id),
+                escape(&c.metric),
+                ctx.format_number(c.baseline),
+                ctx.format_number(c.current),
+                c.delta_pct,
+                class
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    // Targets failing intermittently over the history
+    let flaky = flaky_targets(history);
+    if !flaky.is_empty() {
+        html.push_str(&format!(
+            "<h2>Flaky Targets</h2>\n<p>Failed intermittently or passed only after a retry in the last {} runs.</p>\n<table>\n",
+            history.len()
+        ));
+        html.push_str("<tr><th>Target</th><th>Runs</th><th>Failures</th><th>Retried</th></tr>\n");
+        for target in &flaky {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+                escape(&target.target_id),
+                target.runs,
+                target.failures,
+                target.retried
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    // Per-target charts
+    html.push_str("<h2>Targets</h2>\n");
+    for result in ctx.sorted(&report.results) {
+        push_target(&mut html, result, &series, ctx);
+    }
+
+    // Environment fingerprint
+    if let Some(env) = &report.environment {
+        html.push_str("<h2>Environment</h2>\n<table>\n");
+        if let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(env) {
+            for (key, value) in fields {
+                let value = match value {
+                    serde_json::Value::String(s) => s,
+                    serde_json::Value::Null => "-".to_string(),
+                    other => other.to_string(),
+                };
+                html.push_str(&format!(
+                    "<tr><th>{}</th><td>{}</td></tr>\n",
+                    escape(&key),
+                    escape(&value)
+                ));
+            }
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("<p><em>Generated by forge-benchmarks - Canonical benchmark interface for LLM-Forge</em></p>\n");
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Appends the section of one target: status, box plot and trend.
+fn push_target(
+    html: &mut String,
+    result: &BenchmarkResult,
+    series: &[SeriesPoint],
+    ctx: &ReportContext,
+) {
+    html.push_str(&format!(
+        "<div class=\"target\">\n<h3>{}</h3>\n",
+        escape(&result.target_id)
+    ));
+
+    if result.is_failed() {
+        let error = result
+            .metrics
+            .error
+            .as_deref()
+            .unwrap_or("benchmark failed");
+        html.push_str(&format!(
+            "<p class=\"failed\">FAILED: {}</p>\n",
+            escape(error)
+        ));
+    }
+    if result.is_skipped() {
+        let reason = result
+            .metrics
+            .skip_reason
+            .as_deref()
+            .unwrap_or("precondition not met");
+        html.push_str(&format!(
+            "<p class=\"skipped\">SKIPPED: {}</p>\n",
+            escape(reason)
+        ));
+    }
+    if result.quarantined {
+        html.push_str("<p class=\"quarantined\">Quarantined: failures do not fail the build</p>\n");
+    }
+    if result.attempts > 1 {
+        html.push_str(&format!(
+            "<p class=\"retried\">{} after {} attempts</p>\n",
+            if result.is_failed() {
+                "Failed"
+            } else {
+                "Passed"
+            },
+            result.attempts
+        ));
+    }
+    if result.cached {
+        html.push_str(&format!(
+            "<p class=\"cached\">Cached: measured {}, reused because its inputs are unchanged</p>\n",
+            result.timestamp.format(TIMESTAMP_FORMAT)
+        ));
+    }
+
+    match result.metrics.sample_summary() {
+        Some(summary) => {
+            html.push_str(&format!(
+                "<p>median {} ns &middot; p95 {} ns &middot; CV {:.1}% &middot; {} samples</p>\n",
+                ctx.format_number(summary.median),
+                ctx.format_number(summary.p95),
+                summary.cv_pct(),
+                summary.count
+            ));
+            html.push_str(&box_plot(&summary, &result.metrics.raw_ns, ctx));
+        }
+        None if result.is_passed() => html.push_str("<p>No raw samples recorded.</p>\n"),
+        None => {}
+    }
+
+    let points: Vec<(f64, f64)> = series
+        .iter()
+        .filter(|p| p.target_id == result.target_id)
+        .filter_map(|p| p.value.map(|v| (p.timestamp.timestamp() as f64, v)))
+        .collect();
+    if points.len() >= 2 {
+        html.push_str(&trend_chart(&points, ctx));
+    }
+
+    html.push_str("</div>\n");
+}
+
+/// Renders a horizontal box plot with the individual samples overlaid.
+fn box_plot(summary: &SampleSummary, samples: &[f64], ctx: &ReportContext) -> String {
+    let scale = Scale::new(summary.min, summary.max, PAD, CHART_WIDTH - PAD);
+    let mid = BOX_HEIGHT / 2.0 - 6.0;
+
+    let mut svg = svg_open(BOX_HEIGHT, "Distribution of samples (ns)");
+    // Whiskers
+    svg.push_str(&format!(
+        "<line x1=\"{:.1}\" y1=\"{mid:.1}\" x2=\"{:.1}\" y2=\"{mid:.1}\" stroke=\"#57606a\"/>\n",
+        scale.map(summary.min),
+        scale.map(summary.max)
+    ));
+    for v in [summary.min, summary.max] {
+        svg.push_str(&format!(
+            "<line x1=\"{x:.1}\" y1=\"{:.1}\" x2=\"{x:.1}\" y2=\"{:.1}\" stroke=\"#57606a\"/>\n",
+            mid - 8.0,
+            mid + 8.0,
+            x = scale.map(v)
+        ));
+    }
+    // Interquartile box and median
+    svg.push_str(&format!(
+        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"24\" fill=\"#ddf4ff\" stroke=\"#0969da\"/>\n",
+        scale.map(summary.q1),
+        mid - 12.0,
+        (scale.map(summary.q3) - scale.map(summary.q1)).max(1.0)
+    ));
+    svg.push_str(&format!(
+        "<line x1=\"{x:.1}\" y1=\"{:.1}\" x2=\"{x:.1}\" y2=\"{:.1}\" stroke=\"#0969da\" stroke-width=\"2\"/>\n",
+        mid - 12.0,
+        mid + 12.0,
+        x = scale.map(summary.median)
+    ));
+    // Samples
+    for v in samples.iter().filter(|v| v.is_finite()) {
+        svg.push_str(&format!(
+            "<circle cx=\"{:.1}\" cy=\"{mid:.1}\" r=\"2\" fill=\"#0969da\" fill-opacity=\"0.4\"/>\n",
+            scale.map(*v)
+        ));
+    }
+    // Axis labels
+    svg.push_str(&format!(
+        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n",
+        scale.map(summary.min),
+        BOX_HEIGHT - 4.0,
+        ctx.format_number(summary.min),
+        scale.map(summary.max),
+        BOX_HEIGHT - 4.0,
+        ctx.format_number(summary.max)
+    ));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders a line chart of `(unix seconds, value)` points.
+fn trend_chart(points: &[(f64, f64)], ctx: &ReportContext) -> String {
+    let (t_min, t_max) = bounds(points.iter().map(|p| p.0));
+    let (v_min, v_max) = bounds(points.iter().map(|p| p.1));
+    let x = Scale::new(t_min, t_max, PAD, CHART_WIDTH - PAD);
+    // Inverted: larger values are drawn higher
+    let y = Scale::new(v_min, v_max, TREND_HEIGHT - 20.0, 10.0);
+
+    let path: Vec<String> = points
+        .iter()
+        .map(|(t, v)| format!("{:.1},{:.1}", x.map(*t), y.map(*v)))
+        .collect();
+
+    let mut svg = svg_open(
+        TREND_HEIGHT,
+        &format!("History of {} over {} runs", TREND_METRIC, points.len()),
+    );
+    svg.push_str(&format!(
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"#8250df\" stroke-width=\"1.5\"/>\n",
+        path.join(" ")
+    ));
+    for point in &path {
+        let (cx, cy) = point.split_once(',').unwrap_or_default();
+        svg.push_str(&format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"2.5\" fill=\"#8250df\"/>\n",
+            cx, cy
+        ));
+    }
+    svg.push_str(&format!(
+        "<text x=\"2\" y=\"14\">{}</text>\n<text x=\"2\" y=\"{:.1}\">{}</text>\n",
+        ctx.format_number(v_max),
+        TREND_HEIGHT - 20.0,
+        ctx.format_number(v_min)
+    ));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Opens an `<svg>` element with an accessible title.
+fn svg_open(height: f64, title: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" role=\"img\">\n<title>{}</title>\n",
+        escape(title),
+        w = CHART_WIDTH,
+        h = height
+    )
+}
+
+/// Linear mapping from a data range onto a pixel range.
+struct Scale {
+    min: f64,
+    span: f64,
+    from: f64,
+    to: f64,
+}
+
+impl Scale {
+    fn new(min: f64, max: f64, from: f64, to: f64) -> Self {
+        Self {
+            min,
+            span: max - min,
+            from,
+            to,
+        }
+    }
+
+    fn map(&self, value: f64) -> f64 {
+        if self.span <= 0.0 {
+            // Degenerate range: centre everything
+            return (self.from + self.to) / 2.0;
+        }
+        self.from + (value - self.min) / self.span * (self.to - self.from)
+    }
+}
+
+/// Returns the minimum and maximum of a sequence.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    })
+}
+
+/// Returns the CSS class of a verdict.
+fn verdict_class(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Improved => "improved",
+        Verdict::Unchanged => "unchanged",
+        Verdict::Regressed => "regressed",
+    }
+}
+
+/// Escapes text for HTML content and attributes.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::compare::compare_reports;
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+
+    fn run(avg_ns: f64, minutes_ago: i64) -> RunReport {
+        RunReport::from_legacy(vec![BenchmarkResult::with_timestamp(
+            "parse".to_string(),
+            json!({
+                "avg_ns": avg_ns,
+                "ops_per_sec": 1e9 / avg_ns,
+                "raw_ns": [avg_ns * 0.9, avg_ns, avg_ns * 1.1]
+            }),
+            Utc::now() - Duration::minutes(minutes_ago),
+        )])
+    }
+
+    #[test]
+    fn test_report_is_self_contained() {
+        let baseline = run(1000.0, 30);
+        let current = run(1500.0, 0);
+        let comparisons = compare_reports(&baseline, &current, "avg_ns", 5.0);
+        let history = vec![run(1000.0, 20), run(1200.0, 10), current.clone()];
+
+        let html = generate_html(
+            &current,
+            &comparisons,
+            &history,
+            &ReportContext::for_run(&current),
+        );
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        // No external assets: the SVG namespace is the only URL
+        let without_ns = html.replace("xmlns=\"http://www.w3.org/2000/svg\"", "");
+        assert!(!without_ns.contains("http"));
+        assert!(html.contains("<tr class=\"regressed\">"));
+        assert!(html.contains("+50.00%"));
+        assert!(html.contains("<rect"), "box plot");
+        assert!(html.contains("<polyline"), "trend line");
+        assert!(html.contains("History of ops_per_sec over 3 runs"));
+    }
+
+    #[test]
+    fn test_failed_target_and_escaping() {
+        let report = RunReport::from_legacy(vec![BenchmarkResult::failed(
+            "<script>".to_string(),
+            "a & b".to_string(),
+        )]);
+
+        let html = generate_html(&report, &[], &[], &ReportContext::for_run(&report));
+
+        assert!(html.contains("<h3>&lt;script&gt;</h3>"));
+        assert!(html.contains("FAILED: a &amp; b"));
+        assert!(!html.contains("<svg"));
+    }
+
+    #[test]
+    fn test_skipped_target() {
+        let report = RunReport::from_legacy(vec![BenchmarkResult::skipped(
+            "parse".to_string(),
+            "`npx` not found on PATH".to_string(),
+        )]);
+
+        let html = generate_html(&report, &[], &[], &ReportContext::for_run(&report));
+
+        assert!(html.contains("0/1 passed &middot; 1 skipped"));
+        assert!(html.contains("<p class=\"skipped\">SKIPPED: `npx` not found on PATH</p>"));
+        assert!(!html.contains("No raw samples"));
+    }
+
+    #[test]
+    fn test_flaky_targets_and_attempts() {
+        let mut retried = BenchmarkResult::new("parse".to_string(), json!({"avg_ns": 1.0}));
+        retried.attempts = 2;
+        retried.quarantined = true;
+        let report = RunReport::from_legacy(vec![retried]);
+        let history = vec![report.clone()];
+
+        let html = generate_html(&report, &[], &history, &ReportContext::for_run(&report));
+
+        assert!(html.contains("<h2>Flaky Targets</h2>"));
+        assert!(html.contains("<tr><td>parse</td><td class=\"num\">1</td>"));
+        assert!(html.contains("<p class=\"retried\">Passed after 2 attempts</p>"));
+        assert!(html.contains("<p class=\"quarantined\">Quarantined: "));
+    }
+
+    #[test]
+    fn test_scale_handles_degenerate_range() {
+        let scale = Scale::new(5.0, 5.0, 0.0, 100.0);
+        assert_eq!(scale.map(5.0), 50.0);
+
+        let scale = Scale::new(0.0, 10.0, 100.0, 0.0);
+        assert_eq!(scale.map(10.0), 0.0);
+    }
+}
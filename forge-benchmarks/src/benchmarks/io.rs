@@ -3,10 +3,19 @@
 //! This module provides functions for reading and writing benchmark results
 //! to the canonical output directories.
 
+use super::compare::Comparison;
+use super::environment::EnvironmentInfo;
+use super::export::{self, MetricMapping};
+use super::metrics::MetricDirection;
+use super::naming::{is_safe_component, raw_filename, RawIndex};
+use super::report::ReportContext;
 use super::result::BenchmarkResult;
-use std::fs;
-use std::io;
-use std::path::Path;
+use super::run::{ResultsFile, RunReport};
+use super::tabular::{self, TableFormat};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use thiserror::Error;
 
 /// Default output directory for benchmark results.
@@ -15,9 +24,45 @@ pub const OUTPUT_DIR: &str = "benchmarks/output";
 /// Default raw output directory for individual result files.
 pub const RAW_OUTPUT_DIR: &str = "benchmarks/output/raw";
 
+/// Default combined results file name.
+pub const RESULTS_FILE: &str = "results.json";
+
+/// Default history log file name.
+pub const HISTORY_FILE: &str = "history.jsonl";
+
 /// Default summary file name.
 pub const SUMMARY_FILE: &str = "summary.md";
 
+/// Default baseline comparison summary file name.
+pub const COMPARISON_FILE: &str = "comparison.md";
+
+/// Default HTML report file name.
+pub const HTML_FILE: &str = "report.html";
+
+/// Default environment metadata file name.
+pub const ENVIRONMENT_FILE: &str = "environment.json";
+
+/// Default JUnit XML report file name.
+pub const JUNIT_FILE: &str = "junit.xml";
+
+/// github-action-benchmark `customSmallerIsBetter` file name.
+pub const GITHUB_BENCHMARK_SMALLER_FILE: &str = "github-benchmark-smaller.json";
+
+/// github-action-benchmark `customBiggerIsBetter` file name.
+pub const GITHUB_BENCHMARK_BIGGER_FILE: &str = "github-benchmark-bigger.json";
+
+/// Bencher Metric Format file name.
+pub const BMF_FILE: &str = "bmf.json";
+
+/// Directory of the persistent result cache.
+pub const CACHE_DIR: &str = "benchmarks/output/cache";
+
+/// Advisory lock file guarding the output directory.
+pub const LOCK_FILE: &str = ".lock";
+
+/// Counter distinguishing temp files written concurrently by one process.
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
 /// Errors that can occur during benchmark I/O operations.
 #[derive(Error, Debug)]
 pub enum IoError {
@@ -27,20 +72,38 @@ pub enum IoError {
     #[error("JSON serialization error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Invalid table row {row}: {message}")]
+    InvalidRow { row: usize, message: String },
+
+    #[error("Report template {template}: {message}")]
+    Template { template: String, message: String },
+
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
     #[error("Directory does not exist: {0}")]
     DirectoryNotFound(String),
+
+    #[error("Output directory is locked by another run ({holder}): {path}")]
+    Locked { path: String, holder: String },
 }
 
-/// Writes benchmark results to the canonical output directories.
+/// Writes a benchmark run to the canonical output directories.
 ///
 /// This function:
-/// 1. Writes individual JSON files to `benchmarks/output/raw/`
-/// 2. Writes a combined JSON file to `benchmarks/output/results.json`
+/// 1. Writes individual JSON files to `benchmarks/output/raw/`, named by
+///    `naming::raw_filename` and recorded in `raw/index.json`
+/// 2. Writes the run envelope to `benchmarks/output/results.json`
 /// 3. Writes a Markdown summary to `benchmarks/output/summary.md`
+/// 4. Writes the environment metadata to `benchmarks/output/environment.json`
+///    when it was captured
 ///
 /// # Arguments
 ///
-/// * `results` - The benchmark results to write
+/// * `report` - The benchmark run to write
 /// * `base_path` - Base path for output (defaults to crate root)
 ///
 /// # Returns
@@ -50,17 +113,18 @@ pub enum IoError {
 /// # Example
 ///
 /// ```rust,no_run
+/// use forge_benchmarks::benchmarks::config::RunConfig;
 /// use forge_benchmarks::benchmarks::io::write_results;
 /// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use forge_benchmarks::benchmarks::run::RunReport;
 /// use serde_json::json;
 ///
-/// let results = vec![
-///     BenchmarkResult::new("test".to_string(), json!({})),
-/// ];
+/// let mut report = RunReport::begin(RunConfig::default(), None);
+/// report.results.push(BenchmarkResult::new("test".to_string(), json!({})));
 ///
-/// write_results(&results, ".").unwrap();
+/// write_results(&report.finish(), ".").unwrap();
 /// ```
-pub fn write_results(results: &[BenchmarkResult], base_path: &str) -> Result<(), IoError> {
+pub fn write_results(report: &RunReport, base_path: &str) -> Result<(), IoError> {
     let output_dir = Path::new(base_path).join(OUTPUT_DIR);
     let raw_dir = Path::new(base_path).join(RAW_OUTPUT_DIR);
 
@@ -68,28 +132,39 @@ pub fn write_results(results: &[BenchmarkResult], base_path: &str) -> Result<(),
     fs::create_dir_all(&output_dir)?;
     fs::create_dir_all(&raw_dir)?;
 
-    // Write individual raw result files
-    for result in results {
-        let filename = format!("{}.json", sanitize_filename(&result.target_id));
+    // Write individual raw result files, recording their names in the index
+    let mut index = RawIndex::load(&raw_dir)?;
+    for result in &report.results {
+        let filename = index.insert(&result.target_id);
         let filepath = raw_dir.join(&filename);
         let json = serde_json::to_string_pretty(result)?;
-        fs::write(&filepath, json)?;
+        write_atomic(&filepath, json.as_bytes())?;
     }
+    index.save(&raw_dir)?;
 
     // Write combined results file
-    let combined_path = output_dir.join("results.json");
-    let combined_json = serde_json::to_string_pretty(results)?;
-    fs::write(&combined_path, combined_json)?;
+    let combined_path = output_dir.join(RESULTS_FILE);
+    let combined_json = serde_json::to_string_pretty(report)?;
+    write_atomic(&combined_path, combined_json.as_bytes())?;
 
     // Write markdown summary
-    let summary = super::markdown::generate_summary(results);
+    let summary =
+        super::markdown::generate_summary(&report.results, &ReportContext::for_run(report));
     let summary_path = output_dir.join(SUMMARY_FILE);
-    fs::write(&summary_path, summary)?;
+    write_atomic(&summary_path, summary.as_bytes())?;
+
+    // Write environment metadata
+    if let Some(environment) = &report.environment {
+        write_environment(environment, base_path)?;
+    }
 
     Ok(())
 }
 
-/// Reads benchmark results from the canonical output directory.
+/// Reads the latest benchmark run from the canonical output directory.
+///
+/// Both the `RunReport` envelope and legacy flat `Vec<BenchmarkResult>`
+/// files are accepted; legacy files are wrapped via `RunReport::from_legacy`.
 ///
 /// # Arguments
 ///
@@ -97,24 +172,278 @@ pub fn write_results(results: &[BenchmarkResult], base_path: &str) -> Result<(),
 ///
 /// # Returns
 ///
-/// A vector of `BenchmarkResult` or an `IoError`.
-pub fn read_results(base_path: &str) -> Result<Vec<BenchmarkResult>, IoError> {
-    let results_path = Path::new(base_path).join(OUTPUT_DIR).join("results.json");
+/// The `RunReport` or an `IoError`.
+pub fn read_results(base_path: &str) -> Result<RunReport, IoError> {
+    let results_path = Path::new(base_path).join(OUTPUT_DIR).join(RESULTS_FILE);
+    read_results_file(&results_path)
+}
 
-    if !results_path.exists() {
+/// Reads a benchmark run from an arbitrary results file, e.g. a baseline.
+///
+/// Files with a `.csv` or `.tsv` extension are read as tables written by
+/// `write_results_table`; anything else is read as JSON.
+///
+/// # Arguments
+///
+/// * `path` - Path to a `results.json` file (envelope or legacy format) or table
+///
+/// # Returns
+///
+/// The `RunReport` or an `IoError`.
+pub fn read_results_file(path: &Path) -> Result<RunReport, IoError> {
+    if !path.exists() {
         return Err(IoError::DirectoryNotFound(
-            results_path.to_string_lossy().to_string(),
+            path.to_string_lossy().to_string(),
         ));
     }
 
-    let content = fs::read_to_string(&results_path)?;
-    let results: Vec<BenchmarkResult> = serde_json::from_str(&content)?;
+    if let Some(format) = TableFormat::from_path(path) {
+        return tabular::read_results_table(File::open(path)?, format);
+    }
+
+    let content = fs::read_to_string(path)?;
+    let file: ResultsFile = serde_json::from_str(&content)?;
+
+    Ok(file.into())
+}
+
+/// Writes the results of a run as a CSV or TSV table to
+/// `benchmarks/output/results.<csv|tsv>`.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to write
+/// * `format` - CSV or TSV
+/// * `base_path` - Base path for output
+///
+/// # Returns
+///
+/// The path of the written file, or an `IoError` on failure.
+pub fn write_results_table(
+    report: &RunReport,
+    format: TableFormat,
+    base_path: &str,
+) -> Result<PathBuf, IoError> {
+    let output_dir = Path::new(base_path).join(OUTPUT_DIR);
+    fs::create_dir_all(&output_dir)?;
+
+    let mut table = Vec::new();
+    tabular::write_results_table(report, format, &mut table)?;
+    let table_path = output_dir
+        .join(RESULTS_FILE)
+        .with_extension(format.extension());
+    write_atomic(&table_path, &table)?;
+
+    Ok(table_path)
+}
+
+/// Writes the github-action-benchmark files of a run.
+///
+/// Writes `github-benchmark-smaller.json` (`customSmallerIsBetter`) and
+/// `github-benchmark-bigger.json` (`customBiggerIsBetter`) to
+/// `benchmarks/output/`.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to export
+/// * `mappings` - Metrics to export (see `RunConfig::export_mappings`)
+/// * `base_path` - Base path for output
+///
+/// # Returns
+///
+/// The paths of the written files, or an `IoError` on failure.
+pub fn write_github_benchmark(
+    report: &RunReport,
+    mappings: &[MetricMapping],
+    base_path: &str,
+) -> Result<Vec<PathBuf>, IoError> {
+    let output_dir = Path::new(base_path).join(OUTPUT_DIR);
+    fs::create_dir_all(&output_dir)?;
+
+    let mut paths = Vec::new();
+    for (direction, file) in [
+        (
+            MetricDirection::LowerIsBetter,
+            GITHUB_BENCHMARK_SMALLER_FILE,
+        ),
+        (
+            MetricDirection::HigherIsBetter,
+            GITHUB_BENCHMARK_BIGGER_FILE,
+        ),
+    ] {
+        let entries = export::github_benchmark_entries(report, mappings, direction);
+        let path = output_dir.join(file);
+        write_atomic(&path, serde_json::to_string_pretty(&entries)?.as_bytes())?;
+        paths.push(path);
+    }
 
-    Ok(results)
+    Ok(paths)
+}
+
+/// Writes the run in Bencher Metric Format to `benchmarks/output/bmf.json`.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to export
+/// * `mappings` - Metrics to export (see `RunConfig::export_mappings`)
+/// * `base_path` - Base path for output
+///
+/// # Returns
+///
+/// The path of the written file, or an `IoError` on failure.
+pub fn write_bmf(
+    report: &RunReport,
+    mappings: &[MetricMapping],
+    base_path: &str,
+) -> Result<PathBuf, IoError> {
+    let output_dir = Path::new(base_path).join(OUTPUT_DIR);
+    fs::create_dir_all(&output_dir)?;
+
+    let bmf = export::bmf_report(report, mappings);
+    let path = output_dir.join(BMF_FILE);
+    write_atomic(&path, serde_json::to_string_pretty(&bmf)?.as_bytes())?;
+
+    Ok(path)
+}
+
+/// Writes the baseline comparison summary to `benchmarks/output/comparison.md`.
+///
+/// The summary is limited to `markdown::GITHUB_COMMENT_LIMIT` so it can be
+/// posted as a PR comment as-is.
+///
+/// # Arguments
+///
+/// * `report` - The current run
+/// * `baseline` - The run compared against
+/// * `comparisons` - Output of `compare::compare_reports` for the two runs
+/// * `base_path` - Base path for output
+///
+/// # Returns
+///
+/// The path of the written file, or an `IoError` on failure.
+pub fn write_comparison_summary(
+    report: &RunReport,
+    baseline: &RunReport,
+    comparisons: &[Comparison],
+    base_path: &str,
+) -> Result<PathBuf, IoError> {
+    let output_dir = Path::new(base_path).join(OUTPUT_DIR);
+    fs::create_dir_all(&output_dir)?;
+
+    let summary = super::markdown::generate_comparison_summary(
+        report,
+        baseline,
+        comparisons,
+        &ReportContext::for_run(report),
+        super::markdown::GITHUB_COMMENT_LIMIT,
+    );
+    let path = output_dir.join(COMPARISON_FILE);
+    write_atomic(&path, summary.as_bytes())?;
+
+    Ok(path)
+}
+
+/// Renders `benchmarks/output/summary.md` from the report template.
+///
+/// `write_results` writes the built-in layout; this re-renders the summary
+/// with the template configured in `report.template` and with the
+/// comparisons and history that are only known later in a run.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to render
+/// * `comparisons` - Baseline comparisons (may be empty)
+/// * `history` - Previous runs, oldest first
+/// * `base_path` - Base path for output
+///
+/// # Returns
+///
+/// The path of the written file, or an `IoError` on failure.
+pub fn write_summary(
+    report: &RunReport,
+    comparisons: &[Comparison],
+    history: &[RunReport],
+    base_path: &str,
+) -> Result<PathBuf, IoError> {
+    let output_dir = Path::new(base_path).join(OUTPUT_DIR);
+    fs::create_dir_all(&output_dir)?;
+
+    let summary = super::template::render_summary(
+        report,
+        comparisons,
+        history,
+        &ReportContext::for_run(report),
+    )?;
+    let path = output_dir.join(SUMMARY_FILE);
+    write_atomic(&path, summary.as_bytes())?;
+
+    Ok(path)
+}
+
+/// Writes the self-contained HTML report to `benchmarks/output/report.html`.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to render
+/// * `comparisons` - Baseline comparisons (may be empty)
+/// * `history` - Previous runs, oldest first, for the trend charts
+/// * `base_path` - Base path for output
+///
+/// # Returns
+///
+/// The path of the written file, or an `IoError` on failure.
+pub fn write_html_report(
+    report: &RunReport,
+    comparisons: &[Comparison],
+    history: &[RunReport],
+    base_path: &str,
+) -> Result<PathBuf, IoError> {
+    let output_dir = Path::new(base_path).join(OUTPUT_DIR);
+    fs::create_dir_all(&output_dir)?;
+
+    let html = super::html::generate_html(
+        report,
+        comparisons,
+        history,
+        &ReportContext::for_run(report),
+    );
+    let html_path = output_dir.join(HTML_FILE);
+    write_atomic(&html_path, html.as_bytes())?;
+
+    Ok(html_path)
+}
+
+/// Writes a JUnit XML report of a run to `benchmarks/output/junit.xml`.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to write
+/// * `comparisons` - Baseline comparisons; regressions are reported as failures
+/// * `base_path` - Base path for output
+///
+/// # Returns
+///
+/// The path of the written file, or an `IoError` on failure.
+pub fn write_junit(
+    report: &RunReport,
+    comparisons: &[Comparison],
+    base_path: &str,
+) -> Result<PathBuf, IoError> {
+    let output_dir = Path::new(base_path).join(OUTPUT_DIR);
+    fs::create_dir_all(&output_dir)?;
+
+    let xml = super::junit::generate_junit(report, comparisons);
+    let junit_path = output_dir.join(JUNIT_FILE);
+    write_atomic(&junit_path, xml.as_bytes())?;
+
+    Ok(junit_path)
 }
 
 /// Reads a single benchmark result by target ID.
 ///
+/// The file name is resolved through `raw/index.json`. Directories written
+/// before the index existed fall back to the legacy sanitized name.
+///
 /// # Arguments
 ///
 /// * `base_path` - Base path where results are stored
@@ -124,8 +453,21 @@ pub fn read_results(base_path: &str) -> Result<Vec<BenchmarkResult>, IoError> {
 ///
 /// The `BenchmarkResult` or an `IoError`.
 pub fn read_result_by_id(base_path: &str, target_id: &str) -> Result<BenchmarkResult, IoError> {
-    let filename = format!("{}.json", sanitize_filename(target_id));
-    let filepath = Path::new(base_path).join(RAW_OUTPUT_DIR).join(&filename);
+    let raw_dir = Path::new(base_path).join(RAW_OUTPUT_DIR);
+    let index = RawIndex::load(&raw_dir)?;
+
+    let filename = match index.get(target_id) {
+        Some(filename) => filename.to_string(),
+        None => {
+            let legacy = format!("{}.json", sanitize_filename(target_id));
+            if is_safe_component(&legacy) {
+                legacy
+            } else {
+                raw_filename(target_id)
+            }
+        }
+    };
+    let filepath = raw_dir.join(&filename);
 
     if !filepath.exists() {
         return Err(IoError::DirectoryNotFound(
@@ -139,44 +481,221 @@ pub fn read_result_by_id(base_path: &str, target_id: &str) -> Result<BenchmarkRe
     Ok(result)
 }
 
-/// Appends results to the historical log file.
+/// Appends a benchmark run to the historical log file.
 ///
-/// Results are appended as JSONL (one JSON object per line) for easy parsing
-/// and historical analysis.
+/// Each run is appended as a single JSONL line (one `RunReport` per line)
+/// for easy parsing and historical analysis. Callers that may run
+/// concurrently should hold an `OutputLock` while appending.
 ///
 /// # Arguments
 ///
-/// * `results` - The benchmark results to append
+/// * `report` - The benchmark run to append
 /// * `base_path` - Base path for output
 ///
 /// # Returns
 ///
 /// `Ok(())` on success, or an `IoError` on failure.
-pub fn append_to_history(results: &[BenchmarkResult], base_path: &str) -> Result<(), IoError> {
-    let history_path = Path::new(base_path).join(OUTPUT_DIR).join("history.jsonl");
-
-    let mut history_content = String::new();
-    for result in results {
-        let json_line = serde_json::to_string(result)?;
-        history_content.push_str(&json_line);
-        history_content.push('\n');
-    }
+pub fn append_to_history(report: &RunReport, base_path: &str) -> Result<(), IoError> {
+    let history_path = Path::new(base_path).join(OUTPUT_DIR).join(HISTORY_FILE);
 
-    // Append to existing file or create new one
-    use std::fs::OpenOptions;
-    use std::io::Write;
+    let mut history_content = serde_json::to_string(report)?;
+    history_content.push('\n');
 
+    // Append to existing file or create new one; the whole line is written
+    // in one call so concurrent readers never see a partial record
     let mut file = OpenOptions::new()
         .create(true)
         .append(true)
         .open(&history_path)?;
 
     file.write_all(history_content.as_bytes())?;
+    file.sync_data()?;
 
     Ok(())
 }
 
-/// Sanitizes a string for use as a filename.
+/// Writes the run environment metadata next to the benchmark results.
+///
+/// # Arguments
+///
+/// * `environment` - The captured environment to write
+/// * `base_path` - Base path for output
+///
+/// # Returns
+///
+/// `Ok(())` on success, or an `IoError` on failure.
+pub fn write_environment(environment: &EnvironmentInfo, base_path: &str) -> Result<(), IoError> {
+    let output_dir = Path::new(base_path).join(OUTPUT_DIR);
+    fs::create_dir_all(&output_dir)?;
+
+    let json = serde_json::to_string_pretty(environment)?;
+    write_atomic(&output_dir.join(ENVIRONMENT_FILE), json.as_bytes())?;
+
+    Ok(())
+}
+
+/// Reads the run environment metadata from the canonical output directory.
+///
+/// # Arguments
+///
+/// * `base_path` - Base path where results are stored
+///
+/// # Returns
+///
+/// The `EnvironmentInfo` or an `IoError`.
+pub fn read_environment(base_path: &str) -> Result<EnvironmentInfo, IoError> {
+    let env_path = Path::new(base_path).join(OUTPUT_DIR).join(ENVIRONMENT_FILE);
+
+    if !env_path.exists() {
+        return Err(IoError::DirectoryNotFound(
+            env_path.to_string_lossy().to_string(),
+        ));
+    }
+
+    let content = fs::read_to_string(&env_path)?;
+    let environment: EnvironmentInfo = serde_json::from_str(&content)?;
+
+    Ok(environment)
+}
+
+/// Atomically replaces a file with the given contents.
+///
+/// The contents are written to a temporary file in the same directory,
+/// flushed to disk, and renamed over the destination, so readers see either
+/// the old or the new file but never a partially written one.
+///
+/// # Arguments
+///
+/// * `path` - Destination file path
+/// * `contents` - Bytes to write
+///
+/// # Returns
+///
+/// `Ok(())` on success, or an `IoError` on failure.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), IoError> {
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp_path = dir.join(format!(
+        ".{}.{}.{}.tmp",
+        file_name,
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = (|| {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)?;
+        sync_dir(dir)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    Ok(result?)
+}
+
+/// Flushes directory metadata so a completed rename survives a crash.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Advisory lock on the canonical output directory.
+///
+/// Held for the duration of a run so two concurrent runs cannot interleave
+/// writes to the same output directory. The lock is released on drop or
+/// when the process exits.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use forge_benchmarks::benchmarks::io::OutputLock;
+///
+/// let _lock = OutputLock::acquire(".").expect("another run is in progress");
+/// // ... write results and append to history ...
+/// ```
+#[derive(Debug)]
+pub struct OutputLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl OutputLock {
+    /// Acquires the output directory lock without blocking.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_path` - Base path for output
+    ///
+    /// # Returns
+    ///
+    /// The held `OutputLock`, or `IoError::Locked` naming the process that
+    /// holds it.
+    pub fn acquire(base_path: &str) -> Result<Self, IoError> {
+        let output_dir = Path::new(base_path).join(OUTPUT_DIR);
+        fs::create_dir_all(&output_dir)?;
+
+        let path = output_dir.join(LOCK_FILE);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let holder = fs::read_to_string(&path).unwrap_or_default();
+                return Err(IoError::Locked {
+                    path: path.to_string_lossy().to_string(),
+                    holder: match holder.trim() {
+                        "" => "unknown process".to_string(),
+                        pid => format!("pid {}", pid),
+                    },
+                });
+            }
+            Err(TryLockError::Error(e)) => return Err(e.into()),
+        }
+
+        // Record the holder for diagnostics
+        file.set_len(0)?;
+        write!(file, "{}", std::process::id())?;
+        file.sync_data()?;
+
+        Ok(Self { file, path })
+    }
+
+    /// Returns the path of the lock file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for OutputLock {
+    fn drop(&mut self) {
+        let _ = self.file.set_len(0);
+        let _ = self.file.unlock();
+    }
+}
+
+/// Sanitizes a string for use as a filename (pre-index naming scheme).
+///
+/// Only used to locate result files written before `raw/index.json`
+/// existed; new files are named with `naming::raw_filename`.
 fn sanitize_filename(name: &str) -> String {
     name.chars()
         .map(|c| match c {
@@ -212,31 +731,202 @@ pub fn ensure_output_dirs(base_path: &str) -> Result<(), IoError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::benchmarks::config::RunConfig;
     use serde_json::json;
     use tempfile::TempDir;
 
+    fn report_with(results: Vec<BenchmarkResult>) -> RunReport {
+        let mut report = RunReport::begin(RunConfig::default(), None);
+        report.results = results;
+        report.finish()
+    }
+
     #[test]
     fn test_write_and_read_results() {
         let temp_dir = TempDir::new().unwrap();
         let base_path = temp_dir.path().to_str().unwrap();
 
-        let results = vec![
+        let report = report_with(vec![
             BenchmarkResult::new("test-1".to_string(), json!({"ops_per_sec": 1000})),
             BenchmarkResult::new("test-2".to_string(), json!({"ops_per_sec": 2000})),
-        ];
+        ]);
 
         // Write results
-        write_results(&results, base_path).unwrap();
+        write_results(&report, base_path).unwrap();
 
         // Read back combined results
-        let read_results = read_results(base_path).unwrap();
-        assert_eq!(read_results.len(), 2);
+        let read_report = read_results(base_path).unwrap();
+        assert_eq!(read_report.run_id, report.run_id);
+        assert_eq!(read_report.results.len(), 2);
 
         // Read individual result
         let single = read_result_by_id(base_path, "test-1").unwrap();
         assert_eq!(single.target_id, "test-1");
     }
 
+    #[test]
+    fn test_csv_table_as_baseline() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        let report = report_with(vec![BenchmarkResult::new(
+            "test-1".to_string(),
+            json!({"ops_per_sec": 1000}),
+        )]);
+        let path = write_results_table(&report, TableFormat::Csv, base_path).unwrap();
+        assert!(path.ends_with("results.csv"));
+
+        let baseline = read_results_file(&path).unwrap();
+        assert_eq!(baseline.run_id, report.run_id);
+        assert_eq!(baseline.results[0].ops_per_sec(), Some(1000.0));
+    }
+
+    #[test]
+    fn test_write_trend_exports() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        let report = report_with(vec![BenchmarkResult::new(
+            "test-1".to_string(),
+            json!({"avg_ns": 1000.0, "ops_per_sec": 1000000.0}),
+        )]);
+        let mappings = export::default_mappings();
+
+        let paths = write_github_benchmark(&report, &mappings, base_path).unwrap();
+        let smaller: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&paths[0]).unwrap()).unwrap();
+        assert_eq!(smaller[0]["name"], "test-1 - avg_ns");
+
+        let bmf_path = write_bmf(&report, &mappings, base_path).unwrap();
+        let bmf: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(bmf_path).unwrap()).unwrap();
+        assert_eq!(bmf["test-1"]["throughput"]["value"], 1000000.0);
+    }
+
+    #[test]
+    fn test_write_junit() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        let report = report_with(vec![BenchmarkResult::failed(
+            "test-1".to_string(),
+            "boom".to_string(),
+        )]);
+        let path = write_junit(&report, &[], base_path).unwrap();
+
+        assert!(path.ends_with(JUNIT_FILE));
+        let xml = fs::read_to_string(path).unwrap();
+        assert!(xml.contains(r#"<failure type="error" message="boom">"#));
+    }
+
+    #[test]
+    fn test_unsafe_target_ids_stay_in_raw_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        let ids = ["../escape", "CON", "a/b", "a_b", ".hidden"];
+        let report = report_with(
+            ids.iter()
+                .map(|id| BenchmarkResult::new(id.to_string(), json!({})))
+                .collect(),
+        );
+        write_results(&report, base_path).unwrap();
+
+        // One file per ID plus the index, all inside raw/
+        let raw_dir = Path::new(base_path).join(RAW_OUTPUT_DIR);
+        assert_eq!(fs::read_dir(&raw_dir).unwrap().count(), ids.len() + 1);
+        assert!(!Path::new(base_path)
+            .join("benchmarks/output/escape.json")
+            .exists());
+
+        for id in ids {
+            assert_eq!(read_result_by_id(base_path, id).unwrap().target_id, id);
+        }
+    }
+
+    #[test]
+    fn test_read_legacy_raw_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+        ensure_output_dirs(base_path).unwrap();
+
+        let result = BenchmarkResult::new("old/target".to_string(), json!({}));
+        fs::write(
+            Path::new(base_path)
+                .join(RAW_OUTPUT_DIR)
+                .join("old_target.json"),
+            serde_json::to_string(&result).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_result_by_id(base_path, "old/target")
+                .unwrap()
+                .target_id,
+            "old/target"
+        );
+    }
+
+    #[test]
+    fn test_read_legacy_results() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+        ensure_output_dirs(base_path).unwrap();
+
+        let legacy = vec![BenchmarkResult::new("legacy".to_string(), json!({}))];
+        fs::write(
+            Path::new(base_path).join(OUTPUT_DIR).join(RESULTS_FILE),
+            serde_json::to_string_pretty(&legacy).unwrap(),
+        )
+        .unwrap();
+
+        let report = read_results(base_path).unwrap();
+        assert!(report.is_legacy());
+        assert_eq!(report.results[0].target_id, "legacy");
+    }
+
+    #[test]
+    fn test_write_and_read_environment() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        let environment = EnvironmentInfo::capture(temp_dir.path());
+        write_environment(&environment, base_path).unwrap();
+
+        let read_back = read_environment(base_path).unwrap();
+        assert_eq!(read_back, environment);
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("results.json");
+
+        write_atomic(&path, b"old").unwrap();
+        write_atomic(&path, b"new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        // No temp files are left behind
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_output_lock_is_exclusive() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        let lock = OutputLock::acquire(base_path).unwrap();
+        match OutputLock::acquire(base_path) {
+            Err(IoError::Locked { holder, .. }) => {
+                assert_eq!(holder, format!("pid {}", std::process::id()))
+            }
+            other => panic!("expected lock to be held, got {:?}", other),
+        }
+
+        drop(lock);
+        assert!(OutputLock::acquire(base_path).is_ok());
+    }
+
     #[test]
     fn test_sanitize_filename() {
         assert_eq!(sanitize_filename("normal-name"), "normal-name");
@@ -262,12 +952,15 @@ mod tests {
 
         ensure_output_dirs(base_path).unwrap();
 
-        let results = vec![BenchmarkResult::new("hist-test".to_string(), json!({}))];
+        let report = report_with(vec![BenchmarkResult::new(
+            "hist-test".to_string(),
+            json!({}),
+        )]);
 
-        append_to_history(&results, base_path).unwrap();
-        append_to_history(&results, base_path).unwrap();
+        append_to_history(&report, base_path).unwrap();
+        append_to_history(&report, base_path).unwrap();
 
-        let history_path = Path::new(base_path).join(OUTPUT_DIR).join("history.jsonl");
+        let history_path = Path::new(base_path).join(OUTPUT_DIR).join(HISTORY_FILE);
         let content = fs::read_to_string(&history_path).unwrap();
         let lines: Vec<_> = content.lines().collect();
 
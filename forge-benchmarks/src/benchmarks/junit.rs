@@ -0,0 +1,381 @@
+//! JUnit XML generation for benchmark runs.
+//!
+//! This module renders a `RunReport` as a JUnit XML document so CI dashboards
+//! that understand test reports can display benchmark outcomes. Each target is
+//! a testcase; failed targets and regression-gate violations are failures,
+//! and targets skipped for an unmet precondition are skipped testcases.
+//! Failures of quarantined targets are reported as skipped so they do not
+//! fail the build.
+
+use super::compare::Comparison;
+use super::error::{tail, STDERR_TAIL_LINES};
+use super::result::BenchmarkResult;
+use super::run::RunReport;
+
+/// Name of the test suite in the generated report.
+pub const SUITE_NAME: &str = "forge-benchmarks";
+
+/// Extension metric key holding the captured stderr of a failed target.
+pub const STDERR_KEY: &str = "stderr";
+
+/// Generates a JUnit XML report for a benchmark run.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to render
+/// * `comparisons` - Baseline comparisons; regressions become failures
+///
+/// # Returns
+///
+/// The JUnit XML document as a string.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::junit::generate_junit;
+/// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use forge_benchmarks::benchmarks::run::RunReport;
+/// use serde_json::json;
+///
+/// let report = RunReport::from_legacy(vec![
+///     BenchmarkResult::new("target-1".to_string(), json!({"avg_ns": 1000.0, "samples": 10})),
+/// ]);
+///
+/// let xml = generate_junit(&report, &[]);
+/// assert!(xml.contains(r#"<testcase name="target-1""#));
+/// ```
+pub fn generate_junit(report: &RunReport, comparisons: &[Comparison]) -> String {
+    let failures = report
+        .results
+        .iter()
+        .filter(|r| !r.quarantined)
+        .filter(|r| r.is_failed() || regressions_of(comparisons, &r.target_id).next().is_some())
+        .count();
+    let skipped = report.skipped_count() + report.failed_count() - report.blocking_failed_count();
+    let tests = report.results.len();
+    let time = seconds(
+        (report.finished_at - report.started_at)
+            .num_nanoseconds()
+            .unwrap_or(0) as f64,
+    );
+    let timestamp = report.started_at.format("%Y-%m-%dT%H:%M:%S");
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" time=\"{}\">\n",
+        SUITE_NAME, tests, failures, time
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" id=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{}\" timestamp=\"{}\">\n",
+        SUITE_NAME, report.run_id, tests, failures, skipped, time, timestamp
+    ));
+
+    // Run identity and environment
+    xml.push_str("    <properties>\n");
+    for (name, value) in properties(report) {
+        xml.push_str(&format!(
+            "      <property name=\"{}\" value=\"{}\"/>\n",
+            escape(name),
+            escape(&value)
+        ));
+    }
+    xml.push_str("    </properties>\n");
+
+    for result in &report.results {
+        push_testcase(&mut xml, result, comparisons);
+    }
+
+    xml.push_str("  </testsuite>\n");
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Appends the `<testcase>` element of one result.
+fn push_testcase(xml: &mut String, result: &BenchmarkResult, comparisons: &[Comparison]) {
+    xml.push_str(&format!(
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\">\n",
+        escape(&result.target_id),
+        SUITE_NAME,
+        seconds(measured_ns(result))
+    ));
+
+    if result.is_failed() && result.quarantined {
+        let message = result
+            .metrics
+            .error
+            .as_deref()
+            .unwrap_or("benchmark failed");
+        xml.push_str(&format!(
+            "      <skipped message=\"{}\"/>\n",
+            escape(&format!("quarantined: {}", first_line(message)))
+        ));
+    } else if result.is_failed() {
+        let message = result
+            .metrics
+            .error
+            .as_deref()
+            .unwrap_or("benchmark failed");
+        let kind = result.failure().map_or("error", |failure| failure.kind());
+        xml.push_str(&format!(
+            "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+            kind,
+            escape(first_line(message)),
+            escape(message)
+        ));
+    } else if result.is_skipped() {
+        let reason = result
+            .metrics
+            .skip_reason
+            .as_deref()
+            .unwrap_or("precondition not met");
+        xml.push_str(&format!(
+            "      <skipped message=\"{}\"/>\n",
+            escape(reason)
+        ));
+    } else if !result.quarantined {
+        let regressions: Vec<_> = regressions_of(comparisons, &result.target_id).collect();
+        if !regressions.is_empty() {
+            let details: Vec<_> = regressions.iter().map(|c| c.describe()).collect();
+            xml.push_str(&format!(
+                "      <failure type=\"regression\" message=\"{}\">{}</failure>\n",
+                escape(&format!(
+                    "Regression against baseline: {}",
+                    details.join("; ")
+                )),
+                escape(&details.join("\n"))
+            ));
+        }
+    }
+
+    // Metrics as captured output, so they are visible in the CI test view
+    let metrics = serde_json::to_string_pretty(&result.metrics).unwrap_or_default();
+    xml.push_str(&format!(
+        "      <system-out>{}</system-out>\n",
+        escape(&metrics)
+    ));
+
+    let stderr = result
+        .failure()
+        .and_then(|failure| failure.stderr())
+        .or_else(|| {
+            result
+                .metrics
+                .extra
+                .get(STDERR_KEY)
+                .and_then(|v| v.as_str())
+        });
+    if let Some(stderr) = stderr {
+        xml.push_str(&format!(
+            "      <system-err>{}</system-err>\n",
+            escape(&tail(stderr, STDERR_TAIL_LINES))
+        ));
+    }
+
+    xml.push_str("    </testcase>\n");
+}
+
+/// Returns the run-level `<property>` entries.
+fn properties(report: &RunReport) -> Vec<(&'static str, String)> {
+    let mut props = vec![
+        ("run_id", report.run_id.to_string()),
+        ("started_at", report.started_at.to_rfc3339()),
+        ("finished_at", report.finished_at.to_rfc3339()),
+        (
+            "warmup_iterations",
+            report.config.warmup_iterations.to_string(),
+        ),
+        (
+            "measurement_iterations",
+            report.config.measurement_iterations.to_string(),
+        ),
+    ];
+
+    if let Some(env) = &report.environment {
+        props.push(("os", env.os.clone()));
+        props.push(("arch", env.arch.clone()));
+        props.push(("crate_version", env.crate_version.clone()));
+        if let Some(node) = &env.node_version {
+            props.push(("node_version", node.clone()));
+        }
+        if let Some(git) = &env.git {
+            props.push(("git_commit", git.commit.clone()));
+        }
+    }
+
+    props
+}
+
+/// Returns the regressions recorded for a target.
+fn regressions_of<'a>(
+    comparisons: &'a [Comparison],
+    target_id: &'a str,
+) -> impl Iterator<Item = &'a Comparison> {
+    comparisons
+        .iter()
+        .filter(move |c| c.target_id == target_id && c.is_regression())
+}
+
+/// Total measured time of a result, in nanoseconds.
+fn measured_ns(result: &BenchmarkResult) -> f64 {
+    match (result.metrics.avg_ns, result.metrics.samples) {
+        (Some(avg_ns), Some(samples)) => avg_ns * samples as f64,
+        (Some(avg_ns), None) => avg_ns,
+        _ => 0.0,
+    }
+}
+
+/// Formats nanoseconds as JUnit seconds.
+fn seconds(ns: f64) -> String {
+    format!("{:.6}", ns.max(0.0) / 1_000_000_000.0)
+}
+
+/// Returns the first line of a message.
+fn first_line(message: &str) -> &str {
+    message.lines().next().unwrap_or_default()
+}
+
+/// Escapes text for XML attributes and content.
+///
+/// Characters not allowed in XML 1.0 are dropped.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' | '\r' | '\t' => escaped.push(c),
+            c if c < ' ' || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::compare::compare_reports;
+    use crate::benchmarks::error::BenchError;
+    use serde_json::json;
+
+    #[test]
+    fn test_failed_target_carries_error_and_stderr_tail() {
+        let mut failed = BenchmarkResult::failed(
+            "broken<target>".to_string(),
+            "exit status 1\nmore".to_string(),
+        );
+        let stderr: Vec<String> = (0..30).map(|i| format!("line {}", i)).collect();
+        failed
+            .metrics
+            .extra
+            .insert(STDERR_KEY.to_string(), json!(stderr.join("\n")));
+        let report = RunReport::from_legacy(vec![
+            BenchmarkResult::new(
+                "ok".to_string(),
+                json!({"avg_ns": 2_000_000.0, "samples": 10}),
+            ),
+            failed,
+        ]);
+
+        let xml = generate_junit(&report, &[]);
+
+        assert!(xml.contains(r#"tests="2" failures="1""#));
+        assert!(
+            xml.contains(r#"<testcase name="ok" classname="forge-benchmarks" time="0.020000">"#)
+        );
+        assert!(xml.contains(r#"<testcase name="broken&lt;target&gt;""#));
+        assert!(xml.contains(r#"<failure type="error" message="exit status 1">"#));
+        assert!(xml.contains("line 29</system-err>"));
+        assert!(xml.contains("<system-err>line 10\n"));
+        assert!(!xml.contains("line 9\n"));
+    }
+
+    #[test]
+    fn test_typed_failure_sets_type_and_stderr() {
+        let failed = BenchmarkResult::from_error(
+            "parse".to_string(),
+            BenchError::NonZeroExit {
+                command: "npx llm-forge parse".to_string(),
+                code: 2,
+                stderr: "Error: invalid spec".to_string(),
+            },
+        );
+        let xml = generate_junit(&RunReport::from_legacy(vec![failed]), &[]);
+
+        assert!(xml.contains(
+            r#"<failure type="non_zero_exit" message="`npx llm-forge parse` exited with status 2">"#
+        ));
+        assert!(xml.contains("<system-err>Error: invalid spec</system-err>"));
+
+        let unparsable = BenchmarkResult::from_error(
+            "providers".to_string(),
+            BenchError::Parse {
+                command: "npx vitest run".to_string(),
+                message: "expected value at line 1 column 1".to_string(),
+            },
+        );
+        let xml = generate_junit(&RunReport::from_legacy(vec![unparsable]), &[]);
+        assert!(xml.contains(
+            r#"<failure type="parse" message="failed to parse output of `npx vitest run`: "#
+        ));
+    }
+
+    #[test]
+    fn test_skipped_target() {
+        let report = RunReport::from_legacy(vec![BenchmarkResult::skipped(
+            "parse".to_string(),
+            "`npx` not found on PATH".to_string(),
+        )]);
+
+        let xml = generate_junit(&report, &[]);
+
+        assert!(xml.contains(r#"tests="1" failures="0" errors="0" skipped="1""#));
+        assert!(xml.contains(r#"<skipped message="`npx` not found on PATH"/>"#));
+        assert!(!xml.contains("<failure"));
+    }
+
+    #[test]
+    fn test_quarantined_failure_is_skipped() {
+        let mut quarantined =
+            BenchmarkResult::failed("generate".to_string(), "exit status 1".to_string());
+        quarantined.quarantined = true;
+        let report = RunReport::from_legacy(vec![
+            quarantined,
+            BenchmarkResult::failed("parse".to_string(), "exit status 2".to_string()),
+        ]);
+
+        let xml = generate_junit(&report, &[]);
+
+        assert!(xml.contains(r#"tests="2" failures="1" errors="0" skipped="1""#));
+        assert!(xml.contains(r#"<skipped message="quarantined: exit status 1"/>"#));
+        assert!(xml.contains(r#"<failure type="error" message="exit status 2">"#));
+    }
+
+    #[test]
+    fn test_regressions_are_failures() {
+        let baseline = RunReport::from_legacy(vec![BenchmarkResult::new(
+            "parse".to_string(),
+            json!({"ops_per_sec": 1000.0}),
+        )]);
+        let current = RunReport::from_legacy(vec![BenchmarkResult::new(
+            "parse".to_string(),
+            json!({"ops_per_sec": 500.0}),
+        )]);
+        let comparisons = compare_reports(&baseline, &current, "ops_per_sec", 5.0);
+
+        let xml = generate_junit(&current, &comparisons);
+
+        assert!(xml.contains(r#"failures="1""#));
+        assert!(xml.contains(r#"<failure type="regression""#));
+        assert!(xml.contains("ops_per_sec: 1000 -&gt; 500 (-50.00%, threshold 5.00%)"));
+    }
+
+    #[test]
+    fn test_escape_drops_invalid_characters() {
+        assert_eq!(escape("a&b\u{0}\"c'"), "a&amp;b&quot;c&apos;");
+    }
+}
@@ -1,16 +1,33 @@
 //! Markdown generation for benchmark results.
 //!
 //! This module provides utilities for generating Markdown-formatted
-//! benchmark reports from `BenchmarkResult` collections.
+//! benchmark reports from `BenchmarkResult` collections, including a
+//! baseline comparison sized to fit in a GitHub PR comment. The summary
+//! report is rendered from the default template (see `template`).
 
+use super::compare::{Comparison, Verdict};
+use super::metrics::Metrics;
+use super::report::ReportContext;
 use super::result::BenchmarkResult;
-use chrono::Utc;
+use super::run::RunReport;
+use super::template::{render, ReportData, DEFAULT_TEMPLATE};
+
+/// Maximum size of a GitHub issue or PR comment body.
+pub const GITHUB_COMMENT_LIMIT: usize = 65_536;
+
+/// Footer appended to the comparison report.
+const FOOTER: &str =
+    "---\n\n*Generated by forge-benchmarks - Canonical benchmark interface for LLM-Forge*\n";
 
 /// Generates a Markdown summary report from benchmark results.
 ///
+/// Renders `DEFAULT_TEMPLATE`; the output depends only on `results` and
+/// `ctx`, so identical inputs produce byte-identical reports.
+///
 /// # Arguments
 ///
 /// * `results` - Slice of benchmark results to include in the report
+/// * `ctx` - Generation time, run ID, ordering and number formatting
 ///
 /// # Returns
 ///
@@ -19,90 +36,314 @@ use chrono::Utc;
 /// # Example
 ///
 /// ```rust
+/// use chrono::Utc;
 /// use forge_benchmarks::benchmarks::result::BenchmarkResult;
 /// use forge_benchmarks::benchmarks::markdown::generate_summary;
+/// use forge_benchmarks::benchmarks::report::ReportContext;
 /// use serde_json::json;
 ///
 /// let results = vec![
 ///     BenchmarkResult::new("target-1".to_string(), json!({"ops_per_sec": 1000000})),
 /// ];
 ///
-/// let markdown = generate_summary(&results);
+/// let markdown = generate_summary(&results, &ReportContext::new(Utc::now()));
 /// assert!(markdown.contains("target-1"));
 /// ```
-pub fn generate_summary(results: &[BenchmarkResult]) -> String {
-    let mut md = String::new();
+pub fn generate_summary(results: &[BenchmarkResult], ctx: &ReportContext) -> String {
+    let report = RunReport::from_legacy(results.to_vec());
+    render(DEFAULT_TEMPLATE, &ReportData::new(&report, &[], &[], ctx))
+        .expect("the default template is valid")
+}
 
-    // Header
-    md.push_str("# LLM-Forge Benchmark Results\n\n");
+/// Generates a baseline-comparison summary suitable for a PR comment.
+///
+/// Each target gets a status emoji, its baseline and current value, the
+/// delta, the significance of the change and the verdict, followed by
+/// collapsible per-target statistics. Output never exceeds `max_len`
+/// bytes: rows and sections that do not fit are replaced by a note.
+///
+/// # Arguments
+///
+/// * `report` - The current run
+/// * `baseline` - The run compared against
+/// * `comparisons` - Output of `compare::compare_reports` for the two runs
+/// * `ctx` - Generation time, run ID, ordering and number formatting
+/// * `max_len` - Size limit, usually `GITHUB_COMMENT_LIMIT`
+///
+/// # Returns
+///
+/// A formatted Markdown string.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::compare::compare_reports;
+/// use forge_benchmarks::benchmarks::markdown::{generate_comparison_summary, GITHUB_COMMENT_LIMIT};
+/// use forge_benchmarks::benchmarks::report::ReportContext;
+/// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use forge_benchmarks::benchmarks::run::RunReport;
+/// use serde_json::json;
+///
+/// let baseline = RunReport::from_legacy(vec![
+///     BenchmarkResult::new("parse".to_string(), json!({"ops_per_sec": 1000.0})),
+/// ]);
+/// let current = RunReport::from_legacy(vec![
+///     BenchmarkResult::new("parse".to_string(), json!({"ops_per_sec": 500.0})),
+/// ]);
+/// let comparisons = compare_reports(&baseline, &current, "ops_per_sec", 5.0);
+///
+/// let ctx = ReportContext::for_run(&current);
+/// let md = generate_comparison_summary(&current, &baseline, &comparisons, &ctx, GITHUB_COMMENT_LIMIT);
+/// assert!(md.contains("| 🔴 | parse |"));
+/// ```
+pub fn generate_comparison_summary(
+    report: &RunReport,
+    baseline: &RunReport,
+    comparisons: &[Comparison],
+    ctx: &ReportContext,
+    max_len: usize,
+) -> String {
+    let sorted = ctx.sorted(&report.results);
+    let rows: Vec<Row> = sorted
+        .iter()
+        .map(|result| Row::new(result, baseline, comparisons, ctx))
+        .collect();
+    let count = |status: &str| rows.iter().filter(|r| r.status == status).count();
+
+    let mut md = String::new();
+    md.push_str("# LLM-Forge Benchmark Comparison\n\n");
+    md.push_str(&format!("**Generated:** {}\n\n", ctx.format_timestamp()));
     md.push_str(&format!(
-        "**Generated:** {}\n\n",
-        Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+        "**Baseline:** {} · **Current:** {}\n\n",
+        describe_run(baseline),
+        describe_run(report)
     ));
-
-    // Summary statistics
-    let total = results.len();
-    let failed = results.iter().filter(|r| r.is_failed()).count();
-    let passed = total - failed;
-
-    md.push_str("## Summary\n\n");
-    md.push_str(&format!("- **Total Benchmarks:** {}\n", total));
-    md.push_str(&format!("- **Passed:** {}\n", passed));
-    md.push_str(&format!("- **Failed:** {}\n\n", failed));
-
-    // Results table
-    md.push_str("## Results\n\n");
-    md.push_str("| Target ID | Status | Ops/sec | Avg (ns) | Timestamp |\n");
-    md.push_str("|-----------|--------|---------|----------|----------|\n");
-
-    for result in results {
-        let status = if result.is_failed() { "FAILED" } else { "PASSED" };
-        let ops_per_sec = result
-            .ops_per_sec()
-            .map(|v| format_number(v))
-            .unwrap_or_else(|| "-".to_string());
-        let avg_ns = result
-            .avg_ns()
-            .map(|v| format_number(v))
-            .unwrap_or_else(|| "-".to_string());
-        let timestamp = result.timestamp.format("%H:%M:%S").to_string();
-
+    if let Some(c) = comparisons.first() {
         md.push_str(&format!(
-            "| {} | {} | {} | {} | {} |\n",
-            result.target_id, status, ops_per_sec, avg_ns, timestamp
+            "**Gate:** `{}`, threshold ±{:.2}%\n\n",
+            c.metric, c.threshold_pct
         ));
     }
+    md.push_str(&format!(
+        "{} {} regressed · {} {} improved · {} {} unchanged · {} {} failed · {} {} skipped · {} {} new\n\n",
+        REGRESSED,
+        count(REGRESSED),
+        IMPROVED,
+        count(IMPROVED),
+        UNCHANGED,
+        count(UNCHANGED),
+        FAILED,
+        count(FAILED),
+        SKIPPED,
+        count(SKIPPED),
+        NEW,
+        count(NEW)
+    ));
 
-    md.push('\n');
+    // Everything below is cut to size; keep room for a note and the footer
+    let reserve = FOOTER.len() + 200;
+    let budget = max_len.saturating_sub(reserve);
 
-    // Failed benchmarks details
-    let failed_results: Vec<_> = results.iter().filter(|r| r.is_failed()).collect();
-    if !failed_results.is_empty() {
-        md.push_str("## Failed Benchmarks\n\n");
-        for result in failed_results {
-            md.push_str(&format!("### {}\n\n", result.target_id));
-            if let Some(error) = result.metrics.get("error") {
-                md.push_str(&format!("**Error:** {}\n\n", error));
+    md.push_str("| | Target | Baseline | Current | Delta | Significant | Verdict |\n");
+    md.push_str("|---|---|---:|---:|---:|---|---|\n");
+    let mut shown = 0;
+    for row in &rows {
+        let line = row.table_line();
+        if md.len() + line.len() > budget {
+            break;
+        }
+        md.push_str(&line);
+        shown += 1;
+    }
+    md.push('\n');
+    if shown < rows.len() {
+        push_truncation_note(&mut md, rows.len() - shown);
+    } else {
+        md.push_str("## Per-target statistics\n\n");
+        let mut details_shown = 0;
+        for result in &sorted {
+            let section = details_section(result, baseline, ctx);
+            if md.len() + section.len() > budget {
+                break;
             }
+            md.push_str(&section);
+            details_shown += 1;
+        }
+        if details_shown < sorted.len() {
+            push_truncation_note(&mut md, sorted.len() - details_shown);
         }
     }
 
-    // Detailed metrics
-    md.push_str("## Detailed Metrics\n\n");
-    for result in results.iter().filter(|r| !r.is_failed()) {
-        md.push_str(&format!("### {}\n\n", result.target_id));
-        md.push_str("```json\n");
-        md.push_str(
-            &serde_json::to_string_pretty(&result.metrics).unwrap_or_else(|_| "{}".to_string()),
-        );
-        md.push_str("\n```\n\n");
+    md.push_str(FOOTER);
+    md
+}
+
+const REGRESSED: &str = "🔴";
+const IMPROVED: &str = "🟢";
+const UNCHANGED: &str = "⚪";
+const FAILED: &str = "❌";
+const SKIPPED: &str = "⏭️";
+const NEW: &str = "🆕";
+
+/// One line of the comparison table.
+struct Row<'a> {
+    target_id: &'a str,
+    status: &'static str,
+    baseline: String,
+    current: String,
+    delta: String,
+    significant: String,
+    verdict: &'static str,
+}
+
+impl<'a> Row<'a> {
+    fn new(
+        result: &'a BenchmarkResult,
+        baseline: &RunReport,
+        comparisons: &[Comparison],
+        ctx: &ReportContext,
+    ) -> Self {
+        let comparison = comparisons.iter().find(|c| c.target_id == result.target_id);
+        let mut row = Row {
+            target_id: &result.target_id,
+            status: UNCHANGED,
+            baseline: "-".to_string(),
+            current: "-".to_string(),
+            delta: "-".to_string(),
+            significant: "-".to_string(),
+            verdict: "n/a",
+        };
+
+        if let Some(c) = comparison {
+            row.baseline = ctx.format_number(c.baseline);
+            row.current = ctx.format_number(c.current);
+            row.delta = format!("{:+.2}%", c.delta_pct);
+            row.significant = match (c.is_significant(), c.t_statistic) {
+                (Some(true), Some(t)) => format!("yes (t={:.1})", t),
+                (Some(false), Some(t)) => format!("no (t={:.1})", t),
+                _ => "n/a".to_string(),
+            };
+            (row.status, row.verdict) = match c.verdict {
+                Verdict::Regressed => (REGRESSED, "regressed"),
+                Verdict::Improved => (IMPROVED, "improved"),
+                Verdict::Unchanged => (UNCHANGED, "unchanged"),
+            };
+        } else if result.is_failed() {
+            row.status = FAILED;
+            row.verdict = "failed";
+        } else if result.is_skipped() {
+            row.status = SKIPPED;
+            row.verdict = "skipped";
+        } else if baseline.result(&result.target_id).is_none() {
+            row.status = NEW;
+            row.verdict = "new";
+        }
+        row
     }
 
-    // Footer
-    md.push_str("---\n\n");
-    md.push_str("*Generated by forge-benchmarks - Canonical benchmark interface for LLM-Forge*\n");
+    fn table_line(&self) -> String {
+        format!(
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            self.status,
+            self.target_id,
+            self.baseline,
+            self.current,
+            self.delta,
+            self.significant,
+            self.verdict
+        )
+    }
+}
 
-    md
+/// Renders the collapsible statistics of one target against its baseline.
+fn details_section(result: &BenchmarkResult, baseline: &RunReport, ctx: &ReportContext) -> String {
+    let previous = baseline.result(&result.target_id).map(|r| &r.metrics);
+    let mut section = format!(
+        "<details><summary><b>{}</b></summary>\n\n",
+        result.target_id
+    );
+    if let Some(error) = &result.metrics.error {
+        section.push_str(&format!("**Error:** {}\n\n", error));
+    }
+    section.push_str("| Statistic | Baseline | Current |\n|---|---:|---:|\n");
+    for ((name, before), (_, after)) in statistics(previous, ctx)
+        .into_iter()
+        .zip(statistics(Some(&result.metrics), ctx))
+    {
+        section.push_str(&format!("| {} | {} | {} |\n", name, before, after));
+    }
+    section.push_str("\n</details>\n\n");
+    section
+}
+
+/// Returns the per-target statistics shown in `<details>` sections.
+///
+/// Uses the raw samples when recorded, otherwise the stored aggregates.
+pub(crate) fn statistics(
+    metrics: Option<&Metrics>,
+    ctx: &ReportContext,
+) -> Vec<(&'static str, String)> {
+    let summary = metrics.and_then(|m| m.sample_summary());
+    let cell = |v: Option<f64>| {
+        v.map(|v| ctx.format_number(v))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let fallback = |f: fn(&Metrics) -> Option<f64>| metrics.and_then(f);
+
+    vec![
+        ("median (ns)", cell(summary.map(|s| s.median))),
+        ("p95 (ns)", cell(summary.map(|s| s.p95))),
+        (
+            "mean (ns)",
+            cell(summary.map(|s| s.mean).or(fallback(|m| m.avg_ns))),
+        ),
+        (
+            "CV",
+            summary
+                .map(|s| format!("{:.1}%", s.cv_pct()))
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        (
+            "min (ns)",
+            cell(summary.map(|s| s.min).or(fallback(|m| m.min_ns))),
+        ),
+        (
+            "max (ns)",
+            cell(summary.map(|s| s.max).or(fallback(|m| m.max_ns))),
+        ),
+        ("ops/sec", cell(fallback(|m| m.ops_per_sec))),
+        (
+            "samples",
+            metrics
+                .and_then(|m| m.samples)
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+    ]
+}
+
+/// Describes a run by ID and commit for the comparison header.
+fn describe_run(report: &RunReport) -> String {
+    let commit = report
+        .environment
+        .as_ref()
+        .and_then(|env| env.git.as_ref())
+        .map(|git| format!(" @ `{}`", &git.commit[..git.commit.len().min(8)]))
+        .unwrap_or_default();
+    format!(
+        "run `{}`{}",
+        &report.run_id.simple().to_string()[..8],
+        commit
+    )
+}
+
+/// Appends the note shown when content was cut to fit the size limit.
+fn push_truncation_note(md: &mut String, omitted: usize) {
+    md.push_str(&format!(
+        "\n_{} more target(s) omitted to stay within the comment size limit; \
+         see `results.json` or `report.html` for the full report._\n\n",
+        omitted
+    ));
 }
 
 /// Generates a compact single-line summary for CI/CD logs.
@@ -116,8 +357,8 @@ pub fn generate_summary(results: &[BenchmarkResult]) -> String {
 /// A single-line summary string.
 pub fn generate_ci_summary(results: &[BenchmarkResult]) -> String {
     let total = results.len();
-    let failed = results.iter().filter(|r| r.is_failed()).count();
-    let passed = total - failed;
+    let passed = results.iter().filter(|r| r.is_passed()).count();
+    let skipped = results.iter().filter(|r| r.is_skipped()).count();
 
     let avg_ops: f64 = results
         .iter()
@@ -125,26 +366,18 @@ pub fn generate_ci_summary(results: &[BenchmarkResult]) -> String {
         .sum::<f64>()
         / results.iter().filter(|r| r.ops_per_sec().is_some()).count().max(1) as f64;
 
-    format!(
-        "Benchmarks: {}/{} passed | Avg ops/sec: {:.0}",
-        passed, total, avg_ops
-    )
-}
-
-/// Formats a number with thousand separators for readability.
-fn format_number(n: f64) -> String {
-    if n >= 1_000_000.0 {
-        format!("{:.2}M", n / 1_000_000.0)
-    } else if n >= 1_000.0 {
-        format!("{:.2}K", n / 1_000.0)
-    } else {
-        format!("{:.2}", n)
+    let mut summary = format!("Benchmarks: {}/{} passed", passed, total);
+    if skipped > 0 {
+        summary.push_str(&format!(" ({} skipped)", skipped));
     }
+    summary.push_str(&format!(" | Avg ops/sec: {:.0}", avg_ops));
+    summary
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Utc;
     use serde_json::json;
 
     #[test]
@@ -160,7 +393,7 @@ mod tests {
             ),
         ];
 
-        let summary = generate_summary(&results);
+        let summary = generate_summary(&results, &ReportContext::new(Utc::now()));
 
         assert!(summary.contains("# LLM-Forge Benchmark Results"));
         assert!(summary.contains("benchmark-1"));
@@ -176,13 +409,94 @@ mod tests {
             BenchmarkResult::failed("failure".to_string(), "Test error".to_string()),
         ];
 
-        let summary = generate_summary(&results);
+        let summary = generate_summary(&results, &ReportContext::new(Utc::now()));
 
         assert!(summary.contains("**Failed:** 1"));
         assert!(summary.contains("## Failed Benchmarks"));
         assert!(summary.contains("Test error"));
     }
 
+    fn comparison_fixture(targets: usize) -> (RunReport, RunReport, Vec<Comparison>) {
+        let run = |factor: f64| {
+            RunReport::from_legacy(
+                (0..targets)
+                    .map(|i| {
+                        BenchmarkResult::new(
+                            format!("target-{}", i),
+                            json!({
+                                "ops_per_sec": 1000.0 * factor,
+                                "raw_ns": [1e6 / factor, 1.01e6 / factor, 0.99e6 / factor]
+                            }),
+                        )
+                    })
+                    .collect(),
+            )
+        };
+        let baseline = run(1.0);
+        let mut current = run(0.5);
+        current.results.push(BenchmarkResult::new(
+            "added".to_string(),
+            json!({"ops_per_sec": 1.0}),
+        ));
+        current.results.push(BenchmarkResult::failed(
+            "broken".to_string(),
+            "boom".to_string(),
+        ));
+        let comparisons =
+            crate::benchmarks::compare::compare_reports(&baseline, &current, "ops_per_sec", 5.0);
+        (current, baseline, comparisons)
+    }
+
+    #[test]
+    fn test_comparison_summary() {
+        let (current, baseline, comparisons) = comparison_fixture(2);
+
+        let md = generate_comparison_summary(
+            &current,
+            &baseline,
+            &comparisons,
+            &ReportContext::for_run(&current),
+            GITHUB_COMMENT_LIMIT,
+        );
+
+        assert!(md.contains("🔴 2 regressed · 🟢 0 improved"));
+        assert!(md.contains("| 🔴 | target-0 | 1.00K | 500.00 | -50.00% | yes (t="));
+        assert!(md.contains("| 🆕 | added | - | - | - | - | new |"));
+        assert!(md.contains("| ❌ | broken |"));
+        assert!(md.contains("<details><summary><b>target-1</b></summary>"));
+        assert!(md.contains("**Error:** boom"));
+        assert!(!md.contains("omitted"));
+    }
+
+    #[test]
+    fn test_comparison_summary_truncates_politely() {
+        let (current, baseline, comparisons) = comparison_fixture(2000);
+
+        let md = generate_comparison_summary(
+            &current,
+            &baseline,
+            &comparisons,
+            &ReportContext::for_run(&current),
+            GITHUB_COMMENT_LIMIT,
+        );
+        assert!(md.len() <= GITHUB_COMMENT_LIMIT);
+        assert!(md.contains("more target(s) omitted"));
+        assert!(md.ends_with(FOOTER));
+
+        // Small reports keep the table but drop statistics sections
+        let (current, baseline, comparisons) = comparison_fixture(5);
+        let md = generate_comparison_summary(
+            &current,
+            &baseline,
+            &comparisons,
+            &ReportContext::for_run(&current),
+            2500,
+        );
+        assert!(md.len() <= 2500);
+        assert!(md.contains("| 🔴 | target-4 |"));
+        assert!(md.contains("more target(s) omitted"));
+    }
+
     #[test]
     fn test_ci_summary() {
         let results = vec![
@@ -194,12 +508,9 @@ mod tests {
 
         assert!(summary.contains("2/2 passed"));
         assert!(summary.contains("Avg ops/sec:"));
-    }
 
-    #[test]
-    fn test_format_number() {
-        assert_eq!(format_number(1_500_000.0), "1.50M");
-        assert_eq!(format_number(15_000.0), "15.00K");
-        assert_eq!(format_number(500.0), "500.00");
+        let mut results = results;
+        results.push(BenchmarkResult::skipped("b3".to_string(), "no node".to_string()));
+        assert!(generate_ci_summary(&results).starts_with("Benchmarks: 2/3 passed (1 skipped) |"));
     }
 }
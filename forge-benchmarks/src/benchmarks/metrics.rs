@@ -0,0 +1,450 @@
+//! Typed benchmark metrics - Versioned metrics schema.
+//!
+//! This module defines the `Metrics` struct stored in every `BenchmarkResult`.
+//! Well-known metrics are typed fields with documented units; anything else a
+//! target wants to report goes into the `extra` extension map.
+
+use super::error::BenchError;
+use super::stats::SampleSummary;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Current version of the metrics schema.
+///
+/// Bump this when a well-known field changes meaning or unit.
+pub const METRICS_SCHEMA_VERSION: u32 = 1;
+
+/// Outcome of a benchmark target.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultStatus {
+    /// The target completed and produced measurements.
+    #[default]
+    Passed,
+
+    /// The target could not be executed or measured.
+    Failed,
+
+    /// The target was not run because a precondition was not met.
+    Skipped,
+}
+
+/// Unit of a well-known metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricUnit {
+    /// Nanoseconds per operation.
+    Nanoseconds,
+
+    /// Operations per second.
+    OpsPerSecond,
+
+    /// A plain count.
+    Count,
+}
+
+impl MetricUnit {
+    /// Returns the short unit label used in reports and column headers.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            MetricUnit::Nanoseconds => "ns",
+            MetricUnit::OpsPerSecond => "ops/s",
+            MetricUnit::Count => "count",
+        }
+    }
+
+    /// Returns which direction of change is an improvement.
+    ///
+    /// Counts have no inherent direction and return `None`.
+    pub fn direction(&self) -> Option<MetricDirection> {
+        match self {
+            MetricUnit::Nanoseconds => Some(MetricDirection::LowerIsBetter),
+            MetricUnit::OpsPerSecond => Some(MetricDirection::HigherIsBetter),
+            MetricUnit::Count => None,
+        }
+    }
+}
+
+/// Which direction of change is an improvement for a metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricDirection {
+    /// Smaller values are better (e.g. latency).
+    LowerIsBetter,
+
+    /// Larger values are better (e.g. throughput).
+    HigherIsBetter,
+}
+
+/// Well-known metric keys and their units.
+pub const METRIC_UNITS: &[(&str, MetricUnit)] = &[
+    ("avg_ns", MetricUnit::Nanoseconds),
+    ("min_ns", MetricUnit::Nanoseconds),
+    ("max_ns", MetricUnit::Nanoseconds),
+    ("ops_per_sec", MetricUnit::OpsPerSecond),
+    ("samples", MetricUnit::Count),
+];
+
+/// Typed metrics of a single benchmark target.
+///
+/// Serializes to a flat JSON object, so files written before the typed
+/// schema existed still deserialize; unknown keys are kept in `extra`.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::metrics::Metrics;
+/// use serde_json::json;
+///
+/// let metrics = Metrics::from(json!({"ops_per_sec": 1000.0, "cache_hits": 3}));
+///
+/// assert_eq!(metrics.ops_per_sec, Some(1000.0));
+/// assert_eq!(metrics.extra["cache_hits"], 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(from = "Value")]
+#[schemars(
+    description = "Typed metrics of a single benchmark target. Unknown keys are extension metrics."
+)]
+pub struct Metrics {
+    /// Version of the metrics schema the values were written with.
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+
+    /// Outcome of the target.
+    #[serde(default)]
+    pub status: ResultStatus,
+
+    /// Error message if the target failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Typed cause of the failure, if the target reported one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<BenchError>,
+
+    /// Why the target was skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<String>,
+
+    /// Average time per operation, in nanoseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avg_ns: Option<f64>,
+
+    /// Fastest observed operation, in nanoseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_ns: Option<f64>,
+
+    /// Slowest observed operation, in nanoseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ns: Option<f64>,
+
+    /// Throughput, in operations per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ops_per_sec: Option<f64>,
+
+    /// Number of measured samples.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub samples: Option<u64>,
+
+    /// Raw duration of every measured sample, in nanoseconds.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub raw_ns: Vec<f64>,
+
+    /// Target-specific metrics outside the well-known set.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+fn default_schema_version() -> u32 {
+    METRICS_SCHEMA_VERSION
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            schema_version: METRICS_SCHEMA_VERSION,
+            status: ResultStatus::Passed,
+            error: None,
+            failure: None,
+            skip_reason: None,
+            avg_ns: None,
+            min_ns: None,
+            max_ns: None,
+            ops_per_sec: None,
+            samples: None,
+            raw_ns: Vec::new(),
+            extra: Map::new(),
+        }
+    }
+}
+
+impl Metrics {
+    /// Creates metrics for a failed target.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - Error message describing the failure
+    pub fn failed(error: impl Into<String>) -> Self {
+        Self {
+            status: ResultStatus::Failed,
+            error: Some(error.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Creates metrics for a target that failed with a typed error.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - Cause of the failure; its message becomes `error`
+    pub fn failed_with(error: BenchError) -> Self {
+        Self {
+            failure: Some(error.clone()),
+            ..Self::failed(error.to_string())
+        }
+    }
+
+    /// Creates metrics for a target that was skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `reason` - Unmet precondition, e.g. `node not found on PATH`
+    pub fn skipped(reason: impl Into<String>) -> Self {
+        Self {
+            status: ResultStatus::Skipped,
+            skip_reason: Some(reason.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Looks up a metric by key, including extension metrics.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Metric key as it appears in the serialized JSON
+    ///
+    /// # Returns
+    ///
+    /// The metric value as JSON, or `None` if it is not present.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        match key {
+            "avg_ns" => self.avg_ns.map(Value::from),
+            "min_ns" => self.min_ns.map(Value::from),
+            "max_ns" => self.max_ns.map(Value::from),
+            "ops_per_sec" => self.ops_per_sec.map(Value::from),
+            "samples" => self.samples.map(Value::from),
+            "raw_ns" => (!self.raw_ns.is_empty()).then(|| Value::from(self.raw_ns.clone())),
+            "error" => self.error.clone().map(Value::from),
+            _ => self.extra.get(key).cloned(),
+        }
+    }
+
+    /// Looks up a numeric metric by key, including extension metrics.
+    pub fn value(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(|v| v.as_f64())
+    }
+
+    /// Summarizes the raw samples, if any were recorded.
+    pub fn sample_summary(&self) -> Option<SampleSummary> {
+        SampleSummary::from_samples(&self.raw_ns)
+    }
+
+    /// Returns the unit of a well-known metric key.
+    pub fn unit_of(key: &str) -> Option<MetricUnit> {
+        METRIC_UNITS
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, unit)| *unit)
+    }
+
+    /// Returns the improvement direction of a well-known metric key.
+    pub fn direction_of(key: &str) -> Option<MetricDirection> {
+        Self::unit_of(key).and_then(|unit| unit.direction())
+    }
+
+    /// Converts the metrics to an untyped JSON value.
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
+impl From<Value> for Metrics {
+    /// Converts untyped JSON metrics, keeping unknown keys in `extra`.
+    ///
+    /// Keys are checked one by one: a value that does not fit its typed
+    /// field is preserved verbatim in `extra`, and the remaining fields
+    /// (in particular `status` and `error`) are still honoured. A value that
+    /// is not an object is kept in `extra` under `value`.
+    ///
+    /// `Deserialize` goes through this conversion too, so a result file
+    /// with a mistyped well-known key still reads.
+    fn from(value: Value) -> Self {
+        let map = match value {
+            Value::Object(map) => map,
+            Value::Null => Map::new(),
+            other => {
+                return Self {
+                    extra: Map::from_iter([("value".to_string(), other)]),
+                    ..Self::default()
+                }
+            }
+        };
+        let mut metrics = Self::default();
+        for (key, value) in map {
+            if !metrics.set_known(&key, &value) {
+                metrics.extra.insert(key, value);
+            }
+        }
+        metrics
+    }
+}
+
+impl Metrics {
+    /// Sets the well-known field `key` from a JSON value.
+    ///
+    /// # Returns
+    ///
+    /// `false` if `key` is not a well-known field or `value` does not fit
+    /// its type, leaving the metrics unchanged.
+    fn set_known(&mut self, key: &str, value: &Value) -> bool {
+        fn typed<T: DeserializeOwned>(value: &Value, field: &mut T) -> bool {
+            match T::deserialize(value) {
+                Ok(typed) => {
+                    *field = typed;
+                    true
+                }
+                Err(_) => false,
+            }
+        }
+
+        match key {
+            "schema_version" => typed(value, &mut self.schema_version),
+            "status" => typed(value, &mut self.status),
+            "error" => typed(value, &mut self.error),
+            "failure" => typed(value, &mut self.failure),
+            "skip_reason" => typed(value, &mut self.skip_reason),
+            "avg_ns" => typed(value, &mut self.avg_ns),
+            "min_ns" => typed(value, &mut self.min_ns),
+            "max_ns" => typed(value, &mut self.max_ns),
+            "ops_per_sec" => typed(value, &mut self.ops_per_sec),
+            "samples" => typed(value, &mut self.samples),
+            "raw_ns" => typed(value, &mut self.raw_ns),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_value_splits_known_and_extra() {
+        let metrics = Metrics::from(json!({
+            "ops_per_sec": 1000,
+            "avg_ns": 1000000.0,
+            "samples": 10,
+            "cache_hits": 4
+        }));
+
+        assert_eq!(metrics.schema_version, METRICS_SCHEMA_VERSION);
+        assert_eq!(metrics.status, ResultStatus::Passed);
+        assert_eq!(metrics.ops_per_sec, Some(1000.0));
+        assert_eq!(metrics.samples, Some(10));
+        assert_eq!(metrics.extra.len(), 1);
+        assert_eq!(metrics.value("cache_hits"), Some(4.0));
+    }
+
+    #[test]
+    fn test_legacy_failed_metrics() {
+        let metrics = Metrics::from(json!({"status": "failed", "error": "boom"}));
+
+        assert_eq!(metrics.status, ResultStatus::Failed);
+        assert_eq!(metrics.error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_skipped_metrics() {
+        let value = Metrics::skipped("node not found on PATH").to_value();
+        assert_eq!(value["status"], "skipped");
+        assert_eq!(value["skip_reason"], "node not found on PATH");
+        assert!(value.get("error").is_none());
+        assert_eq!(Metrics::from(value).status, ResultStatus::Skipped);
+    }
+
+    #[test]
+    fn test_raw_samples() {
+        let metrics = Metrics::from(json!({"raw_ns": [30.0, 10.0, 20.0]}));
+
+        assert_eq!(metrics.raw_ns.len(), 3);
+        assert_eq!(metrics.sample_summary().unwrap().median, 20.0);
+        assert!(Metrics::default().sample_summary().is_none());
+        assert!(Metrics::default().to_value().get("raw_ns").is_none());
+    }
+
+    #[test]
+    fn test_mistyped_values_are_preserved() {
+        let metrics = Metrics::from(json!({"samples": "many"}));
+
+        assert_eq!(metrics.samples, None);
+        assert_eq!(metrics.extra["samples"], "many");
+    }
+
+    #[test]
+    fn test_failed_legacy_metrics_with_mistyped_field() {
+        let metrics = Metrics::from(json!({
+            "status": "failed",
+            "error": "npx exited with code 1",
+            "samples": "ten",
+            "avg_ns": 1500.0
+        }));
+
+        assert_eq!(metrics.status, ResultStatus::Failed);
+        assert_eq!(metrics.error.as_deref(), Some("npx exited with code 1"));
+        assert_eq!(metrics.avg_ns, Some(1500.0));
+        assert_eq!(metrics.samples, None);
+        assert_eq!(metrics.extra.len(), 1);
+        assert_eq!(metrics.extra["samples"], "ten");
+    }
+
+    #[test]
+    fn test_deserialize_preserves_mistyped_values() {
+        let metrics: Metrics = serde_json::from_value(json!({
+            "ops_per_sec": 1000.0,
+            "samples": 10.5,
+            "cache_hits": 4
+        }))
+        .unwrap();
+
+        assert_eq!(metrics.ops_per_sec, Some(1000.0));
+        assert_eq!(metrics.samples, None);
+        assert_eq!(metrics.extra["samples"], 10.5);
+        assert_eq!(metrics.extra["cache_hits"], 4);
+
+        let value = metrics.to_value();
+        assert_eq!(serde_json::from_value::<Metrics>(value).unwrap(), metrics);
+    }
+
+    #[test]
+    fn test_serialization_is_flat() {
+        let metrics = Metrics {
+            ops_per_sec: Some(2.0),
+            ..Metrics::default()
+        };
+        let value = metrics.to_value();
+
+        assert_eq!(value["ops_per_sec"], 2.0);
+        assert_eq!(value["status"], "passed");
+        assert!(value.get("avg_ns").is_none());
+        assert_eq!(Metrics::unit_of("avg_ns"), Some(MetricUnit::Nanoseconds));
+        assert_eq!(
+            Metrics::direction_of("ops_per_sec"),
+            Some(MetricDirection::HigherIsBetter)
+        );
+        assert_eq!(Metrics::direction_of("samples"), None);
+    }
+}
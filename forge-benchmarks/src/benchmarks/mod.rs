@@ -3,22 +3,57 @@
 //! This module provides the canonical benchmark interface for the LLM-Forge project,
 //! implementing the unified benchmark structure used across all 25 benchmark-target repositories.
 
+pub mod cache;
+pub mod compare;
+pub mod config;
+pub mod environment;
+pub mod error;
+pub mod events;
+pub mod export;
+pub mod history;
+pub mod html;
 pub mod io;
+pub mod junit;
 pub mod markdown;
+pub mod metrics;
+pub mod naming;
+pub mod report;
 pub mod result;
+pub mod retention;
+pub mod retry;
+pub mod run;
+pub mod schema;
+pub mod stats;
+pub mod tabular;
+pub mod template;
+pub mod terminal;
 
-use crate::adapters::{all_targets, BenchTarget};
+use crate::adapters::{
+    all_targets, check_preconditions, find_forge_root, resolve_forge_root, BenchTarget,
+    ForgeRootError,
+};
+use cache::{CacheMode, ResultCache};
+use chrono::Utc;
+use config::RunConfig;
+use environment::EnvironmentInfo;
+use error::BenchError;
+use events::{EventPublisher, RunEvent};
 use result::BenchmarkResult;
-use tracing::{info, warn};
+use run::RunReport;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 
 /// Runs all registered benchmark targets and returns their results.
 ///
 /// This is the main entrypoint for the benchmark suite, executing each
 /// registered `BenchTarget` and collecting `BenchmarkResult` instances.
+/// The targets run in the Forge checkout found by `find_forge_root`.
 ///
 /// # Returns
 ///
-/// A `Vec<BenchmarkResult>` containing the results from all benchmark targets.
+/// A `Vec<BenchmarkResult>` containing the results from all benchmark
+/// targets, or a `ForgeRootError` if no llm-forge checkout was found.
 ///
 /// # Example
 ///
@@ -27,53 +62,342 @@ use tracing::{info, warn};
 ///
 /// #[tokio::main]
 /// async fn main() {
-///     let results = run_all_benchmarks().await;
+///     let results = run_all_benchmarks().await.expect("llm-forge checkout");
 ///     println!("Completed {} benchmarks", results.len());
 /// }
 /// ```
-pub async fn run_all_benchmarks() -> Vec<BenchmarkResult> {
-    let targets = all_targets();
-    let mut results = Vec::with_capacity(targets.len());
+pub async fn run_all_benchmarks() -> Result<Vec<BenchmarkResult>, ForgeRootError> {
+    run_all_benchmarks_with_events(&EventPublisher::disabled()).await
+}
+
+/// Runs all registered benchmark targets, publishing their progress.
+///
+/// Publishes `TargetStarted`, then `SampleRecorded` for every measured
+/// iteration, then `TargetFinished` or `TargetFailed` for each target.
+/// Targets whose preconditions are not met are not run; they are recorded
+/// as skipped and publish `TargetSkipped`. Failed attempts that the retry
+/// policy of the target allows to repeat publish `TargetRetrying`.
+///
+/// # Arguments
+///
+/// * `events` - Publisher the events are sent to
+///
+/// # Returns
+///
+/// A `Vec<BenchmarkResult>` containing the results from all benchmark
+/// targets, or a `ForgeRootError` if no llm-forge checkout was found.
+pub async fn run_all_benchmarks_with_events(
+    events: &EventPublisher,
+) -> Result<Vec<BenchmarkResult>, ForgeRootError> {
+    let forge_root = find_forge_root()?;
+    Ok(run_targets(&forge_root, &RunConfig::default(), events, None).await)
+}
+
+/// Cached results a run may reuse.
+struct Reuse<'a> {
+    cache: &'a dyn ResultCache,
+    mode: CacheMode,
+    config: &'a RunConfig,
+    environment: Option<&'a EnvironmentInfo>,
+}
+
+impl Reuse<'_> {
+    /// Hashes the inputs of `target`; `None` if it declares none or they
+    /// cannot be read.
+    fn input_hash(&self, target: &dyn BenchTarget) -> Option<String> {
+        let inputs = target.inputs();
+        if inputs.is_empty() {
+            return None;
+        }
+        cache::input_hash(&target.id(), &inputs, self.config, self.environment)
+            .map_err(|e| warn!("Failed to hash inputs of {}: {}", target.id(), e))
+            .ok()
+    }
+
+    /// Returns the cached result for `input_hash`, marked as cached.
+    fn lookup(&self, input_hash: &str) -> Option<BenchmarkResult> {
+        if self.mode == CacheMode::Refresh {
+            return None;
+        }
+        let mut result = self.cache.lookup(input_hash)?;
+        result.cached = true;
+        Some(result)
+    }
+}
+
+async fn run_targets(
+    forge_root: &Path,
+    config: &RunConfig,
+    events: &EventPublisher,
+    reuse: Option<&Reuse<'_>>,
+) -> Vec<BenchmarkResult> {
+    let targets = all_targets(forge_root, config);
+    let total = targets.len();
+    let mut results = Vec::with_capacity(total);
 
-    info!("Starting benchmark suite with {} targets", targets.len());
+    info!("Starting benchmark suite with {} targets", total);
 
-    for target in targets {
+    #[cfg(feature = "infra-metrics")]
+    if let Err(e) = crate::infra::metrics::init_metrics() {
+        warn!("Failed to initialize metrics: {}", e);
+    }
+
+    for (index, target) in targets.into_iter().enumerate() {
         let target_id = target.id();
         info!("Running benchmark: {}", target_id);
+        events.publish(RunEvent::TargetStarted {
+            target_id: target_id.clone(),
+            index,
+            total,
+            at: Utc::now(),
+        });
 
-        match target.run().await {
+        // A cached result is only valid while the target could still run
+        if let Err(reason) = check_preconditions(&target.preconditions()) {
+            info!("Skipping benchmark {}: {}", target_id, reason);
+            let result = BenchmarkResult::skipped(target_id, reason);
+            events.publish(RunEvent::target_done(&result));
+            results.push(result);
+            continue;
+        }
+
+        let input_hash = reuse.and_then(|reuse| reuse.input_hash(target.as_ref()));
+        let cached = reuse
+            .zip(input_hash.as_deref())
+            .and_then(|(reuse, hash)| reuse.lookup(hash));
+        if let Some(mut result) = cached {
+            info!("Reusing cached result for {} (inputs unchanged)", target_id);
+            // Retries and quarantine describe this run, not the cached one
+            result.attempts = 1;
+            result.quarantined = config.is_quarantined(&result.target_id);
+            events.publish(RunEvent::target_done(&result));
+            results.push(result);
+            continue;
+        }
+
+        // Only time spent running attempts counts, not the backoff between them
+        let mut elapsed = Duration::ZERO;
+        let policy = config.retry_policy(&target_id);
+        let mut attempt = 1;
+        let outcome = loop {
+            let started = Instant::now();
+            let outcome = events::scope(events.clone(), target_id.clone(), target.run()).await;
+            elapsed += started.elapsed();
+            match outcome {
+                Err(e) if policy.should_retry(attempt, &e) => {
+                    let delay = policy.backoff(attempt);
+                    warn!(
+                        "Benchmark {} attempt {}/{} failed ({}): {}; retrying in {:?}",
+                        target_id,
+                        attempt,
+                        policy.max_attempts,
+                        e.kind(),
+                        e,
+                        delay
+                    );
+                    events.publish(RunEvent::TargetRetrying {
+                        target_id: target_id.clone(),
+                        attempt,
+                        max_attempts: policy.max_attempts,
+                        error: e.to_string(),
+                        at: Utc::now(),
+                    });
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                outcome => break outcome,
+            }
+        };
+        let mut result = match outcome {
             Ok(result) => {
-                info!(
-                    "Benchmark {} completed successfully",
-                    result.target_id
-                );
-                results.push(result);
+                info!("Benchmark {} completed successfully", result.target_id);
+                result
+            }
+            Err(BenchError::Precondition { message }) => {
+                info!("Skipping benchmark {}: {}", target_id, message);
+                BenchmarkResult::skipped(target_id, message)
             }
             Err(e) => {
-                warn!("Benchmark {} failed: {}", target_id, e);
+                warn!("Benchmark {} failed ({}): {}", target_id, e.kind(), e);
                 // Create a failed result entry
-                results.push(BenchmarkResult::failed(target_id, e.to_string()));
+                BenchmarkResult::from_error(target_id, e)
             }
+        };
+        let duration_secs = elapsed.as_secs_f64();
+        debug!("Benchmark {} took {:.3}s", result.target_id, duration_secs);
+        result.input_hash = input_hash;
+        result.attempts = attempt;
+        result.quarantined = config.is_quarantined(&result.target_id);
+        if let Some(reuse) = reuse.filter(|_| result.is_passed()) {
+            reuse.cache.store(&result);
+        }
+        events.publish(RunEvent::target_done(&result));
+
+        #[cfg(feature = "infra-metrics")]
+        if !result.is_skipped() {
+            crate::infra::metrics::record_result(&result, duration_secs);
         }
+
+        results.push(result);
     }
 
     info!("Benchmark suite completed: {} results", results.len());
     results
 }
 
+/// Runs all registered benchmark targets as a single identified run.
+///
+/// Captures the environment before any target executes, then groups the
+/// results in a `RunReport` with a fresh run ID and start/finish timestamps.
+/// The targets run in `config.forge_root` if set, otherwise in the checkout
+/// resolved by `resolve_forge_root`; the root used is recorded in the
+/// report's configuration.
+///
+/// # Arguments
+///
+/// * `config` - Effective configuration of the run
+///
+/// # Returns
+///
+/// The finished `RunReport`, or a `ForgeRootError` if the Forge root is
+/// not an llm-forge checkout or none was found.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use forge_benchmarks::benchmarks::config::RunConfig;
+/// use forge_benchmarks::benchmarks::run_suite;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let report = run_suite(RunConfig::default()).await.expect("llm-forge checkout");
+///     println!("Run {} completed {} benchmarks", report.run_id, report.results.len());
+/// }
+/// ```
+pub async fn run_suite(config: RunConfig) -> Result<RunReport, ForgeRootError> {
+    run_suite_with_events(config, &EventPublisher::disabled()).await
+}
+
+/// Runs all registered benchmark targets as a single identified run,
+/// publishing its progress.
+///
+/// Publishes `RunStarted`, the target events of
+/// `run_all_benchmarks_with_events`, then `RunFinished`.
+///
+/// # Arguments
+///
+/// * `config` - Effective configuration of the run
+/// * `events` - Publisher the events are sent to
+///
+/// # Returns
+///
+/// The finished `RunReport`, or a `ForgeRootError` (see `run_suite`).
+pub async fn run_suite_with_events(
+    config: RunConfig,
+    events: &EventPublisher,
+) -> Result<RunReport, ForgeRootError> {
+    run_suite_inner(config, events, None).await
+}
+
+/// Runs all registered benchmark targets as a single identified run,
+/// reusing cached results of targets whose inputs are unchanged.
+///
+/// A target is reused when it declares inputs (see `BenchTarget::inputs`)
+/// and `cache` holds a result for their hash. Reused results are marked
+/// `cached`; freshly measured ones carry their `input_hash` and are stored
+/// in `cache` if they passed.
+///
+/// # Arguments
+///
+/// * `config` - Effective configuration of the run
+/// * `events` - Publisher the events are sent to
+/// * `cache` - Storage of earlier results
+/// * `mode` - `Refresh` measures every target and only updates `cache`
+///
+/// # Returns
+///
+/// The finished `RunReport`, or a `ForgeRootError` (see `run_suite`).
+pub async fn run_suite_cached(
+    config: RunConfig,
+    events: &EventPublisher,
+    cache: &dyn ResultCache,
+    mode: CacheMode,
+) -> Result<RunReport, ForgeRootError> {
+    run_suite_inner(config, events, Some((cache, mode))).await
+}
+
+async fn run_suite_inner(
+    mut config: RunConfig,
+    events: &EventPublisher,
+    cache: Option<(&dyn ResultCache, CacheMode)>,
+) -> Result<RunReport, ForgeRootError> {
+    // A configured root is explicit; the binary has already applied its
+    // flag and the environment to it
+    let forge_root = resolve_forge_root(config.forge_root.as_deref(), None)?;
+    config.forge_root = Some(forge_root.clone());
+    let environment = EnvironmentInfo::capture(&forge_root);
+    let mut report = RunReport::begin(config, Some(environment));
+
+    info!("Starting run {}", report.run_id);
+    events.publish(RunEvent::RunStarted {
+        run_id: report.run_id,
+        at: report.started_at,
+    });
+    let reuse = cache.map(|(cache, mode)| Reuse {
+        cache,
+        mode,
+        config: &report.config,
+        environment: report.environment.as_ref(),
+    });
+    report.results = run_targets(&forge_root, &report.config, events, reuse.as_ref()).await;
+
+    let report = report.finish();
+    events.publish(RunEvent::RunFinished {
+        run_id: report.run_id,
+        passed: report.passed_count(),
+        failed: report.failed_count(),
+        skipped: report.skipped_count(),
+        at: report.finished_at,
+    });
+    Ok(report)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[tokio::test]
     async fn test_run_all_benchmarks() {
-        let results = run_all_benchmarks().await;
+        // Outside an llm-forge checkout, run against an empty root where
+        // every target is skipped for its unmet preconditions
+        let fixture = tempfile::TempDir::new().unwrap();
+        let results = match run_all_benchmarks().await {
+            Ok(results) => results,
+            Err(ForgeRootError::NotFound { .. }) => {
+                let results = run_targets(
+                    fixture.path(),
+                    &RunConfig::default(),
+                    &EventPublisher::disabled(),
+                    None,
+                )
+                .await;
+                assert!(results.iter().all(|result| result.is_skipped()));
+                results
+            }
+            Err(e) => panic!("unexpected Forge root error: {}", e),
+        };
         // Should have at least the registered targets
         assert!(!results.is_empty(), "Should have benchmark results");
 
         for result in &results {
-            assert!(!result.target_id.is_empty(), "Target ID should not be empty");
-            assert!(result.timestamp <= chrono::Utc::now(), "Timestamp should be in the past");
+            assert!(
+                !result.target_id.is_empty(),
+                "Target ID should not be empty"
+            );
+            assert!(
+                result.timestamp <= chrono::Utc::now(),
+                "Timestamp should be in the past"
+            );
         }
     }
 }
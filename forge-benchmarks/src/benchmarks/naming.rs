@@ -0,0 +1,199 @@
+//! Safe file naming for per-target result files.
+//!
+//! Target IDs may come from user manifests and vitest case names, so they
+//! cannot be used as file names directly. This module turns an ID into a
+//! normalized slug plus a short hash of the original ID, and maintains an
+//! index mapping each `target_id` to the file it was written to.
+
+use super::io::{write_atomic, IoError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Name of the index file inside the raw output directory.
+pub const INDEX_FILE: &str = "index.json";
+
+/// Maximum length of the slug part of a file name.
+pub const MAX_SLUG_LEN: usize = 64;
+
+/// Number of hex characters of the ID hash appended to the slug.
+const HASH_LEN: usize = 12;
+
+/// Device names reserved on Windows regardless of extension.
+const RESERVED_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Mapping from target ID to raw result file name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawIndex {
+    /// File name (within the raw directory) for each target ID.
+    pub entries: BTreeMap<String, String>,
+}
+
+impl RawIndex {
+    /// Loads the index from a raw output directory.
+    ///
+    /// A missing index yields an empty one.
+    pub fn load(raw_dir: &Path) -> Result<Self, IoError> {
+        let path = raw_dir.join(INDEX_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Atomically writes the index into a raw output directory.
+    pub fn save(&self, raw_dir: &Path) -> Result<(), IoError> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_atomic(&raw_dir.join(INDEX_FILE), json.as_bytes())
+    }
+
+    /// Records the file name for a target ID and returns it.
+    pub fn insert(&mut self, target_id: &str) -> String {
+        let filename = raw_filename(target_id);
+        self.entries.insert(target_id.to_string(), filename.clone());
+        filename
+    }
+
+    /// Looks up the file name recorded for a target ID.
+    ///
+    /// Entries that are not a single safe path component are ignored, so a
+    /// tampered index cannot point outside the raw directory.
+    pub fn get(&self, target_id: &str) -> Option<&str> {
+        self.entries
+            .get(target_id)
+            .map(String::as_str)
+            .filter(|name| is_safe_component(name))
+    }
+}
+
+/// Returns the raw result file name for a target ID.
+///
+/// The name is `<slug>-<hash>.json`, where the slug is the ID reduced to
+/// `[a-z0-9._-]`, stripped of leading dots and separators, prefixed if it is
+/// a reserved device name, and truncated to `MAX_SLUG_LEN` characters. The
+/// hash of the original ID keeps distinct IDs from colliding.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::naming::raw_filename;
+///
+/// let name = raw_filename("../../etc/passwd");
+/// assert!(name.starts_with("etc_passwd-"));
+/// assert!(name.ends_with(".json"));
+/// ```
+pub fn raw_filename(target_id: &str) -> String {
+    format!("{}-{}.json", slugify(target_id), short_hash(target_id))
+}
+
+/// Normalizes a target ID into a file-name-safe slug.
+pub fn slugify(target_id: &str) -> String {
+    let mut slug = String::with_capacity(target_id.len());
+    for c in target_id.chars() {
+        let mapped = match c {
+            'a'..='z' | '0'..='9' | '-' | '.' => c,
+            'A'..='Z' => c.to_ascii_lowercase(),
+            _ => '_',
+        };
+        // Collapse runs of replacement characters
+        if mapped == '_' && slug.ends_with('_') {
+            continue;
+        }
+        slug.push(mapped);
+    }
+
+    let mut slug: String = slug
+        .trim_start_matches(['.', '_', '-'])
+        .chars()
+        .take(MAX_SLUG_LEN)
+        .collect();
+    while slug.ends_with(['.', '_']) {
+        slug.pop();
+    }
+    // `..` inside a name is harmless, but keep slugs free of it for clarity
+    while slug.contains("..") {
+        slug = slug.replace("..", ".");
+    }
+
+    if slug.is_empty() {
+        return "target".to_string();
+    }
+    let stem = slug.split('.').next().unwrap_or_default();
+    if RESERVED_NAMES.contains(&stem) {
+        slug.insert(0, '_');
+    }
+    slug
+}
+
+/// Returns a short, stable hex hash of a target ID.
+fn short_hash(target_id: &str) -> String {
+    let digest = Sha256::digest(target_id.as_bytes());
+    let hex = format!("{:x}", digest);
+    hex[..HASH_LEN].to_string()
+}
+
+/// Checks that a name is a single, non-special path component.
+pub(crate) fn is_safe_component(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', '\0'])
+        && !name.chars().any(char::is_control)
+        && Path::new(name).components().count() == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_slugify_normalizes() {
+        assert_eq!(slugify("forge-cli-parse"), "forge-cli-parse");
+        assert_eq!(
+            slugify("Provider Detection: OpenAI"),
+            "provider_detection_openai"
+        );
+        assert_eq!(slugify("../../secret"), "secret");
+        assert_eq!(slugify(".hidden"), "hidden");
+        assert_eq!(slugify("a\u{0}b\u{7}c"), "a_b_c");
+        assert_eq!(slugify("CON"), "_con");
+        assert_eq!(slugify("nul.txt"), "_nul.txt");
+        assert_eq!(slugify("..."), "target");
+        assert_eq!(slugify(&"x".repeat(500)).len(), MAX_SLUG_LEN);
+    }
+
+    #[test]
+    fn test_raw_filename_is_collision_free() {
+        let a = raw_filename("suite/case a");
+        let b = raw_filename("suite/case_a");
+
+        assert_ne!(a, b);
+        assert!(a.starts_with("suite_case_a-"));
+        assert_eq!(raw_filename("suite/case a"), a, "names must be stable");
+        assert!(is_safe_component(&a));
+    }
+
+    #[test]
+    fn test_index_round_trip_rejects_unsafe_entries() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut index = RawIndex::default();
+        let name = index.insert("target/one");
+        index
+            .entries
+            .insert("evil".to_string(), "../../etc/passwd".to_string());
+        index.save(temp_dir.path()).unwrap();
+
+        let loaded = RawIndex::load(temp_dir.path()).unwrap();
+        assert_eq!(loaded.get("target/one"), Some(name.as_str()));
+        assert_eq!(loaded.get("evil"), None);
+        assert_eq!(loaded.get("missing"), None);
+    }
+}
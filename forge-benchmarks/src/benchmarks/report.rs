@@ -0,0 +1,197 @@
+//! Rendering context shared by the report generators.
+//!
+//! Report output depends only on its inputs and a `ReportContext`: the
+//! generation timestamp, run ID, result ordering and number formatting are
+//! all passed in explicitly, so the same run always renders to byte-identical
+//! Markdown and HTML.
+
+use super::result::BenchmarkResult;
+use super::run::RunReport;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Format of timestamps in generated reports.
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
+
+/// Order of targets in generated reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// The order in which targets were executed.
+    #[default]
+    Recorded,
+
+    /// Alphabetical by target ID.
+    TargetId,
+}
+
+/// Number formatting convention of generated reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberLocale {
+    /// Decimal point, e.g. `1.50M`.
+    #[default]
+    En,
+
+    /// Decimal comma, e.g. `1,50M`.
+    De,
+}
+
+impl NumberLocale {
+    /// Formats a number compactly with two decimals and a `K`/`M` suffix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use forge_benchmarks::benchmarks::report::NumberLocale;
+    ///
+    /// assert_eq!(NumberLocale::En.format(1_500_000.0), "1.50M");
+    /// assert_eq!(NumberLocale::De.format(1_500.0), "1,50K");
+    /// ```
+    pub fn format(&self, n: f64) -> String {
+        let formatted = if n >= 1_000_000.0 {
+            format!("{:.2}M", n / 1_000_000.0)
+        } else if n >= 1_000.0 {
+            format!("{:.2}K", n / 1_000.0)
+        } else {
+            format!("{:.2}", n)
+        };
+
+        match self {
+            NumberLocale::En => formatted,
+            NumberLocale::De => formatted.replace('.', ","),
+        }
+    }
+}
+
+/// Report layout options chosen in the run configuration.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ReportOptions {
+    /// Order of targets in reports.
+    pub sort: SortOrder,
+
+    /// Number formatting convention.
+    pub locale: NumberLocale,
+
+    /// Handlebars template rendering `summary.md`. A relative path is
+    /// resolved against the directory of the configuration file (see
+    /// `RunConfig::load`). The built-in layout (`template::DEFAULT_TEMPLATE`)
+    /// is used when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<PathBuf>,
+}
+
+/// Everything a report generator needs besides the data itself.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use forge_benchmarks::benchmarks::report::ReportContext;
+///
+/// let ctx = ReportContext::new(Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap());
+/// assert_eq!(ctx.format_timestamp(), "2026-01-02 03:04:05 UTC");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportContext {
+    /// Timestamp printed as the generation time.
+    pub generated_at: DateTime<Utc>,
+
+    /// Run the report describes, if any.
+    pub run_id: Option<Uuid>,
+
+    /// Order of targets.
+    pub sort: SortOrder,
+
+    /// Number formatting convention.
+    pub locale: NumberLocale,
+}
+
+impl ReportContext {
+    /// Creates a context with default options and no run ID.
+    pub fn new(generated_at: DateTime<Utc>) -> Self {
+        Self {
+            generated_at,
+            run_id: None,
+            sort: SortOrder::default(),
+            locale: NumberLocale::default(),
+        }
+    }
+
+    /// Creates the context for rendering a run.
+    ///
+    /// The generation time is the run's `finished_at`, and the options come
+    /// from the run's configuration, so rendering the same run twice yields
+    /// identical output.
+    pub fn for_run(report: &RunReport) -> Self {
+        Self {
+            generated_at: report.finished_at,
+            run_id: (!report.run_id.is_nil()).then_some(report.run_id),
+            sort: report.config.report.sort,
+            locale: report.config.report.locale,
+        }
+    }
+
+    /// Formats a number according to the locale.
+    pub fn format_number(&self, n: f64) -> String {
+        self.locale.format(n)
+    }
+
+    /// Formats the generation timestamp.
+    pub fn format_timestamp(&self) -> String {
+        self.generated_at.format(TIMESTAMP_FORMAT).to_string()
+    }
+
+    /// Returns the results in report order.
+    pub fn sorted<'a>(&self, results: &'a [BenchmarkResult]) -> Vec<&'a BenchmarkResult> {
+        let mut sorted: Vec<&BenchmarkResult> = results.iter().collect();
+        if self.sort == SortOrder::TargetId {
+            // Stable, so duplicate IDs keep their recorded order
+            sorted.sort_by(|a, b| a.target_id.cmp(&b.target_id));
+        }
+        sorted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::config::RunConfig;
+    use serde_json::json;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(NumberLocale::En.format(1_500_000.0), "1.50M");
+        assert_eq!(NumberLocale::En.format(15_000.0), "15.00K");
+        assert_eq!(NumberLocale::En.format(500.0), "500.00");
+        assert_eq!(NumberLocale::De.format(500.0), "500,00");
+    }
+
+    #[test]
+    fn test_context_for_run_is_deterministic() {
+        let mut config = RunConfig::default();
+        config.report.sort = SortOrder::TargetId;
+        let mut report = RunReport::begin(config, None);
+        report.results = vec![
+            BenchmarkResult::new("b".to_string(), json!({})),
+            BenchmarkResult::new("a".to_string(), json!({})),
+        ];
+        let report = report.finish();
+
+        let ctx = ReportContext::for_run(&report);
+        assert_eq!(ctx, ReportContext::for_run(&report));
+        assert_eq!(ctx.generated_at, report.finished_at);
+        assert_eq!(ctx.run_id, Some(report.run_id));
+
+        let order: Vec<_> = ctx
+            .sorted(&report.results)
+            .iter()
+            .map(|r| r.target_id.as_str())
+            .collect();
+        assert_eq!(order, ["a", "b"]);
+    }
+}
@@ -3,15 +3,18 @@
 //! This module defines the canonical `BenchmarkResult` struct used across
 //! all 25 benchmark-target repositories for consistent result reporting.
 
+use super::error::BenchError;
+use super::metrics::{Metrics, ResultStatus};
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
 /// Standardized benchmark result structure.
 ///
 /// This struct contains the exact fields required by the unified benchmark interface:
 /// - `target_id`: Unique identifier for the benchmark target
-/// - `metrics`: JSON value containing benchmark-specific metrics
+/// - `metrics`: Typed metrics (see `Metrics`), with an extension map for
+///   benchmark-specific values
 /// - `timestamp`: UTC timestamp when the benchmark completed
 ///
 /// # Example
@@ -31,26 +34,61 @@ use serde_json::Value;
 ///
 /// assert_eq!(result.target_id, "provider-detection");
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(description = "Result of a single benchmark target.")]
 pub struct BenchmarkResult {
     /// Unique identifier for the benchmark target.
     ///
     /// This should match the `id()` returned by the corresponding `BenchTarget`.
     pub target_id: String,
 
-    /// JSON value containing benchmark-specific metrics.
+    /// Typed benchmark metrics.
     ///
-    /// The structure of this field varies by benchmark type but commonly includes:
+    /// Well-known fields include:
     /// - `ops_per_sec`: Operations per second
     /// - `avg_ns`: Average nanoseconds per operation
     /// - `min_ns`: Minimum nanoseconds observed
     /// - `max_ns`: Maximum nanoseconds observed
     /// - `samples`: Number of samples collected
     /// - `error`: Error message if the benchmark failed
-    pub metrics: Value,
+    /// - `failure`: Typed cause of the failure (see `BenchError`)
+    /// - `skip_reason`: Unmet precondition if the benchmark was skipped
+    ///
+    /// Any other keys are kept in `Metrics::extra`.
+    pub metrics: Metrics,
 
     /// UTC timestamp when the benchmark completed.
     pub timestamp: DateTime<Utc>,
+
+    /// Content hash of the inputs the result was measured with.
+    ///
+    /// Set by the runner when result caching is enabled. A later run whose
+    /// inputs hash to the same value may reuse this result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_hash: Option<String>,
+
+    /// Set when the result was reused from an earlier run instead of being
+    /// measured.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+
+    /// Number of times the target was run to produce this result; more
+    /// than one when failed attempts were retried.
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub attempts: u32,
+
+    /// Set when the target is quarantined: its failure does not fail the
+    /// build.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub quarantined: bool,
+}
+
+fn one() -> u32 {
+    1
+}
+
+fn is_one(value: &u32) -> bool {
+    *value == 1
 }
 
 impl BenchmarkResult {
@@ -59,16 +97,20 @@ impl BenchmarkResult {
     /// # Arguments
     ///
     /// * `target_id` - Unique identifier for the benchmark target
-    /// * `metrics` - JSON value containing benchmark metrics
+    /// * `metrics` - Typed metrics, or a JSON value converted via `Metrics::from`
     ///
     /// # Returns
     ///
     /// A new `BenchmarkResult` instance with the current timestamp.
-    pub fn new(target_id: String, metrics: Value) -> Self {
+    pub fn new(target_id: String, metrics: impl Into<Metrics>) -> Self {
         Self {
             target_id,
-            metrics,
+            metrics: metrics.into(),
             timestamp: Utc::now(),
+            input_hash: None,
+            cached: false,
+            attempts: 1,
+            quarantined: false,
         }
     }
 
@@ -77,17 +119,25 @@ impl BenchmarkResult {
     /// # Arguments
     ///
     /// * `target_id` - Unique identifier for the benchmark target
-    /// * `metrics` - JSON value containing benchmark metrics
+    /// * `metrics` - Typed metrics, or a JSON value converted via `Metrics::from`
     /// * `timestamp` - Specific UTC timestamp
     ///
     /// # Returns
     ///
     /// A new `BenchmarkResult` instance with the specified timestamp.
-    pub fn with_timestamp(target_id: String, metrics: Value, timestamp: DateTime<Utc>) -> Self {
+    pub fn with_timestamp(
+        target_id: String,
+        metrics: impl Into<Metrics>,
+        timestamp: DateTime<Utc>,
+    ) -> Self {
         Self {
             target_id,
-            metrics,
+            metrics: metrics.into(),
             timestamp,
+            input_hash: None,
+            cached: false,
+            attempts: 1,
+            quarantined: false,
         }
     }
 
@@ -102,26 +152,96 @@ impl BenchmarkResult {
     ///
     /// A new `BenchmarkResult` with error metrics.
     pub fn failed(target_id: String, error: String) -> Self {
-        Self::new(
-            target_id,
-            serde_json::json!({
-                "status": "failed",
-                "error": error
-            }),
-        )
+        Self::new(target_id, Metrics::failed(error))
+    }
+
+    /// Creates a failed benchmark result from a typed error.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_id` - Unique identifier for the benchmark target
+    /// * `error` - Cause of the failure
+    ///
+    /// # Returns
+    ///
+    /// A new `BenchmarkResult` with error metrics and the typed `failure`.
+    pub fn from_error(target_id: String, error: BenchError) -> Self {
+        Self::new(target_id, Metrics::failed_with(error))
+    }
+
+    /// Creates a skipped benchmark result.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_id` - Unique identifier for the benchmark target
+    /// * `reason` - Unmet precondition that kept the target from running
+    ///
+    /// # Returns
+    ///
+    /// A new `BenchmarkResult` with `skipped` status and no measurements.
+    pub fn skipped(target_id: String, reason: String) -> Self {
+        Self::new(target_id, Metrics::skipped(reason))
+    }
+
+    /// Returns the typed cause of the failure, if one was recorded.
+    ///
+    /// Results written before the taxonomy existed, or failed via
+    /// `failed`, only carry the error message.
+    pub fn failure(&self) -> Option<&BenchError> {
+        self.metrics.failure.as_ref()
     }
 
     /// Checks if this benchmark result represents a failure.
     ///
     /// # Returns
     ///
-    /// `true` if the metrics contain a "status" field with value "failed".
+    /// `true` if the metrics status is `failed`.
     pub fn is_failed(&self) -> bool {
-        self.metrics
-            .get("status")
-            .and_then(|v| v.as_str())
-            .map(|s| s == "failed")
-            .unwrap_or(false)
+        self.metrics.status == ResultStatus::Failed
+    }
+
+    /// Checks if this benchmark was skipped because a precondition was not met.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the metrics status is `skipped`.
+    pub fn is_skipped(&self) -> bool {
+        self.metrics.status == ResultStatus::Skipped
+    }
+
+    /// Checks if this benchmark ran and produced measurements.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the metrics status is `passed`.
+    pub fn is_passed(&self) -> bool {
+        self.metrics.status == ResultStatus::Passed
+    }
+
+    /// Returns the status shown in reports.
+    ///
+    /// # Returns
+    ///
+    /// `FAILED`, `FAILED (quarantined)`, `SKIPPED`, `PASSED`, or
+    /// `PASSED (cached)` for a reused result.
+    pub fn status_label(&self) -> &'static str {
+        if self.is_failed() && self.quarantined {
+            "FAILED (quarantined)"
+        } else if self.is_failed() {
+            "FAILED"
+        } else if self.is_skipped() {
+            "SKIPPED"
+        } else if self.cached {
+            "PASSED (cached)"
+        } else {
+            "PASSED"
+        }
+    }
+
+    /// Checks if this result fails the build: it failed and its target is
+    /// not quarantined.
+    pub fn is_blocking_failure(&self) -> bool {
+        self.is_failed() && !self.quarantined
     }
 
     /// Gets the operations per second if available in metrics.
@@ -130,7 +250,7 @@ impl BenchmarkResult {
     ///
     /// `Some(ops)` if `ops_per_sec` is present, `None` otherwise.
     pub fn ops_per_sec(&self) -> Option<f64> {
-        self.metrics.get("ops_per_sec").and_then(|v| v.as_f64())
+        self.metrics.ops_per_sec
     }
 
     /// Gets the average nanoseconds per operation if available.
@@ -139,7 +259,7 @@ impl BenchmarkResult {
     ///
     /// `Some(ns)` if `avg_ns` is present, `None` otherwise.
     pub fn avg_ns(&self) -> Option<f64> {
-        self.metrics.get("avg_ns").and_then(|v| v.as_f64())
+        self.metrics.avg_ns
     }
 }
 
@@ -156,7 +276,7 @@ mod tests {
         );
 
         assert_eq!(result.target_id, "test-target");
-        assert_eq!(result.metrics["ops_per_sec"], 1000000);
+        assert_eq!(result.metrics.ops_per_sec, Some(1000000.0));
         assert!(result.timestamp <= Utc::now());
     }
 
@@ -168,7 +288,66 @@ mod tests {
         );
 
         assert!(result.is_failed());
-        assert_eq!(result.metrics["error"], "Connection timeout");
+        assert_eq!(result.metrics.error.as_deref(), Some("Connection timeout"));
+        assert!(result.failure().is_none());
+    }
+
+    #[test]
+    fn test_skipped_benchmark_result() {
+        let result = BenchmarkResult::skipped(
+            "skipped-target".to_string(),
+            "node not found on PATH".to_string(),
+        );
+
+        assert!(result.is_skipped());
+        assert!(!result.is_failed());
+        assert!(!result.is_passed());
+        assert_eq!(result.status_label(), "SKIPPED");
+        assert_eq!(
+            result.metrics.skip_reason.as_deref(),
+            Some("node not found on PATH")
+        );
+    }
+
+    #[test]
+    fn test_typed_failure_roundtrip() {
+        let result = BenchmarkResult::from_error(
+            "failing-target".to_string(),
+            BenchError::Spawn {
+                command: "npx vitest".to_string(),
+                message: "No such file or directory".to_string(),
+            },
+        );
+        assert!(result.is_failed());
+        assert_eq!(
+            result.metrics.error.as_deref(),
+            Some("failed to spawn `npx vitest`: No such file or directory")
+        );
+
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["metrics"]["failure"]["kind"], "spawn");
+        let deserialized: BenchmarkResult = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized.failure(), result.failure());
+    }
+
+    #[test]
+    fn test_attempts_and_quarantine() {
+        let mut result = BenchmarkResult::failed("flaky".to_string(), "EBUSY".to_string());
+        let value = serde_json::to_value(&result).unwrap();
+        assert!(value.get("attempts").is_none());
+        assert!(value.get("quarantined").is_none());
+        assert!(result.is_blocking_failure());
+
+        result.attempts = 3;
+        result.quarantined = true;
+        assert!(!result.is_blocking_failure());
+        assert_eq!(result.status_label(), "FAILED (quarantined)");
+
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["attempts"], 3);
+        let deserialized: BenchmarkResult = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized.attempts, 3);
+        assert!(deserialized.quarantined);
     }
 
     #[test]
@@ -195,4 +374,21 @@ mod tests {
         assert_eq!(deserialized.target_id, result.target_id);
         assert_eq!(deserialized.metrics, result.metrics);
     }
+
+    #[test]
+    fn test_cached_marker() {
+        let mut result = BenchmarkResult::new("cache-test".to_string(), json!({}));
+        let value = serde_json::to_value(&result).unwrap();
+        assert!(value.get("input_hash").is_none());
+        assert!(value.get("cached").is_none());
+        assert_eq!(result.status_label(), "PASSED");
+
+        result.input_hash = Some("abc".to_string());
+        result.cached = true;
+        let deserialized: BenchmarkResult =
+            serde_json::from_str(&serde_json::to_string(&result).unwrap()).unwrap();
+        assert!(deserialized.cached);
+        assert_eq!(deserialized.input_hash.as_deref(), Some("abc"));
+        assert_eq!(deserialized.status_label(), "PASSED (cached)");
+    }
 }
@@ -0,0 +1,718 @@
+//! History compaction, rotation and retention.
+//!
+//! This module keeps `history.jsonl` bounded. Compaction keeps the most
+//! recent runs in full detail in the active log and moves older runs into
+//! one rotated segment per calendar month, optionally downsampled to daily or
+//! weekly aggregates and gzip-compressed. Runs evicted by later passes are
+//! merged into the existing segment and aggregates of their period.
+//! `HistoryReader` reads rotated segments transparently.
+
+use super::history::{history_path, HistoryReader};
+use super::io::{write_atomic, IoError, OUTPUT_DIR};
+use super::metrics::Metrics;
+use super::result::BenchmarkResult;
+use super::run::RunReport;
+use chrono::{DateTime, Datelike, Duration, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// File name prefix of rotated history segments.
+pub const SEGMENT_PREFIX: &str = "history-";
+
+/// Extension metric counting the passing runs folded into an aggregate.
+pub const PASSED_RUNS_METRIC: &str = "passed_runs";
+
+/// Extension metric counting the failing runs folded into an aggregate.
+pub const FAILED_RUNS_METRIC: &str = "failed_runs";
+
+/// Granularity that runs evicted from the active log are downsampled to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DownsamplePeriod {
+    /// Keep evicted runs in full detail.
+    #[default]
+    None,
+
+    /// One aggregate per target per UTC day.
+    Daily,
+
+    /// One aggregate per target per ISO week (starting Monday, UTC).
+    Weekly,
+}
+
+impl DownsamplePeriod {
+    /// Returns the start of the period containing a timestamp.
+    fn bucket_start(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let midnight = timestamp
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .map(|dt| dt.and_utc())
+            .unwrap_or(timestamp);
+        match self {
+            DownsamplePeriod::None => timestamp,
+            DownsamplePeriod::Daily => midnight,
+            DownsamplePeriod::Weekly => {
+                midnight - Duration::days(i64::from(timestamp.weekday().num_days_from_monday()))
+            }
+        }
+    }
+}
+
+/// Retention policy for the history log.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::retention::{DownsamplePeriod, RetentionPolicy};
+///
+/// let policy = RetentionPolicy {
+///     keep_full_runs: Some(50),
+///     downsample: DownsamplePeriod::Daily,
+///     compress: true,
+///     max_segments: Some(12),
+/// };
+/// assert!(policy.is_enabled());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    /// Number of most recent runs kept in full detail in `history.jsonl`.
+    ///
+    /// `None` disables compaction entirely.
+    pub keep_full_runs: Option<usize>,
+
+    /// How runs evicted from the active log are downsampled.
+    pub downsample: DownsamplePeriod,
+
+    /// Gzip-compress rotated segments.
+    pub compress: bool,
+
+    /// Maximum number of monthly segments to keep; segments of older months
+    /// are deleted.
+    pub max_segments: Option<usize>,
+}
+
+impl RetentionPolicy {
+    /// Checks whether the policy performs any compaction.
+    pub fn is_enabled(&self) -> bool {
+        self.keep_full_runs.is_some()
+    }
+}
+
+/// Marks a `RunReport` as a downsampled aggregate of several runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AggregateInfo {
+    /// Granularity of the aggregate.
+    pub period: DownsamplePeriod,
+
+    /// Start of the aggregated period.
+    pub period_start: DateTime<Utc>,
+
+    /// Number of runs folded into the aggregate.
+    pub run_count: usize,
+
+    /// IDs of the runs folded into the aggregate; legacy runs have none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub run_ids: Vec<Uuid>,
+}
+
+/// Outcome of a compaction pass.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompactionSummary {
+    /// Runs kept in full detail in the active log.
+    pub kept_runs: usize,
+
+    /// Runs moved out of the active log.
+    pub evicted_runs: usize,
+
+    /// Records held by the segments written in this pass.
+    pub archived_records: usize,
+
+    /// Rotated segments written in this pass.
+    pub segments: Vec<PathBuf>,
+
+    /// Rotated segments deleted by `max_segments`.
+    pub deleted_segments: Vec<PathBuf>,
+}
+
+/// Applies a retention policy to the history log in the canonical output directory.
+///
+/// Runs beyond `keep_full_runs` are evicted from `history.jsonl` (oldest
+/// first) and merged into the segment of their month, named
+/// `history-<YYYY-MM>.jsonl[.gz]`, where they are downsampled according to
+/// the policy together with the records already there. Consecutive legacy
+/// single-result lines are grouped into runs first. Unreadable lines in the
+/// active log are dropped with a warning.
+///
+/// Segments are written before the active log is replaced, each atomically.
+/// Runs already recorded in a segment (left behind in the active log by an
+/// interrupted compaction) are not merged a second time, so a crash neither
+/// loses nor duplicates runs. Callers should hold an `OutputLock`.
+///
+/// # Arguments
+///
+/// * `base_path` - Base path where results are stored
+/// * `policy` - Retention policy to apply
+///
+/// # Returns
+///
+/// A `CompactionSummary`, or an `IoError`.
+pub fn compact_history(
+    base_path: &str,
+    policy: &RetentionPolicy,
+) -> Result<CompactionSummary, IoError> {
+    let Some(keep) = policy.keep_full_runs else {
+        return Ok(CompactionSummary::default());
+    };
+
+    let active = history_path(base_path);
+    if !active.exists() {
+        return Ok(CompactionSummary::default());
+    }
+
+    let runs: Vec<RunReport> = HistoryReader::open_file(&active)?.collect::<Result<_, _>>()?;
+    let mut runs = group_legacy_runs(runs);
+    let evict_count = runs.len().saturating_sub(keep);
+    let kept = runs.split_off(evict_count);
+    let evicted = runs;
+
+    let mut summary = CompactionSummary {
+        kept_runs: kept.len(),
+        evicted_runs: evicted.len(),
+        ..CompactionSummary::default()
+    };
+
+    if evicted.is_empty() {
+        return Ok(summary);
+    }
+
+    let mut by_month: BTreeMap<String, Vec<RunReport>> = BTreeMap::new();
+    for run in evicted {
+        let period_start = policy.downsample.bucket_start(run.started_at);
+        by_month
+            .entry(period_start.format("%Y-%m").to_string())
+            .or_default()
+            .push(run);
+    }
+
+    for (month, runs) in by_month {
+        let (segment, archived) = merge_into_segment(base_path, &month, runs, policy)?;
+        summary.archived_records += archived;
+        summary.segments.push(segment);
+    }
+    write_jsonl(&active, &kept)?;
+
+    info!(
+        "Compacted history: kept {} runs, archived {} runs into {} segment(s)",
+        summary.kept_runs,
+        summary.evicted_runs,
+        summary.segments.len()
+    );
+
+    if let Some(max_segments) = policy.max_segments {
+        let segments = rotated_segments(base_path)?;
+        let excess = segments.len().saturating_sub(max_segments);
+        for segment in segments.into_iter().take(excess) {
+            warn!(
+                "Deleting history segment {} (max_segments = {})",
+                segment.display(),
+                max_segments
+            );
+            fs::remove_file(&segment)?;
+            summary.deleted_segments.push(segment);
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Merges evicted runs into the segment of `month`.
+///
+/// Returns the path of the segment and the number of records it holds.
+fn merge_into_segment(
+    base_path: &str,
+    month: &str,
+    runs: Vec<RunReport>,
+    policy: &RetentionPolicy,
+) -> Result<(PathBuf, usize), IoError> {
+    let path = segment_path(base_path, month, policy.compress);
+    let other = segment_path(base_path, month, !policy.compress);
+
+    // After a change of `compress` the month may still be in the other
+    // format; if both exist, the current format was written last and holds
+    // everything the other does.
+    let source = [&path, &other].into_iter().find(|p| p.exists());
+    let mut records: Vec<RunReport> = match source {
+        Some(source) => HistoryReader::open_file(source)?.collect::<Result<_, _>>()?,
+        None => Vec::new(),
+    };
+
+    let archived: HashSet<Uuid> = records.iter().flat_map(folded_run_ids).collect();
+    records.extend(
+        runs.into_iter()
+            .filter(|run| run.is_legacy() || !archived.contains(&run.run_id)),
+    );
+    records.sort_by_key(|run| run.started_at);
+
+    let records = downsample(records, policy.downsample);
+    write_segment(&path, &records, policy.compress)?;
+    if other.exists() {
+        fs::remove_file(&other)?;
+    }
+    Ok((path, records.len()))
+}
+
+/// Returns the IDs of the runs a history record stands for.
+fn folded_run_ids(run: &RunReport) -> Vec<Uuid> {
+    match &run.aggregate {
+        Some(aggregate) => aggregate.run_ids.clone(),
+        None if run.is_legacy() => Vec::new(),
+        None => vec![run.run_id],
+    }
+}
+
+/// Groups consecutive legacy runs into whole runs.
+///
+/// Before run grouping every result was written as its own line, so each
+/// legacy line reads back as a run of one result. Consecutive lines belong
+/// to the same run until a target repeats.
+fn group_legacy_runs(runs: Vec<RunReport>) -> Vec<RunReport> {
+    let mut grouped: Vec<RunReport> = Vec::with_capacity(runs.len());
+    for run in runs {
+        if let Some(previous) = grouped.last_mut().filter(|previous| {
+            previous.is_legacy()
+                && run.is_legacy()
+                && run.results.iter().all(|result| {
+                    previous
+                        .results
+                        .iter()
+                        .all(|other| other.target_id != result.target_id)
+                })
+        }) {
+            previous.started_at = previous.started_at.min(run.started_at);
+            previous.finished_at = previous.finished_at.max(run.finished_at);
+            previous.results.extend(run.results);
+            continue;
+        }
+        grouped.push(run);
+    }
+    grouped
+}
+
+/// Lists rotated history segments, oldest first.
+///
+/// # Arguments
+///
+/// * `base_path` - Base path where results are stored
+///
+/// # Returns
+///
+/// Paths of `history-*.jsonl` and `history-*.jsonl.gz` files.
+pub fn rotated_segments(base_path: &str) -> Result<Vec<PathBuf>, IoError> {
+    let output_dir = Path::new(base_path).join(OUTPUT_DIR);
+    if !output_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut segments: Vec<PathBuf> = fs::read_dir(&output_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| is_segment(path))
+        .collect();
+    segments.sort();
+    Ok(segments)
+}
+
+/// Checks whether a path names a rotated history segment.
+pub(crate) fn is_segment(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| {
+            name.starts_with(SEGMENT_PREFIX)
+                && (name.ends_with(".jsonl") || name.ends_with(".jsonl.gz"))
+        })
+        .unwrap_or(false)
+}
+
+/// Folds runs into per-period aggregates.
+///
+/// Aggregates among the runs are merged with the other runs of their
+/// period. With `DownsamplePeriod::None` the runs are returned unchanged.
+pub fn downsample(runs: Vec<RunReport>, period: DownsamplePeriod) -> Vec<RunReport> {
+    if period == DownsamplePeriod::None {
+        return runs;
+    }
+
+    let mut buckets: BTreeMap<DateTime<Utc>, Vec<RunReport>> = BTreeMap::new();
+    for run in runs {
+        buckets
+            .entry(period.bucket_start(run.started_at))
+            .or_default()
+            .push(run);
+    }
+
+    buckets
+        .into_iter()
+        .map(|(start, runs)| aggregate_bucket(start, period, runs))
+        .collect()
+}
+
+fn aggregate_bucket(
+    period_start: DateTime<Utc>,
+    period: DownsamplePeriod,
+    runs: Vec<RunReport>,
+) -> RunReport {
+    let run_count = runs
+        .iter()
+        .map(|r| r.aggregate.as_ref().map(|a| a.run_count).unwrap_or(1))
+        .sum();
+    let run_ids = runs.iter().flat_map(folded_run_ids).collect();
+    let finished_at = runs
+        .iter()
+        .map(|r| r.finished_at)
+        .max()
+        .unwrap_or(period_start);
+    let config = runs.last().map(|r| r.config.clone()).unwrap_or_default();
+
+    let mut by_target: BTreeMap<String, Vec<BenchmarkResult>> = BTreeMap::new();
+    for result in runs.into_iter().flat_map(|r| r.results) {
+        by_target
+            .entry(result.target_id.clone())
+            .or_default()
+            .push(result);
+    }
+
+    let results = by_target
+        .into_iter()
+        .map(|(target_id, results)| {
+            BenchmarkResult::with_timestamp(target_id, aggregate_metrics(&results), period_start)
+        })
+        .collect();
+
+    RunReport {
+        run_id: Uuid::new_v4(),
+        started_at: period_start,
+        finished_at,
+        environment: None,
+        config,
+        results,
+        aggregate: Some(AggregateInfo {
+            period,
+            period_start,
+            run_count,
+            run_ids,
+        }),
+    }
+}
+
+/// Combines the metrics of several results for the same target.
+///
+/// Averages are taken over passing results, weighted by the number of runs
+/// each stands for; extremes and sample counts are combined across them.
+/// Passing and failing runs are counted in the `passed_runs` and
+/// `failed_runs` extensions. A target that never passed keeps the status and
+/// cause of its last result.
+fn aggregate_metrics(results: &[BenchmarkResult]) -> Metrics {
+    let passed: Vec<(f64, &Metrics)> = results
+        .iter()
+        .filter_map(|r| {
+            let runs = run_counter(r, PASSED_RUNS_METRIC, r.is_passed());
+            (runs > 0).then_some((runs as f64, &r.metrics))
+        })
+        .collect();
+    let passed_runs: u64 = results
+        .iter()
+        .map(|r| run_counter(r, PASSED_RUNS_METRIC, r.is_passed()))
+        .sum();
+    let failed_runs: u64 = results
+        .iter()
+        .map(|r| run_counter(r, FAILED_RUNS_METRIC, r.is_failed()))
+        .sum();
+
+    let mean = |f: fn(&Metrics) -> Option<f64>| {
+        let (sum, weight) = passed
+            .iter()
+            .filter_map(|(runs, m)| f(m).map(|value| (value * runs, *runs)))
+            .fold((0.0, 0.0), |(sum, weight), (v, w)| (sum + v, weight + w));
+        (weight > 0.0).then(|| sum / weight)
+    };
+
+    let mut metrics = Metrics {
+        avg_ns: mean(|m| m.avg_ns),
+        ops_per_sec: mean(|m| m.ops_per_sec),
+        min_ns: passed.iter().filter_map(|(_, m)| m.min_ns).reduce(f64::min),
+        max_ns: passed.iter().filter_map(|(_, m)| m.max_ns).reduce(f64::max),
+        samples: passed
+            .iter()
+            .filter_map(|(_, m)| m.samples)
+            .reduce(|a, b| a + b),
+        ..Metrics::default()
+    };
+
+    if let Some(last) = results.last().filter(|_| passed.is_empty()) {
+        metrics.status = last.metrics.status;
+        metrics.error = last.metrics.error.clone();
+        metrics.failure = last.metrics.failure.clone();
+        metrics.skip_reason = last.metrics.skip_reason.clone();
+    }
+    if passed_runs > 0 {
+        metrics
+            .extra
+            .insert(PASSED_RUNS_METRIC.to_string(), passed_runs.into());
+    }
+    if failed_runs > 0 {
+        metrics
+            .extra
+            .insert(FAILED_RUNS_METRIC.to_string(), failed_runs.into());
+    }
+    metrics
+}
+
+/// Returns how many runs a result counts for in the run counter `key`.
+///
+/// Aggregates carry the counter; a single result counts once if `counts`.
+fn run_counter(result: &BenchmarkResult, key: &str, counts: bool) -> u64 {
+    match result.metrics.extra.get(key) {
+        Some(value) => value.as_u64().unwrap_or(0),
+        None => u64::from(counts),
+    }
+}
+
+/// Names the segment of a month (`YYYY-MM`), so segment names sort
+/// chronologically.
+fn segment_path(base_path: &str, month: &str, compress: bool) -> PathBuf {
+    let extension = if compress { "jsonl.gz" } else { "jsonl" };
+    Path::new(base_path)
+        .join(OUTPUT_DIR)
+        .join(format!("{}{}.{}", SEGMENT_PREFIX, month, extension))
+}
+
+fn to_jsonl(runs: &[RunReport]) -> Result<String, IoError> {
+    let mut content = String::new();
+    for run in runs {
+        content.push_str(&serde_json::to_string(run)?);
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+fn write_jsonl(path: &Path, runs: &[RunReport]) -> Result<(), IoError> {
+    write_atomic(path, to_jsonl(runs)?.as_bytes())
+}
+
+fn write_segment(path: &Path, runs: &[RunReport], compress: bool) -> Result<(), IoError> {
+    if !compress {
+        return write_jsonl(path, runs);
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(to_jsonl(runs)?.as_bytes())?;
+    write_atomic(path, &encoder.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::config::RunConfig;
+    use crate::benchmarks::io::{append_to_history, ensure_output_dirs};
+    use chrono::TimeZone;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn run_at(day: u32, hour: u32, ops: f64) -> RunReport {
+        run_on(5, day, hour, ops)
+    }
+
+    fn run_on(month: u32, day: u32, hour: u32, ops: f64) -> RunReport {
+        let started_at = Utc.with_ymd_and_hms(2024, month, day, hour, 0, 0).unwrap();
+        let mut report = RunReport::begin(RunConfig::default(), None);
+        report.started_at = started_at;
+        report.finished_at = started_at;
+        report.results = vec![BenchmarkResult::with_timestamp(
+            "t".to_string(),
+            json!({"ops_per_sec": ops, "min_ns": ops, "samples": 10}),
+            started_at,
+        )];
+        report
+    }
+
+    fn history_with(runs: &[RunReport]) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+        ensure_output_dirs(base_path).unwrap();
+        for run in runs {
+            append_to_history(run, base_path).unwrap();
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn test_downsample_daily() {
+        let runs = vec![run_at(1, 1, 10.0), run_at(1, 5, 20.0), run_at(2, 1, 30.0)];
+
+        let aggregates = downsample(runs, DownsamplePeriod::Daily);
+
+        assert_eq!(aggregates.len(), 2);
+        let first = &aggregates[0];
+        assert_eq!(first.aggregate.as_ref().unwrap().run_count, 2);
+        assert_eq!(first.results[0].ops_per_sec(), Some(15.0));
+        assert_eq!(first.results[0].metrics.min_ns, Some(10.0));
+        assert_eq!(first.results[0].metrics.samples, Some(20));
+    }
+
+    #[test]
+    fn test_weekly_bucket_starts_on_monday() {
+        // 2024-05-01 is a Wednesday
+        let start = DownsamplePeriod::Weekly.bucket_start(run_at(1, 12, 0.0).started_at);
+        assert_eq!(start, Utc.with_ymd_and_hms(2024, 4, 29, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_compact_keeps_recent_runs_and_reads_segments() {
+        let runs: Vec<_> = (1..=5).map(|d| run_at(d, 0, d as f64)).collect();
+        let temp_dir = history_with(&runs);
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        let policy = RetentionPolicy {
+            keep_full_runs: Some(2),
+            downsample: DownsamplePeriod::Weekly,
+            compress: true,
+            max_segments: None,
+        };
+        let summary = compact_history(base_path, &policy).unwrap();
+
+        assert_eq!(summary.kept_runs, 2);
+        assert_eq!(summary.evicted_runs, 3);
+        assert_eq!(summary.segments.len(), 1);
+        assert!(summary.segments[0]
+            .to_string_lossy()
+            .ends_with("history-2024-04.jsonl.gz"));
+
+        // Reader sees the aggregate from the compressed segment, then full runs
+        let all: Vec<RunReport> = HistoryReader::open(base_path)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(all[0].aggregate.is_some());
+        assert_eq!(all[2].run_id, runs[4].run_id);
+    }
+
+    #[test]
+    fn test_compaction_merges_across_passes() {
+        let temp_dir = history_with(&[]);
+        let base_path = temp_dir.path().to_str().unwrap();
+        let policy = RetentionPolicy {
+            keep_full_runs: Some(1),
+            downsample: DownsamplePeriod::Daily,
+            ..RetentionPolicy::default()
+        };
+
+        // Compact after every run, as `run_benchmarks` does
+        for (hour, ops) in [(1, 10.0), (2, 20.0), (3, 60.0), (4, 0.0)] {
+            append_to_history(&run_at(1, hour, ops), base_path).unwrap();
+            compact_history(base_path, &policy).unwrap();
+        }
+
+        assert_eq!(rotated_segments(base_path).unwrap().len(), 1);
+        let all: Vec<RunReport> = HistoryReader::open(base_path)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        let aggregate = all[0].aggregate.as_ref().unwrap();
+        assert_eq!(aggregate.run_count, 3);
+        assert_eq!(aggregate.run_ids.len(), 3);
+        // Weighted by run, not by pass: (10 + 20 + 60) / 3
+        assert_eq!(all[0].results[0].ops_per_sec(), Some(30.0));
+        assert_eq!(all[0].results[0].metrics.samples, Some(30));
+        assert_eq!(all[0].results[0].metrics.extra[PASSED_RUNS_METRIC], 3);
+    }
+
+    #[test]
+    fn test_interrupted_compaction_does_not_duplicate_runs() {
+        let runs: Vec<_> = (1..=3).map(|h| run_at(1, h, 1.0)).collect();
+        let temp_dir = history_with(&runs);
+        let base_path = temp_dir.path().to_str().unwrap();
+        let policy = RetentionPolicy {
+            keep_full_runs: Some(1),
+            downsample: DownsamplePeriod::Daily,
+            ..RetentionPolicy::default()
+        };
+
+        // Simulate a crash after the segment was written: the active log
+        // still holds the archived runs
+        let active = history_path(base_path);
+        let before = fs::read_to_string(&active).unwrap();
+        compact_history(base_path, &policy).unwrap();
+        fs::write(&active, before).unwrap();
+        compact_history(base_path, &policy).unwrap();
+
+        let all: Vec<RunReport> = HistoryReader::open(base_path)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].aggregate.as_ref().unwrap().run_count, 2);
+    }
+
+    #[test]
+    fn test_legacy_lines_are_grouped_into_runs() {
+        let temp_dir = history_with(&[]);
+        let base_path = temp_dir.path().to_str().unwrap();
+        let mut content = String::new();
+        for (hour, target) in [(1, "a"), (1, "b"), (2, "a"), (2, "b"), (3, "a")] {
+            let timestamp = Utc.with_ymd_and_hms(2024, 5, 1, hour, 0, 0).unwrap();
+            let result = BenchmarkResult::with_timestamp(
+                target.to_string(),
+                json!({"ops_per_sec": 1.0}),
+                timestamp,
+            );
+            content.push_str(&serde_json::to_string(&result).unwrap());
+            content.push('\n');
+        }
+        fs::write(history_path(base_path), content).unwrap();
+
+        let policy = RetentionPolicy {
+            keep_full_runs: Some(1),
+            ..RetentionPolicy::default()
+        };
+        let summary = compact_history(base_path, &policy).unwrap();
+
+        assert_eq!(summary.evicted_runs, 2);
+        assert_eq!(summary.archived_records, 2);
+        let segment: Vec<RunReport> = HistoryReader::open_file(&summary.segments[0])
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(segment[0].results.len(), 2);
+    }
+
+    #[test]
+    fn test_max_segments_deletes_oldest() {
+        let runs: Vec<_> = (4..=7).map(|m| run_on(m, 1, 0, 1.0)).collect();
+        let temp_dir = history_with(&runs);
+        let base_path = temp_dir.path().to_str().unwrap();
+
+        let policy = RetentionPolicy {
+            keep_full_runs: Some(3),
+            max_segments: Some(1),
+            ..RetentionPolicy::default()
+        };
+        compact_history(base_path, &policy).unwrap();
+        append_to_history(&run_on(8, 1, 0, 1.0), base_path).unwrap();
+        let summary = compact_history(base_path, &policy).unwrap();
+
+        assert_eq!(summary.deleted_segments.len(), 1);
+        let segments = rotated_segments(base_path).unwrap();
+        assert_eq!(segments.len(), 1);
+        assert!(segments[0].ends_with("history-2024-05.jsonl"));
+    }
+}
@@ -0,0 +1,115 @@
+//! Retry policy for failed benchmark targets.
+//!
+//! Subprocess benchmarks occasionally fail for reasons unrelated to the code
+//! under benchmark, e.g. a locked npm cache or a transient filesystem error.
+//! The runner retries such failures according to the `RetryPolicy` of the
+//! target and records the number of attempts in its result.
+
+use super::error::BenchError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Default delay before the first retry, in milliseconds.
+pub const DEFAULT_BACKOFF_MS: u64 = 500;
+
+/// How often a failed target is attempted and how long to wait in between.
+///
+/// The delay starts at `backoff_ms` and doubles after every attempt.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::retry::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy {
+///     max_attempts: 3,
+///     backoff_ms: 100,
+/// };
+///
+/// assert_eq!(policy.backoff(1), Duration::from_millis(100));
+/// assert_eq!(policy.backoff(2), Duration::from_millis(200));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Number of attempts, including the first; `1` disables retries.
+    pub max_attempts: u32,
+
+    /// Delay before the first retry, in milliseconds.
+    pub backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff_ms: DEFAULT_BACKOFF_MS,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay after failed attempt number `attempt` (one-based).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u64
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        Duration::from_millis(self.backoff_ms.saturating_mul(factor))
+    }
+
+    /// Checks whether another attempt should follow a failure.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - One-based number of the attempt that failed
+    /// * `error` - Why it failed
+    ///
+    /// # Returns
+    ///
+    /// `true` if attempts are left and the failure may be transient.
+    /// Environmental failures (a missing tool, an unmet precondition) do
+    /// not go away on their own and are never retried.
+    pub fn should_retry(&self, attempt: u32, error: &BenchError) -> bool {
+        attempt < self.max_attempts && !error.is_environmental()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            backoff_ms: 250,
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(250));
+        assert_eq!(policy.backoff(3), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(200), Duration::from_millis(u64::MAX));
+    }
+
+    #[test]
+    fn test_should_retry() {
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            backoff_ms: 0,
+        };
+        let transient = BenchError::NonZeroExit {
+            command: "npx vitest bench".to_string(),
+            code: 1,
+            stderr: "EBUSY: resource busy or locked".to_string(),
+        };
+        let missing = BenchError::Spawn {
+            command: "npx".to_string(),
+            message: "not found".to_string(),
+        };
+
+        assert!(policy.should_retry(1, &transient));
+        assert!(!policy.should_retry(2, &transient));
+        assert!(!policy.should_retry(1, &missing));
+        assert!(!RetryPolicy::default().should_retry(1, &transient));
+    }
+}
@@ -0,0 +1,224 @@
+//! Run-level result envelope.
+//!
+//! This module defines `RunReport`, which groups the `BenchmarkResult`s of a
+//! single benchmark run together with its identity, timing, environment and
+//! effective configuration.
+
+use super::config::RunConfig;
+use super::environment::EnvironmentInfo;
+use super::result::BenchmarkResult;
+use super::retention::AggregateInfo;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// All results of a single benchmark run, with run metadata.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::config::RunConfig;
+/// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use forge_benchmarks::benchmarks::run::RunReport;
+/// use serde_json::json;
+///
+/// let mut report = RunReport::begin(RunConfig::default(), None);
+/// report.results.push(BenchmarkResult::new("target-1".to_string(), json!({})));
+/// let report = report.finish();
+///
+/// assert!(report.finished_at >= report.started_at);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(description = "All results of a single benchmark run, with run metadata.")]
+pub struct RunReport {
+    /// Unique identifier of the run.
+    ///
+    /// Reports converted from legacy result files use the nil UUID.
+    pub run_id: Uuid,
+
+    /// UTC timestamp when the run started.
+    pub started_at: DateTime<Utc>,
+
+    /// UTC timestamp when the run finished.
+    pub finished_at: DateTime<Utc>,
+
+    /// Environment the run executed in, if captured.
+    #[serde(default)]
+    pub environment: Option<EnvironmentInfo>,
+
+    /// Effective configuration of the run.
+    #[serde(default)]
+    pub config: RunConfig,
+
+    /// Results of every target executed in the run.
+    pub results: Vec<BenchmarkResult>,
+
+    /// Set when this record is a downsampled aggregate of several runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregate: Option<AggregateInfo>,
+}
+
+impl RunReport {
+    /// Starts a new run with a fresh run ID and no results.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Effective configuration of the run
+    /// * `environment` - Captured environment, if any
+    ///
+    /// # Returns
+    ///
+    /// A `RunReport` whose start and finish timestamps are both set to now.
+    pub fn begin(config: RunConfig, environment: Option<EnvironmentInfo>) -> Self {
+        let now = Utc::now();
+        Self {
+            run_id: Uuid::new_v4(),
+            started_at: now,
+            finished_at: now,
+            environment,
+            config,
+            results: Vec::new(),
+            aggregate: None,
+        }
+    }
+
+    /// Marks the run as finished at the current time.
+    pub fn finish(mut self) -> Self {
+        self.finished_at = Utc::now();
+        self
+    }
+
+    /// Wraps a legacy flat list of results in a report envelope.
+    ///
+    /// The run ID is nil and the start/finish timestamps are derived from
+    /// the earliest and latest result timestamps.
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - Results read from a pre-envelope file
+    ///
+    /// # Returns
+    ///
+    /// A `RunReport` containing the given results.
+    pub fn from_legacy(results: Vec<BenchmarkResult>) -> Self {
+        let now = Utc::now();
+        let started_at = results.iter().map(|r| r.timestamp).min().unwrap_or(now);
+        let finished_at = results.iter().map(|r| r.timestamp).max().unwrap_or(now);
+
+        Self {
+            run_id: Uuid::nil(),
+            started_at,
+            finished_at,
+            environment: None,
+            config: RunConfig::default(),
+            results,
+            aggregate: None,
+        }
+    }
+
+    /// Checks whether this report was converted from a legacy file.
+    pub fn is_legacy(&self) -> bool {
+        self.run_id.is_nil()
+    }
+
+    /// Returns the number of failed results in the run.
+    pub fn failed_count(&self) -> usize {
+        self.results.iter().filter(|r| r.is_failed()).count()
+    }
+
+    /// Returns the number of failed results that fail the build, i.e. of
+    /// targets that are not quarantined.
+    pub fn blocking_failed_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.is_blocking_failure())
+            .count()
+    }
+
+    /// Returns the number of skipped results in the run.
+    pub fn skipped_count(&self) -> usize {
+        self.results.iter().filter(|r| r.is_skipped()).count()
+    }
+
+    /// Returns the number of passed results in the run.
+    pub fn passed_count(&self) -> usize {
+        self.results.iter().filter(|r| r.is_passed()).count()
+    }
+
+    /// Looks up the result for a target ID.
+    pub fn result(&self, target_id: &str) -> Option<&BenchmarkResult> {
+        self.results.iter().find(|r| r.target_id == target_id)
+    }
+}
+
+/// On-disk representation of a results file.
+///
+/// Accepts both the `RunReport` envelope and the legacy flat array of
+/// results written before run grouping was introduced.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+#[schemars(title = "forge-benchmarks results file")]
+pub(crate) enum ResultsFile {
+    Report(Box<RunReport>),
+    Legacy(Vec<BenchmarkResult>),
+}
+
+impl From<ResultsFile> for RunReport {
+    fn from(file: ResultsFile) -> Self {
+        match file {
+            ResultsFile::Report(report) => *report,
+            ResultsFile::Legacy(results) => RunReport::from_legacy(results),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_begin_and_finish() {
+        let report = RunReport::begin(RunConfig::default(), None);
+        assert!(!report.is_legacy());
+        assert!(report.results.is_empty());
+
+        let report = report.finish();
+        assert!(report.finished_at >= report.started_at);
+    }
+
+    #[test]
+    fn test_from_legacy() {
+        let results = vec![
+            BenchmarkResult::new("a".to_string(), json!({})),
+            BenchmarkResult::failed("b".to_string(), "boom".to_string()),
+            BenchmarkResult::skipped("c".to_string(), "no node".to_string()),
+        ];
+
+        let report = RunReport::from_legacy(results);
+
+        assert!(report.is_legacy());
+        assert_eq!(report.results.len(), 3);
+        assert_eq!(report.failed_count(), 1);
+        assert_eq!(report.skipped_count(), 1);
+        assert_eq!(report.passed_count(), 1);
+        assert!(report.result("a").is_some());
+    }
+
+    #[test]
+    fn test_results_file_accepts_both_formats() {
+        let legacy =
+            serde_json::to_string(&vec![BenchmarkResult::new("legacy".to_string(), json!({}))])
+                .unwrap();
+        let report: RunReport = serde_json::from_str::<ResultsFile>(&legacy).unwrap().into();
+        assert!(report.is_legacy());
+
+        let envelope =
+            serde_json::to_string(&RunReport::begin(RunConfig::default(), None)).unwrap();
+        let report: RunReport = serde_json::from_str::<ResultsFile>(&envelope)
+            .unwrap()
+            .into();
+        assert!(!report.is_legacy());
+    }
+}
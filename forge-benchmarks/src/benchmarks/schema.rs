@@ -0,0 +1,161 @@
+//! JSON Schema for benchmark result files.
+//!
+//! This module generates the JSON Schema of `results.json` from the result
+//! types and validates arbitrary results files against it, so every
+//! repository producing forge-benchmarks output can be checked in CI.
+//!
+//! The generated schema is shipped as `schemas/benchmark-results.schema.json`.
+
+use super::run::ResultsFile;
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+
+/// Path of the shipped schema file, relative to the crate root.
+pub const RESULTS_SCHEMA_PATH: &str = "schemas/benchmark-results.schema.json";
+
+/// Errors that can occur while validating a results file.
+#[derive(Error, Debug)]
+pub enum SchemaError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("JSON parse error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(String),
+
+    #[error("Results file does not match schema:\n{}", .0.join("\n"))]
+    Invalid(Vec<String>),
+}
+
+/// Generates the JSON Schema for results files.
+///
+/// The schema accepts both the `RunReport` envelope and the legacy flat
+/// array of `BenchmarkResult`s.
+///
+/// # Returns
+///
+/// The schema as a JSON value.
+pub fn results_schema() -> Value {
+    let schema = schemars::schema_for!(ResultsFile);
+    serde_json::to_value(schema).unwrap_or(Value::Null)
+}
+
+/// Validates a parsed results document against the results schema.
+///
+/// # Arguments
+///
+/// * `document` - The parsed contents of a results file
+///
+/// # Returns
+///
+/// `Ok(())` if the document is valid, or `SchemaError::Invalid` listing
+/// every violation with its JSON pointer.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::schema::validate_results;
+/// use serde_json::json;
+///
+/// let legacy = json!([{
+///     "target_id": "t",
+///     "metrics": {"ops_per_sec": 10.0},
+///     "timestamp": "2024-01-01T00:00:00Z"
+/// }]);
+///
+/// assert!(validate_results(&legacy).is_ok());
+/// assert!(validate_results(&json!({"results": 1})).is_err());
+/// ```
+pub fn validate_results(document: &Value) -> Result<(), SchemaError> {
+    let schema = results_schema();
+    let validator = jsonschema::validator_for(&schema)
+        .map_err(|e| SchemaError::InvalidSchema(e.to_string()))?;
+
+    let errors: Vec<String> = validator
+        .iter_errors(document)
+        .map(|e| format!("{}: {}", e.instance_path, e))
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaError::Invalid(errors))
+    }
+}
+
+/// Reads a results file from disk and validates it against the schema.
+///
+/// # Arguments
+///
+/// * `path` - Path to a `results.json` (or compatible) file
+///
+/// # Returns
+///
+/// `Ok(())` if the file is valid, or a `SchemaError`.
+pub fn validate_results_file(path: &Path) -> Result<(), SchemaError> {
+    let content = fs::read_to_string(path)?;
+    let document: Value = serde_json::from_str(&content)?;
+    validate_results(&document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::config::RunConfig;
+    use crate::benchmarks::environment::EnvironmentInfo;
+    use crate::benchmarks::result::BenchmarkResult;
+    use crate::benchmarks::run::RunReport;
+    use serde_json::json;
+
+    #[test]
+    fn test_shipped_schema_is_up_to_date() {
+        let shipped_path = Path::new(env!("CARGO_MANIFEST_DIR")).join(RESULTS_SCHEMA_PATH);
+        let shipped: Value =
+            serde_json::from_str(&fs::read_to_string(shipped_path).unwrap()).unwrap();
+
+        assert_eq!(
+            shipped,
+            results_schema(),
+            "{} is stale; regenerate it with `run_benchmarks --print-schema`",
+            RESULTS_SCHEMA_PATH
+        );
+    }
+
+    #[test]
+    fn test_validate_report() {
+        let mut report = RunReport::begin(
+            RunConfig::default(),
+            Some(EnvironmentInfo::capture(Path::new("."))),
+        );
+        report.results.push(BenchmarkResult::new(
+            "ok".to_string(),
+            json!({"ops_per_sec": 1.0, "custom": [1, 2]}),
+        ));
+        report.results.push(BenchmarkResult::failed(
+            "bad".to_string(),
+            "boom".to_string(),
+        ));
+
+        let document = serde_json::to_value(report.finish()).unwrap();
+        validate_results(&document).unwrap();
+    }
+
+    #[test]
+    fn test_validate_rejects_wrong_types() {
+        let document = json!([{
+            "target_id": "t",
+            "metrics": {"status": "exploded"},
+            "timestamp": "2024-01-01T00:00:00Z"
+        }]);
+
+        match validate_results(&document) {
+            Err(SchemaError::Invalid(errors)) => assert!(!errors.is_empty()),
+            other => panic!("expected validation errors, got {:?}", other),
+        }
+    }
+}
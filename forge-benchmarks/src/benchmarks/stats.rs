@@ -0,0 +1,240 @@
+//! Descriptive statistics over raw benchmark samples.
+
+use serde::{Deserialize, Serialize};
+
+/// Five-number summary plus mean and spread of a sample set.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SampleSummary {
+    /// Number of samples.
+    pub count: usize,
+
+    /// Smallest sample.
+    pub min: f64,
+
+    /// First quartile (25th percentile).
+    pub q1: f64,
+
+    /// Median (50th percentile).
+    pub median: f64,
+
+    /// Third quartile (75th percentile).
+    pub q3: f64,
+
+    /// 95th percentile.
+    pub p95: f64,
+
+    /// Largest sample.
+    pub max: f64,
+
+    /// Arithmetic mean.
+    pub mean: f64,
+
+    /// Sample standard deviation (0 for a single sample).
+    pub std_dev: f64,
+}
+
+impl SampleSummary {
+    /// Computes the summary of a sample set.
+    ///
+    /// Non-finite samples are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Raw samples, in any order
+    ///
+    /// # Returns
+    ///
+    /// The summary, or `None` if there are no finite samples.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use forge_benchmarks::benchmarks::stats::SampleSummary;
+    ///
+    /// let summary = SampleSummary::from_samples(&[4.0, 1.0, 3.0, 2.0, 5.0]).unwrap();
+    /// assert_eq!(summary.median, 3.0);
+    /// assert_eq!(summary.q1, 2.0);
+    /// ```
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        let count = sorted.len();
+        let mean = sorted.iter().sum::<f64>() / count as f64;
+        let variance = if count > 1 {
+            sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (count - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            count,
+            min: sorted[0],
+            q1: percentile(&sorted, 25.0),
+            median: percentile(&sorted, 50.0),
+            q3: percentile(&sorted, 75.0),
+            p95: percentile(&sorted, 95.0),
+            max: sorted[count - 1],
+            mean,
+            std_dev: variance.sqrt(),
+        })
+    }
+
+    /// Coefficient of variation (standard deviation relative to the mean),
+    /// in percent.
+    pub fn cv_pct(&self) -> f64 {
+        if self.mean == 0.0 {
+            0.0
+        } else {
+            self.std_dev / self.mean.abs() * 100.0
+        }
+    }
+}
+
+/// Welch's t statistic for the difference of means between two sample sets.
+///
+/// Positive values mean `after` is larger than `before`.
+///
+/// # Returns
+///
+/// The t statistic, or `None` if either set has fewer than two samples or
+/// both have zero variance.
+pub fn welch_t(before: &SampleSummary, after: &SampleSummary) -> Option<f64> {
+    if before.count < 2 || after.count < 2 {
+        return None;
+    }
+    let standard_error = (before.std_dev.powi(2) / before.count as f64
+        + after.std_dev.powi(2) / after.count as f64)
+        .sqrt();
+    if standard_error == 0.0 {
+        return None;
+    }
+    Some((after.mean - before.mean) / standard_error)
+}
+
+/// Welch-Satterthwaite degrees of freedom of `welch_t`.
+///
+/// # Returns
+///
+/// The degrees of freedom, or `None` where `welch_t` is `None`.
+pub fn welch_df(before: &SampleSummary, after: &SampleSummary) -> Option<f64> {
+    if before.count < 2 || after.count < 2 {
+        return None;
+    }
+    let a = before.std_dev.powi(2) / before.count as f64;
+    let b = after.std_dev.powi(2) / after.count as f64;
+    let denominator = a.powi(2) / (before.count - 1) as f64 + b.powi(2) / (after.count - 1) as f64;
+    if denominator == 0.0 {
+        return None;
+    }
+    Some((a + b).powi(2) / denominator)
+}
+
+/// Two-sided 95% critical values of Student's t for 1 to 30 degrees of
+/// freedom.
+const T_CRITICAL_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Returns the two-sided 95% critical value of Student's t.
+///
+/// Fractional degrees of freedom (as from `welch_df`) are rounded down,
+/// which errs on the side of calling a change not significant.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::stats::t_critical_95;
+///
+/// assert_eq!(t_critical_95(9.7), 2.262);
+/// assert_eq!(t_critical_95(1000.0), 1.960);
+/// ```
+pub fn t_critical_95(df: f64) -> f64 {
+    let df = df.floor().max(1.0);
+    match df as usize {
+        n @ 1..=30 => T_CRITICAL_95[n - 1],
+        31..=39 => T_CRITICAL_95[29],
+        40..=59 => 2.021,
+        60..=119 => 2.000,
+        120..=999 => 1.980,
+        _ => 1.960,
+    }
+}
+
+/// Returns the `p`th percentile of sorted samples, interpolating linearly
+/// between closest ranks.
+///
+/// # Arguments
+///
+/// * `sorted` - Samples sorted in ascending order (must not be empty)
+/// * `p` - Percentile in `0..=100`
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let samples: Vec<f64> = (1..=100).map(f64::from).collect();
+        let summary = SampleSummary::from_samples(&samples).unwrap();
+
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.min, 1.0);
+        assert_eq!(summary.max, 100.0);
+        assert_eq!(summary.median, 50.5);
+        assert!((summary.p95 - 95.05).abs() < 1e-9);
+        assert!((summary.cv_pct() - 57.44).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_welch_t() {
+        let before = SampleSummary::from_samples(&[10.0, 11.0, 9.0, 10.0]).unwrap();
+        let after = SampleSummary::from_samples(&[20.0, 21.0, 19.0, 20.0]).unwrap();
+        let same = SampleSummary::from_samples(&[10.0, 10.0]).unwrap();
+        let single = SampleSummary::from_samples(&[10.0]).unwrap();
+
+        assert!(welch_t(&before, &after).unwrap() > 10.0);
+        assert!(welch_t(&after, &before).unwrap() < -10.0);
+        assert_eq!(welch_t(&same, &same), None);
+        assert_eq!(welch_t(&before, &single), None);
+    }
+
+    #[test]
+    fn test_welch_df_and_critical_value() {
+        let before = SampleSummary::from_samples(&[10.0, 11.0, 9.0, 10.0]).unwrap();
+        let after = SampleSummary::from_samples(&[20.0, 21.0, 19.0, 20.0]).unwrap();
+        let same = SampleSummary::from_samples(&[10.0, 10.0]).unwrap();
+
+        // Equal variances and counts: n1 + n2 - 2
+        assert!((welch_df(&before, &after).unwrap() - 6.0).abs() < 1e-9);
+        assert_eq!(welch_df(&same, &same), None);
+
+        assert_eq!(t_critical_95(0.4), 12.706);
+        assert_eq!(t_critical_95(6.0), 2.447);
+        assert_eq!(t_critical_95(35.0), 2.042);
+        assert_eq!(t_critical_95(f64::INFINITY), 1.960);
+    }
+
+    #[test]
+    fn test_degenerate_samples() {
+        assert_eq!(SampleSummary::from_samples(&[]), None);
+        assert_eq!(SampleSummary::from_samples(&[f64::NAN]), None);
+
+        let single = SampleSummary::from_samples(&[7.0]).unwrap();
+        assert_eq!(single.median, 7.0);
+        assert_eq!(single.std_dev, 0.0);
+        assert_eq!(single.cv_pct(), 0.0);
+    }
+}
@@ -0,0 +1,488 @@
+//! CSV and TSV export of benchmark results and history series.
+//!
+//! This module flattens `BenchmarkResult` metrics into one row per target with
+//! stable column names, so results can be loaded into spreadsheets or pandas
+//! directly. Well-known metrics carry their unit in the header and parameter
+//! values (the `params` object, flattened into `params.*` columns) come
+//! before the measurements. Tables written here can be read back, e.g. to use
+//! a CSV file as a baseline.
+
+use super::history::SeriesPoint;
+use super::io::IoError;
+use super::metrics::{Metrics, METRIC_UNITS};
+use super::result::BenchmarkResult;
+use super::run::RunReport;
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Write};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Prefix of extension metrics describing a target's parameter values.
+pub const PARAMS_PREFIX: &str = "params.";
+
+/// Extension metric holding a target's parameter values.
+const PARAMS_KEY: &str = "params";
+
+/// Columns preceding the metric columns in a results table.
+const LEADING_COLUMNS: &[&str] = &["run_id", "target_id", "timestamp", "status", "error"];
+
+/// Checks whether a column holds text rather than metric values.
+///
+/// Text cells are read back verbatim, so e.g. a commit `0123` stays a
+/// string.
+fn is_text_column(key: &str) -> bool {
+    LEADING_COLUMNS.contains(&key) || key == "skip_reason" || key.starts_with(PARAMS_PREFIX)
+}
+
+/// Delimited text format of a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    /// Comma-separated values.
+    Csv,
+
+    /// Tab-separated values.
+    Tsv,
+}
+
+impl TableFormat {
+    /// Returns the field delimiter.
+    pub fn delimiter(&self) -> u8 {
+        match self {
+            TableFormat::Csv => b',',
+            TableFormat::Tsv => b'\t',
+        }
+    }
+
+    /// Returns the conventional file extension.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TableFormat::Csv => "csv",
+            TableFormat::Tsv => "tsv",
+        }
+    }
+
+    /// Detects the format from a file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "csv" => Some(TableFormat::Csv),
+            "tsv" | "tab" => Some(TableFormat::Tsv),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the header of a metric column, including its unit if known.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::tabular::column_header;
+///
+/// assert_eq!(column_header("avg_ns"), "avg_ns (ns)");
+/// assert_eq!(column_header("cache_hits"), "cache_hits");
+/// ```
+pub fn column_header(key: &str) -> String {
+    match Metrics::unit_of(key) {
+        Some(unit) => format!("{} ({})", key, unit.symbol()),
+        None => key.to_string(),
+    }
+}
+
+/// Returns the metric key of a column header, stripping any unit suffix.
+fn column_key(header: &str) -> &str {
+    match header.rfind(" (") {
+        Some(pos) if header.ends_with(')') => &header[..pos],
+        _ => header,
+    }
+}
+
+/// Writes the results of a run as a table, one row per target.
+///
+/// Columns are `run_id`, `target_id`, `timestamp`, `status`, `error`, the
+/// sorted `params.*` columns, every well-known metric and finally the sorted
+/// remaining extension metrics.
+///
+/// # Arguments
+///
+/// * `report` - The benchmark run to write
+/// * `format` - CSV or TSV
+/// * `writer` - Destination of the table
+///
+/// # Returns
+///
+/// `Ok(())` on success, or an `IoError` on failure.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use forge_benchmarks::benchmarks::run::RunReport;
+/// use forge_benchmarks::benchmarks::tabular::{write_results_table, TableFormat};
+/// use serde_json::json;
+///
+/// let report = RunReport::from_legacy(vec![
+///     BenchmarkResult::new("parse".to_string(), json!({"ops_per_sec": 1000.0})),
+/// ]);
+///
+/// let mut csv = Vec::new();
+/// write_results_table(&report, TableFormat::Csv, &mut csv).unwrap();
+/// assert!(String::from_utf8(csv).unwrap().contains("ops_per_sec (ops/s)"));
+/// ```
+pub fn write_results_table<W: Write>(
+    report: &RunReport,
+    format: TableFormat,
+    writer: W,
+) -> Result<(), IoError> {
+    let rows: Vec<BTreeMap<String, String>> = report
+        .results
+        .iter()
+        .map(|result| flatten_result(report.run_id, result))
+        .collect();
+
+    // Stable column order: identity, parameters, well-known, extensions
+    let mut params = BTreeSet::new();
+    let mut extra = BTreeSet::new();
+    for key in rows.iter().flat_map(|row| row.keys()) {
+        if LEADING_COLUMNS.contains(&key.as_str()) || Metrics::unit_of(key).is_some() {
+            continue;
+        }
+        if key.starts_with(PARAMS_PREFIX) {
+            params.insert(key.clone());
+        } else {
+            extra.insert(key.clone());
+        }
+    }
+    let columns: Vec<String> = LEADING_COLUMNS
+        .iter()
+        .map(|c| c.to_string())
+        .chain(params)
+        .chain(METRIC_UNITS.iter().map(|(key, _)| key.to_string()))
+        .chain(extra)
+        .collect();
+
+    let mut csv = csv::WriterBuilder::new()
+        .delimiter(format.delimiter())
+        .from_writer(writer);
+    csv.write_record(columns.iter().map(|c| column_header(c)))?;
+    for row in &rows {
+        csv.write_record(
+            columns
+                .iter()
+                .map(|c| row.get(c).map(String::as_str).unwrap_or_default()),
+        )?;
+    }
+    csv.flush()?;
+
+    Ok(())
+}
+
+/// Writes a metric time series (e.g. history query output) as a table.
+///
+/// # Arguments
+///
+/// * `points` - Series points, in order
+/// * `metric` - Metric key the values belong to
+/// * `format` - CSV or TSV
+/// * `writer` - Destination of the table
+pub fn write_series_table<W: Write>(
+    points: &[SeriesPoint],
+    metric: &str,
+    format: TableFormat,
+    writer: W,
+) -> Result<(), IoError> {
+    let mut csv = csv::WriterBuilder::new()
+        .delimiter(format.delimiter())
+        .from_writer(writer);
+    csv.write_record([
+        "run_id".to_string(),
+        "timestamp".to_string(),
+        "git_commit".to_string(),
+        "target_id".to_string(),
+        column_header(metric),
+    ])?;
+    for point in points {
+        csv.write_record([
+            point.run_id.to_string(),
+            point.timestamp.to_rfc3339(),
+            point.git_commit.clone().unwrap_or_default(),
+            point.target_id.clone(),
+            point.value.map(|v| v.to_string()).unwrap_or_default(),
+        ])?;
+    }
+    csv.flush()?;
+
+    Ok(())
+}
+
+/// Reads a results table written by `write_results_table`.
+///
+/// Unit suffixes in headers are ignored and `params.*` columns are nested
+/// into the `params` object again, so the metrics round-trip. Parameter
+/// values are read as strings. The report takes the run ID of the first row;
+/// timing comes from the result timestamps.
+///
+/// # Arguments
+///
+/// * `reader` - Source of the table
+/// * `format` - CSV or TSV
+///
+/// # Returns
+///
+/// The `RunReport` or an `IoError`.
+pub fn read_results_table<R: Read>(reader: R, format: TableFormat) -> Result<RunReport, IoError> {
+    let mut csv = csv::ReaderBuilder::new()
+        .delimiter(format.delimiter())
+        .from_reader(reader);
+    let headers: Vec<String> = csv
+        .headers()?
+        .iter()
+        .map(|h| column_key(h).to_string())
+        .collect();
+
+    let mut run_id = None;
+    let mut results = Vec::new();
+    for (index, record) in csv.records().enumerate() {
+        let record = record?;
+        let row = index + 2; // 1-based, after the header
+        let invalid = |message: String| IoError::InvalidRow { row, message };
+
+        let mut target_id = None;
+        let mut timestamp = None;
+        let mut metrics = Map::new();
+        for (key, cell) in headers.iter().zip(record.iter()) {
+            if cell.is_empty() {
+                continue;
+            }
+            match key.as_str() {
+                "run_id" => {
+                    let id = cell.parse::<Uuid>().map_err(|e| invalid(e.to_string()))?;
+                    run_id.get_or_insert(id);
+                }
+                "target_id" => target_id = Some(cell.to_string()),
+                "timestamp" => {
+                    let ts = DateTime::parse_from_rfc3339(cell)
+                        .map_err(|e| invalid(format!("timestamp: {}", e)))?;
+                    timestamp = Some(ts.with_timezone(&Utc));
+                }
+                _ if key.starts_with(PARAMS_PREFIX) => {
+                    insert_nested(&mut metrics, key, Value::String(cell.to_string()));
+                }
+                _ if is_text_column(key) => {
+                    metrics.insert(key.clone(), Value::String(cell.to_string()));
+                }
+                _ => {
+                    metrics.insert(key.clone(), parse_cell(cell));
+                }
+            }
+        }
+
+        let target_id = target_id.ok_or_else(|| invalid("missing target_id".to_string()))?;
+        let metrics = Metrics::from(Value::Object(metrics));
+        results.push(match timestamp {
+            Some(ts) => BenchmarkResult::with_timestamp(target_id, metrics, ts),
+            None => BenchmarkResult::new(target_id, metrics),
+        });
+    }
+
+    let mut report = RunReport::from_legacy(results);
+    if let Some(run_id) = run_id {
+        report.run_id = run_id;
+    }
+    Ok(report)
+}
+
+/// Flattens a result into column name / cell pairs.
+fn flatten_result(run_id: Uuid, result: &BenchmarkResult) -> BTreeMap<String, String> {
+    let mut row = BTreeMap::new();
+    row.insert("run_id".to_string(), run_id.to_string());
+    row.insert("target_id".to_string(), result.target_id.clone());
+    row.insert("timestamp".to_string(), result.timestamp.to_rfc3339());
+
+    if let Value::Object(metrics) = result.metrics.to_value() {
+        for (key, value) in &metrics {
+            if key != "schema_version" {
+                flatten_value(key, value, &mut row);
+            }
+        }
+    }
+    row
+}
+
+/// Flattens a JSON value into `row`.
+///
+/// The `params` object becomes one `params.*` column per parameter; other
+/// objects and arrays are kept as JSON text. Strings in metric columns that
+/// would read back as another type are written as JSON strings.
+fn flatten_value(key: &str, value: &Value, row: &mut BTreeMap<String, String>) {
+    let cell = match value {
+        Value::Object(map) if key == PARAMS_KEY || key.starts_with(PARAMS_PREFIX) => {
+            for (child, value) in map {
+                flatten_value(&format!("{}.{}", key, child), value, row);
+            }
+            return;
+        }
+        Value::Null => return,
+        Value::String(s) if is_text_column(key) || parse_cell(s).is_string() => s.clone(),
+        other => other.to_string(),
+    };
+    row.insert(key.to_string(), cell);
+}
+
+/// Inserts a value under a dotted key, creating nested objects.
+fn insert_nested(map: &mut Map<String, Value>, key: &str, value: Value) {
+    match key.split_once('.') {
+        Some((head, rest)) => {
+            let child = map
+                .entry(head.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(child) = child {
+                insert_nested(child, rest, value);
+            }
+        }
+        None => {
+            map.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// Parses a cell back into the JSON value it was written from.
+fn parse_cell(cell: &str) -> Value {
+    if let Ok(i) = cell.parse::<i64>() {
+        return Value::from(i);
+    }
+    if let Ok(f) = cell.parse::<f64>() {
+        if f.is_finite() {
+            return Value::from(f);
+        }
+    }
+    match cell {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+    if cell.starts_with(['[', '{', '"']) {
+        if let Ok(value) = serde_json::from_str(cell) {
+            return value;
+        }
+    }
+    Value::String(cell.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_report() -> RunReport {
+        RunReport::from_legacy(vec![
+            BenchmarkResult::new(
+                "parse, large".to_string(),
+                json!({
+                    "avg_ns": 1500.5,
+                    "ops_per_sec": 666444.5,
+                    "samples": 10,
+                    "params": {"size": "1000", "format": "openapi"},
+                    "cache": {"hits": 3, "misses": 1},
+                    "tags": ["a", "b"]
+                }),
+            ),
+            BenchmarkResult::failed("broken".to_string(), "exit status 1".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_columns_are_stable_and_carry_units() {
+        let mut out = Vec::new();
+        write_results_table(&sample_report(), TableFormat::Csv, &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let header = csv.lines().next().unwrap();
+
+        assert_eq!(
+            header,
+            "run_id,target_id,timestamp,status,error,params.format,params.size,\
+             avg_ns (ns),min_ns (ns),max_ns (ns),ops_per_sec (ops/s),samples (count),\
+             cache,tags"
+        );
+        assert!(csv.contains("\"parse, large\""));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let report = sample_report();
+        for format in [TableFormat::Csv, TableFormat::Tsv] {
+            let mut out = Vec::new();
+            write_results_table(&report, format, &mut out).unwrap();
+            let read = read_results_table(out.as_slice(), format).unwrap();
+
+            assert_eq!(read.results.len(), 2);
+            for (original, read) in report.results.iter().zip(&read.results) {
+                assert_eq!(read.target_id, original.target_id);
+                assert_eq!(read.timestamp, original.timestamp);
+                assert_eq!(read.metrics, original.metrics);
+            }
+        }
+    }
+
+    #[test]
+    fn test_text_cells_round_trip() {
+        let report = RunReport::from_legacy(vec![BenchmarkResult::new(
+            "parse".to_string(),
+            json!({
+                "ops_per_sec": 10.0,
+                "commit": "0123",
+                "flag": "true",
+                "latency.p99": 7,
+                "params": {"commit": "0123", "provider": "openai"}
+            }),
+        )]);
+        let mut out = Vec::new();
+        write_results_table(&report, TableFormat::Csv, &mut out).unwrap();
+        let csv = String::from_utf8(out.clone()).unwrap();
+        let header = csv.lines().next().unwrap();
+        assert!(header.contains("params.commit,params.provider"));
+
+        let read = read_results_table(out.as_slice(), TableFormat::Csv).unwrap();
+        let extra = &read.results[0].metrics.extra;
+        assert_eq!(extra["commit"], "0123");
+        assert_eq!(extra["flag"], "true");
+        assert_eq!(extra["latency.p99"], 7);
+        assert_eq!(extra["params"]["commit"], "0123");
+        assert_eq!(read.results[0].metrics, report.results[0].metrics);
+    }
+
+    #[test]
+    fn test_series_table() {
+        let point = SeriesPoint {
+            run_id: Uuid::nil(),
+            timestamp: DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            git_commit: Some("abc123".to_string()),
+            target_id: "parse".to_string(),
+            value: Some(42.5),
+        };
+        let mut out = Vec::new();
+        write_series_table(&[point], "ops_per_sec", TableFormat::Tsv, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "run_id\ttimestamp\tgit_commit\ttarget_id\tops_per_sec (ops/s)\n\
+             00000000-0000-0000-0000-000000000000\t2026-01-02T03:04:05+00:00\tabc123\tparse\t42.5\n"
+        );
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            TableFormat::from_path(Path::new("base.CSV")),
+            Some(TableFormat::Csv)
+        );
+        assert_eq!(
+            TableFormat::from_path(Path::new("base.tsv")),
+            Some(TableFormat::Tsv)
+        );
+        assert_eq!(TableFormat::from_path(Path::new("results.json")), None);
+    }
+}
@@ -0,0 +1,543 @@
+//! Template-driven Markdown reports.
+//!
+//! Reports are rendered with Handlebars from a `ReportData` value, which
+//! holds the run metadata, results, baseline comparisons and history with
+//! numbers and timestamps already formatted through a `ReportContext`. The
+//! built-in layout of `summary.md` ships as `DEFAULT_TEMPLATE`; a user
+//! template can be selected with `report.template` in the run configuration.
+
+use super::compare::Comparison;
+use super::io::IoError;
+use super::markdown::statistics;
+use super::report::{ReportContext, TIMESTAMP_FORMAT};
+use super::result::BenchmarkResult;
+use super::run::RunReport;
+use handlebars::Handlebars;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// The default `summary.md` layout.
+pub const DEFAULT_TEMPLATE: &str = include_str!("../../templates/summary.md.hbs");
+
+/// Name under which the built-in template is reported in errors.
+const DEFAULT_TEMPLATE_NAME: &str = "<default>";
+
+/// Everything a report template can refer to.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReportData {
+    /// Generation timestamp, e.g. `2026-03-01 12:00:00 UTC`.
+    pub generated_at: String,
+
+    /// Metadata of the rendered run.
+    pub run: RunData,
+
+    /// Results in report order.
+    pub results: Vec<ResultData>,
+
+    /// Baseline comparisons; empty without a baseline.
+    pub comparisons: Vec<ComparisonData>,
+
+    /// Previous runs, oldest first; empty when history is not loaded.
+    pub history: Vec<HistoryData>,
+}
+
+/// Metadata of a run.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunData {
+    /// Run ID, absent for legacy result files.
+    pub run_id: Option<String>,
+
+    /// Start of the run.
+    pub started_at: String,
+
+    /// End of the run.
+    pub finished_at: String,
+
+    /// Commit the run was made on, if known.
+    pub git_commit: Option<String>,
+
+    /// Number of results.
+    pub total: usize,
+
+    /// Number of successful results.
+    pub passed: usize,
+
+    /// Number of failed results.
+    pub failed: usize,
+
+    /// Number of skipped results.
+    pub skipped: usize,
+}
+
+/// One target's result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResultData {
+    /// Target ID.
+    pub target_id: String,
+
+    /// `PASSED`, `PASSED (cached)`, `FAILED` or `SKIPPED`.
+    pub status: &'static str,
+
+    /// Whether the benchmark ran and produced measurements.
+    pub passed: bool,
+
+    /// Whether the benchmark failed.
+    pub failed: bool,
+
+    /// Whether the benchmark was skipped.
+    pub skipped: bool,
+
+    /// Whether the result was reused from an earlier run.
+    pub cached: bool,
+
+    /// Error message of a failed benchmark.
+    pub error: Option<String>,
+
+    /// Unmet precondition of a skipped benchmark.
+    pub skip_reason: Option<String>,
+
+    /// Time of day the result was recorded, e.g. `12:00:00`.
+    pub timestamp: String,
+
+    /// Formatted throughput, `-` when missing.
+    pub ops_per_sec: String,
+
+    /// Formatted mean latency in nanoseconds, `-` when missing.
+    pub avg_ns: String,
+
+    /// Formatted statistics shown in the detailed metrics section.
+    pub statistics: Vec<StatisticData>,
+
+    /// All metrics, unformatted.
+    pub metrics: serde_json::Value,
+}
+
+/// One named, formatted statistic.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatisticData {
+    /// Label, e.g. `p95 (ns)`.
+    pub name: &'static str,
+
+    /// Formatted value, `-` when missing.
+    pub value: String,
+}
+
+/// One baseline comparison.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComparisonData {
+    /// Target ID.
+    pub target_id: String,
+
+    /// Compared metric.
+    pub metric: String,
+
+    /// Formatted baseline value.
+    pub baseline: String,
+
+    /// Formatted current value.
+    pub current: String,
+
+    /// Signed change, e.g. `+12.50%`.
+    pub delta: String,
+
+    /// `improved`, `unchanged` or `regressed`.
+    pub verdict: String,
+
+    /// Whether the verdict is a regression.
+    pub regressed: bool,
+}
+
+/// One previous run.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryData {
+    /// Metadata of the run.
+    pub run: RunData,
+
+    /// Results of the run, in report order.
+    pub results: Vec<ResultData>,
+}
+
+impl ReportData {
+    /// Builds the template data of a run.
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - The run to render
+    /// * `comparisons` - Baseline comparisons (may be empty)
+    /// * `history` - Previous runs, oldest first (may be empty)
+    /// * `ctx` - Generation time, run ID, ordering and number formatting
+    pub fn new(
+        report: &RunReport,
+        comparisons: &[Comparison],
+        history: &[RunReport],
+        ctx: &ReportContext,
+    ) -> Self {
+        let mut run = RunData::new(report);
+        run.run_id = ctx.run_id.map(|id| id.to_string());
+
+        Self {
+            generated_at: ctx.format_timestamp(),
+            run,
+            results: results(&report.results, ctx),
+            comparisons: comparisons
+                .iter()
+                .map(|c| ComparisonData {
+                    target_id: c.target_id.clone(),
+                    metric: c.metric.clone(),
+                    baseline: ctx.format_number(c.baseline),
+                    current: ctx.format_number(c.current),
+                    delta: format!("{:+.2}%", c.delta_pct),
+                    verdict: serde_json::to_value(c.verdict)
+                        .ok()
+                        .and_then(|v| v.as_str().map(str::to_string))
+                        .unwrap_or_default(),
+                    regressed: c.is_regression(),
+                })
+                .collect(),
+            history: history
+                .iter()
+                .map(|run| HistoryData {
+                    run: RunData::new(run),
+                    results: results(&run.results, ctx),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl RunData {
+    fn new(report: &RunReport) -> Self {
+        Self {
+            run_id: (!report.run_id.is_nil()).then(|| report.run_id.to_string()),
+            started_at: report.started_at.format(TIMESTAMP_FORMAT).to_string(),
+            finished_at: report.finished_at.format(TIMESTAMP_FORMAT).to_string(),
+            git_commit: report
+                .environment
+                .as_ref()
+                .and_then(|env| env.git.as_ref())
+                .map(|git| git.commit.clone()),
+            total: report.results.len(),
+            passed: report.passed_count(),
+            failed: report.failed_count(),
+            skipped: report.skipped_count(),
+        }
+    }
+}
+
+/// Formats results in report order.
+fn results(results: &[BenchmarkResult], ctx: &ReportContext) -> Vec<ResultData> {
+    let cell = |v: Option<f64>| {
+        v.map(|v| ctx.format_number(v))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    ctx.sorted(results)
+        .into_iter()
+        .map(|result| ResultData {
+            target_id: result.target_id.clone(),
+            status: result.status_label(),
+            passed: result.is_passed(),
+            failed: result.is_failed(),
+            skipped: result.is_skipped(),
+            cached: result.cached,
+            error: result.metrics.error.clone(),
+            skip_reason: result.metrics.skip_reason.clone(),
+            timestamp: result.timestamp.format("%H:%M:%S").to_string(),
+            ops_per_sec: cell(result.ops_per_sec()),
+            avg_ns: cell(result.avg_ns()),
+            statistics: statistics(Some(&result.metrics), ctx)
+                .into_iter()
+                .map(|(name, value)| StatisticData { name, value })
+                .collect(),
+            metrics: serde_json::to_value(&result.metrics).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Renders report data with a Handlebars template.
+///
+/// Output is not HTML-escaped, since reports are Markdown.
+///
+/// # Arguments
+///
+/// * `template` - Handlebars template source
+/// * `data` - Data the template refers to
+///
+/// # Returns
+///
+/// The rendered report, or `IoError::Template` if the template is invalid.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::report::ReportContext;
+/// use forge_benchmarks::benchmarks::result::BenchmarkResult;
+/// use forge_benchmarks::benchmarks::run::RunReport;
+/// use forge_benchmarks::benchmarks::template::{render, ReportData};
+/// use serde_json::json;
+///
+/// let report = RunReport::from_legacy(vec![
+///     BenchmarkResult::new("parse".to_string(), json!({"ops_per_sec": 1500.0})),
+/// ]);
+/// let data = ReportData::new(&report, &[], &[], &ReportContext::for_run(&report));
+///
+/// let md = render("{{#each results}}{{target_id}}: {{ops_per_sec}}{{/each}}", &data).unwrap();
+/// assert_eq!(md, "parse: 1.50K");
+/// ```
+pub fn render(template: &str, data: &ReportData) -> Result<String, IoError> {
+    render_named(DEFAULT_TEMPLATE_NAME, template, data)
+}
+
+/// Renders the `summary.md` of a run.
+///
+/// Uses the template file configured in `report.template`, or
+/// `DEFAULT_TEMPLATE` when none is set.
+///
+/// # Arguments
+///
+/// * `report` - The run to render
+/// * `comparisons` - Baseline comparisons (may be empty)
+/// * `history` - Previous runs, oldest first (may be empty)
+/// * `ctx` - Generation time, run ID, ordering and number formatting
+///
+/// # Returns
+///
+/// The rendered summary, or an `IoError` if the template cannot be read
+/// or rendered.
+pub fn render_summary(
+    report: &RunReport,
+    comparisons: &[Comparison],
+    history: &[RunReport],
+    ctx: &ReportContext,
+) -> Result<String, IoError> {
+    let data = ReportData::new(report, comparisons, history, ctx);
+    match &report.config.report.template {
+        Some(path) => render_named(&path.display().to_string(), &load(path)?, &data),
+        None => render(DEFAULT_TEMPLATE, &data),
+    }
+}
+
+/// Checks that a template file can be read and compiled.
+///
+/// Lets a run reject a broken `report.template` before any target runs,
+/// instead of after the results are measured.
+///
+/// # Arguments
+///
+/// * `path` - Path of the template file
+///
+/// # Returns
+///
+/// `Ok(())`, or an `IoError` if the file cannot be read or is not a valid
+/// template.
+pub fn check_template(path: &Path) -> Result<(), IoError> {
+    let source = load(path)?;
+    Handlebars::new()
+        .register_template_string(DEFAULT_TEMPLATE_NAME, source)
+        .map_err(|e| IoError::Template {
+            template: path.display().to_string(),
+            message: e.to_string(),
+        })
+}
+
+/// Reads a template file.
+fn load(path: &Path) -> Result<String, IoError> {
+    Ok(fs::read_to_string(path)?)
+}
+
+/// Renders a template, naming it `name` in errors.
+fn render_named(name: &str, template: &str, data: &ReportData) -> Result<String, IoError> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(handlebars::no_escape);
+
+    handlebars
+        .render_template(template, data)
+        .map_err(|e| IoError::Template {
+            template: name.to_string(),
+            message: e.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::compare::compare_reports;
+    use crate::benchmarks::metrics::Metrics;
+    use crate::benchmarks::report::{NumberLocale, SortOrder};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    fn sample_report() -> RunReport {
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let mut report = RunReport::from_legacy(vec![
+            BenchmarkResult::with_timestamp(
+                "zeta".to_string(),
+                json!({"ops_per_sec": 2500.0, "avg_ns": 400000.0, "samples": 10}),
+                at,
+            ),
+            BenchmarkResult::with_timestamp(
+                "alpha".to_string(),
+                Metrics::failed("npx not found"),
+                at,
+            ),
+            BenchmarkResult::with_timestamp(
+                "mid".to_string(),
+                Metrics::skipped("node_modules does not exist"),
+                at,
+            ),
+        ]);
+        report.run_id = uuid::Uuid::new_v4();
+        report
+    }
+
+    #[test]
+    fn test_default_summary_snapshot() {
+        let mut report = sample_report();
+        report.run_id = uuid::Uuid::from_u128(1);
+        let ctx = ReportContext {
+            generated_at: Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap(),
+            sort: SortOrder::TargetId,
+            locale: NumberLocale::De,
+            ..ReportContext::for_run(&report)
+        };
+
+        let summary = render_summary(&report, &[], &[], &ctx).unwrap();
+
+        assert_eq!(summary, render_summary(&report, &[], &[], &ctx).unwrap());
+        assert_eq!(
+            summary,
+            "# LLM-Forge Benchmark Results
+
+**Generated:** 2026-03-01 12:00:00 UTC
+
+**Run:** `00000000-0000-0000-0000-000000000001`
+
+## Summary
+
+- **Total Benchmarks:** 3
+- **Passed:** 1
+- **Failed:** 1
+- **Skipped:** 1
+
+## Results
+
+| Target ID | Status | Ops/sec | Avg (ns) | Timestamp |
+|-----------|--------|---------|----------|----------|
+| alpha | FAILED | - | - | 12:00:00 |
+| mid | SKIPPED | - | - | 12:00:00 |
+| zeta | PASSED | 2,50K | 400,00K | 12:00:00 |
+
+## Failed Benchmarks
+
+### alpha
+
+**Error:** npx not found
+
+## Skipped Benchmarks
+
+### mid
+
+**Reason:** node_modules does not exist
+
+## Detailed Metrics
+
+<details><summary><b>zeta</b></summary>
+
+| Statistic | Value |
+|-----------|------:|
+| median (ns) | - |
+| p95 (ns) | - |
+| mean (ns) | 400,00K |
+| CV | - |
+| min (ns) | - |
+| max (ns) | - |
+| ops/sec | 2,50K |
+| samples | 10 |
+
+</details>
+
+---
+
+*Generated by forge-benchmarks - Canonical benchmark interface for LLM-Forge*
+"
+        );
+    }
+
+    #[test]
+    fn test_default_summary_in_recorded_order() {
+        let report = sample_report();
+        let summary = render_summary(&report, &[], &[], &ReportContext::for_run(&report)).unwrap();
+
+        assert!(summary.contains(
+            "| zeta | PASSED | 2.50K | 400.00K | 12:00:00 |\n\
+             | alpha | FAILED | - | - | 12:00:00 |\n"
+        ));
+        assert!(summary.contains("## Failed Benchmarks\n\n### alpha\n\n**Error:** npx not found"));
+    }
+
+    #[test]
+    fn test_user_template_sees_comparisons_and_history() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("summary.md.hbs");
+        fs::write(
+            &path,
+            "{{#each comparisons}}{{target_id}} {{verdict}} {{delta}}\n{{/each}}{{#each history}}{{run.total}} in {{run.started_at}}\n{{/each}}",
+        )
+        .unwrap();
+
+        let mut baseline = RunReport::from_legacy(vec![BenchmarkResult::new(
+            "zeta".to_string(),
+            json!({"ops_per_sec": 5000.0}),
+        )]);
+        baseline.started_at = Utc.with_ymd_and_hms(2026, 2, 28, 9, 30, 0).unwrap();
+        let mut report = sample_report();
+        report.config.report.template = Some(path);
+        let comparisons = compare_reports(&baseline, &report, "ops_per_sec", 5.0);
+
+        let md = render_summary(
+            &report,
+            &comparisons,
+            &[baseline],
+            &ReportContext::for_run(&report),
+        )
+        .unwrap();
+        assert_eq!(md, "zeta regressed -50.00%\n1 in 2026-02-28 09:30:00 UTC\n");
+    }
+
+    #[test]
+    fn test_template_errors() {
+        let report = sample_report();
+        let data = ReportData::new(&report, &[], &[], &ReportContext::for_run(&report));
+        assert!(matches!(
+            render("{{#each results}}", &data),
+            Err(IoError::Template { .. })
+        ));
+
+        let mut report = report;
+        report.config.report.template = Some("does/not/exist.hbs".into());
+        assert!(matches!(
+            render_summary(&report, &[], &[], &ReportContext::for_run(&report)),
+            Err(IoError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_template() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let valid = temp_dir.path().join("valid.md.hbs");
+        let invalid = temp_dir.path().join("invalid.md.hbs");
+        fs::write(&valid, DEFAULT_TEMPLATE).unwrap();
+        fs::write(&invalid, "{{#each results}}").unwrap();
+
+        assert!(check_template(&valid).is_ok());
+        assert!(matches!(
+            check_template(&invalid),
+            Err(IoError::Template { template, .. }) if template == invalid.display().to_string()
+        ));
+        assert!(matches!(
+            check_template(&temp_dir.path().join("missing.md.hbs")),
+            Err(IoError::Io(_))
+        ));
+    }
+}
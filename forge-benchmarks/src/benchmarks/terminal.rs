@@ -0,0 +1,532 @@
+//! Terminal output for the benchmark binary.
+//!
+//! `LiveProgress` is a `RunObserver` showing the running target with its
+//! iteration count, elapsed time and running mean, redrawn in place on an
+//! interactive terminal, and `render_table` produces the aligned final
+//! table. Both fall back to plain, uncoloured, line-oriented output when
+//! stdout is not a TTY or `NO_COLOR` is set.
+
+use super::compare::{Comparison, Verdict};
+use super::events::{RunEvent, RunObserver};
+use super::result::BenchmarkResult;
+use super::run::RunReport;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// ANSI sequence returning to the line start and clearing it.
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+const BOLD: &str = "1";
+const RED: &str = "31";
+const GREEN: &str = "32";
+const YELLOW: &str = "33";
+
+/// How output is rendered on the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalStyle {
+    /// Redraw the progress line in place.
+    pub interactive: bool,
+
+    /// Use ANSI colours.
+    pub color: bool,
+}
+
+impl TerminalStyle {
+    /// Detects the style for stdout.
+    ///
+    /// Interactive, coloured output is used only when stdout is a TTY and
+    /// `NO_COLOR` is unset or empty.
+    pub fn detect() -> Self {
+        Self::resolve(
+            io::stdout().is_terminal(),
+            env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()),
+        )
+    }
+
+    /// Plain, line-oriented output without colours.
+    pub fn plain() -> Self {
+        Self {
+            interactive: false,
+            color: false,
+        }
+    }
+
+    fn resolve(is_terminal: bool, no_color: bool) -> Self {
+        let rich = is_terminal && !no_color;
+        Self {
+            interactive: rich,
+            color: rich,
+        }
+    }
+
+    /// Wraps `text` in an ANSI colour when colours are enabled.
+    fn paint(&self, text: &str, code: Option<&str>) -> String {
+        match code {
+            Some(code) if self.color => format!("\x1b[{}m{}\x1b[0m", code, text),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// Formats nanoseconds with a readable unit, e.g. `1.25 ms`.
+///
+/// # Example
+///
+/// ```rust
+/// use forge_benchmarks::benchmarks::terminal::format_duration_ns;
+///
+/// assert_eq!(format_duration_ns(1_250_000.0), "1.25 ms");
+/// assert_eq!(format_duration_ns(850.0), "850 ns");
+/// ```
+pub fn format_duration_ns(ns: f64) -> String {
+    let abs = ns.abs();
+    if abs >= 1e9 {
+        format!("{:.2} s", ns / 1e9)
+    } else if abs >= 1e6 {
+        format!("{:.2} ms", ns / 1e6)
+    } else if abs >= 1e3 {
+        format!("{:.2} µs", ns / 1e3)
+    } else {
+        format!("{:.0} ns", ns)
+    }
+}
+
+/// Live per-target progress written to a terminal.
+///
+/// On an interactive terminal the current target's line is redrawn after
+/// every iteration; otherwise only one line per finished target is written.
+pub struct LiveProgress<W> {
+    style: TerminalStyle,
+    state: Mutex<ProgressState<W>>,
+}
+
+struct ProgressState<W> {
+    out: W,
+    target_id: String,
+    position: String,
+    started: Instant,
+    sum_ns: f64,
+    count: u32,
+}
+
+impl LiveProgress<io::Stdout> {
+    /// Creates progress output on stdout.
+    pub fn stdout(style: TerminalStyle) -> Self {
+        Self::new(io::stdout(), style)
+    }
+}
+
+impl<W: Write + Send> LiveProgress<W> {
+    /// Creates progress output on `out`.
+    pub fn new(out: W, style: TerminalStyle) -> Self {
+        Self {
+            style,
+            state: Mutex::new(ProgressState {
+                out,
+                target_id: String::new(),
+                position: String::new(),
+                started: Instant::now(),
+                sum_ns: 0.0,
+                count: 0,
+            }),
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        match self.state.into_inner() {
+            Ok(state) => state.out,
+            Err(poisoned) => poisoned.into_inner().out,
+        }
+    }
+}
+
+impl<W: Write + Send> LiveProgress<W> {
+    fn target_started(&self, target_id: &str, index: usize, total: usize) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.target_id = target_id.to_string();
+        state.position = format!("[{}/{}]", index + 1, total);
+        state.started = Instant::now();
+        state.sum_ns = 0.0;
+        state.count = 0;
+
+        if self.style.interactive {
+            let line = format!("{}{} {} starting", CLEAR_LINE, state.position, target_id);
+            let _ = state.out.write_all(line.as_bytes());
+            let _ = state.out.flush();
+        }
+    }
+
+    fn sample_recorded(&self, iteration: u32, iterations: u32, sample_ns: f64) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.sum_ns += sample_ns;
+        state.count += 1;
+
+        if self.style.interactive {
+            let line = format!(
+                "{}{} {}  iteration {}/{}  elapsed {}  mean {}",
+                CLEAR_LINE,
+                state.position,
+                state.target_id,
+                iteration,
+                iterations,
+                format_duration_ns(state.started.elapsed().as_nanos() as f64),
+                format_duration_ns(state.sum_ns / f64::from(state.count))
+            );
+            let _ = state.out.write_all(line.as_bytes());
+            let _ = state.out.flush();
+        }
+    }
+
+    /// Prints a failed attempt and restarts the sample statistics.
+    fn target_retrying(&self, target_id: &str, attempt: u32, max_attempts: u32, error: &str) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.sum_ns = 0.0;
+        state.count = 0;
+
+        let clear = if self.style.interactive {
+            CLEAR_LINE
+        } else {
+            ""
+        };
+        let line = format!(
+            "{}{} {} {}: {} (attempt {}/{})",
+            clear,
+            state.position,
+            self.style.paint("RETRY", Some(YELLOW)),
+            target_id,
+            error,
+            attempt,
+            max_attempts
+        );
+        let _ = writeln!(state.out, "{}", line);
+        let _ = state.out.flush();
+    }
+
+    /// Prints the final line of a target; `unrun` is the status label,
+    /// colour and message of a target that failed or was skipped.
+    fn target_done(&self, target_id: &str, unrun: Option<(&str, &'static str, &str)>) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let elapsed = format_duration_ns(state.started.elapsed().as_nanos() as f64);
+        let line = match unrun {
+            Some((label, color, message)) => format!(
+                "{} {} {}: {}",
+                state.position,
+                self.style.paint(label, Some(color)),
+                target_id,
+                message
+            ),
+            None => format!(
+                "{} {} {} ({} samples in {})",
+                state.position,
+                self.style.paint("PASSED", Some(GREEN)),
+                target_id,
+                state.count,
+                elapsed
+            ),
+        };
+
+        let clear = if self.style.interactive {
+            CLEAR_LINE
+        } else {
+            ""
+        };
+        let _ = writeln!(state.out, "{}{}", clear, line);
+        let _ = state.out.flush();
+    }
+}
+
+impl<W: Write + Send> RunObserver for LiveProgress<W> {
+    fn on_event(&self, event: &RunEvent) {
+        match event {
+            RunEvent::TargetStarted {
+                target_id,
+                index,
+                total,
+                ..
+            } => self.target_started(target_id, *index, *total),
+            RunEvent::SampleRecorded {
+                iteration,
+                iterations,
+                sample_ns,
+                ..
+            } => self.sample_recorded(*iteration, *iterations, *sample_ns),
+            RunEvent::TargetRetrying {
+                target_id,
+                attempt,
+                max_attempts,
+                error,
+                ..
+            } => self.target_retrying(target_id, *attempt, *max_attempts, error),
+            RunEvent::TargetFinished { result } => self.target_done(&result.target_id, None),
+            RunEvent::TargetFailed {
+                target_id, error, ..
+            } => self.target_done(target_id, Some(("FAILED", RED, error))),
+            RunEvent::TargetSkipped {
+                target_id, reason, ..
+            } => self.target_done(target_id, Some(("SKIPPED", YELLOW, reason))),
+            RunEvent::RunStarted { .. } | RunEvent::RunFinished { .. } => {}
+        }
+    }
+}
+
+/// Renders the final results table.
+///
+/// Columns are target, status, median, p95, coefficient of variation and
+/// the change of the gate metric against the baseline, if one was given.
+///
+/// # Arguments
+///
+/// * `report` - The finished run
+/// * `comparisons` - Baseline comparisons (may be empty)
+/// * `style` - Whether to use colours
+///
+/// # Returns
+///
+/// The table, one line per target plus header and separator.
+pub fn render_table(
+    report: &RunReport,
+    comparisons: &[Comparison],
+    style: TerminalStyle,
+) -> String {
+    const HEADERS: [&str; 6] = ["Target", "Status", "Median", "p95", "CV", "Δ baseline"];
+    const RIGHT_ALIGNED: [bool; 6] = [false, false, true, true, true, true];
+
+    let rows: Vec<[(String, Option<&str>); 6]> = report
+        .results
+        .iter()
+        .map(|result| table_row(result, comparisons))
+        .collect();
+
+    let mut widths = HEADERS.map(|h| h.chars().count());
+    for row in &rows {
+        for (width, (text, _)) in widths.iter_mut().zip(row) {
+            *width = (*width).max(text.chars().count());
+        }
+    }
+
+    let pad = |text: &str, column: usize| {
+        let fill = " ".repeat(widths[column] - text.chars().count());
+        if RIGHT_ALIGNED[column] {
+            format!("{}{}", fill, text)
+        } else {
+            format!("{}{}", text, fill)
+        }
+    };
+
+    let mut out = String::new();
+    let header: Vec<String> = HEADERS
+        .iter()
+        .enumerate()
+        .map(|(i, h)| style.paint(&pad(h, i), Some(BOLD)))
+        .collect();
+    out.push_str(header.join("  ").trim_end());
+    out.push('\n');
+    let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    out.push_str(&rule.join("  "));
+    out.push('\n');
+
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(i, (text, code))| style.paint(&pad(text, i), *code))
+            .collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Cells of one table row with their colours.
+fn table_row(
+    result: &BenchmarkResult,
+    comparisons: &[Comparison],
+) -> [(String, Option<&'static str>); 6] {
+    let dash = || "-".to_string();
+    let summary = result.metrics.sample_summary();
+
+    let status = (
+        result.status_label().to_string(),
+        Some(if result.is_failed() {
+            RED
+        } else if result.is_skipped() {
+            YELLOW
+        } else {
+            GREEN
+        }),
+    );
+    let delta = match comparisons.iter().find(|c| c.target_id == result.target_id) {
+        Some(c) => (
+            format!("{:+.2}%", c.delta_pct),
+            match c.verdict {
+                Verdict::Regressed => Some(RED),
+                Verdict::Improved => Some(GREEN),
+                Verdict::Unchanged => None,
+            },
+        ),
+        None => (dash(), None),
+    };
+    let cv = match summary {
+        Some(s) => {
+            let cv = s.cv_pct();
+            // Flag noisy measurements
+            (format!("{:.1}%", cv), (cv > 10.0).then_some(YELLOW))
+        }
+        None => (dash(), None),
+    };
+
+    [
+        (result.target_id.clone(), None),
+        status,
+        (
+            summary
+                .map(|s| format_duration_ns(s.median))
+                .unwrap_or_else(dash),
+            None,
+        ),
+        (
+            summary
+                .map(|s| format_duration_ns(s.p95))
+                .unwrap_or_else(dash),
+            None,
+        ),
+        cv,
+        delta,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::compare::compare_reports;
+    use serde_json::json;
+
+    fn sample_report(median_ns: f64) -> RunReport {
+        RunReport::from_legacy(vec![
+            BenchmarkResult::new(
+                "forge-cli-parse".to_string(),
+                json!({"ops_per_sec": 1e9 / median_ns, "raw_ns": vec![median_ns; 5]}),
+            ),
+            BenchmarkResult::failed("gen".to_string(), "npx not found".to_string()),
+        ])
+    }
+
+    fn started(target_id: &str, index: usize, total: usize) -> RunEvent {
+        RunEvent::TargetStarted {
+            target_id: target_id.to_string(),
+            index,
+            total,
+            at: chrono::Utc::now(),
+        }
+    }
+
+    fn sample(iteration: u32, iterations: u32, sample_ns: f64) -> RunEvent {
+        RunEvent::SampleRecorded {
+            target_id: "forge-cli-parse".to_string(),
+            iteration,
+            iterations,
+            sample_ns,
+            at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_style_detection() {
+        assert!(TerminalStyle::resolve(true, false).interactive);
+        assert!(TerminalStyle::resolve(true, false).color);
+        assert_eq!(TerminalStyle::resolve(false, false), TerminalStyle::plain());
+        assert_eq!(TerminalStyle::resolve(true, true), TerminalStyle::plain());
+    }
+
+    #[test]
+    fn test_plain_table_is_aligned() {
+        let baseline = sample_report(1_000_000.0);
+        let current = sample_report(2_000_000.0);
+        let comparisons = compare_reports(&baseline, &current, "ops_per_sec", 5.0);
+
+        let table = render_table(&current, &comparisons, TerminalStyle::plain());
+
+        assert!(!table.contains('\x1b'));
+        assert_eq!(
+            table,
+            "\
+Target           Status   Median      p95    CV  Δ baseline
+---------------  ------  -------  -------  ----  ----------
+forge-cli-parse  PASSED  2.00 ms  2.00 ms  0.0%     -50.00%
+gen              FAILED        -        -     -           -
+"
+        );
+    }
+
+    #[test]
+    fn test_colored_table() {
+        let style = TerminalStyle::resolve(true, false);
+        let table = render_table(&sample_report(1_000.0), &[], style);
+
+        assert!(table.contains("\x1b[32mPASSED\x1b[0m"));
+        assert!(table.contains("\x1b[31mFAILED\x1b[0m"));
+    }
+
+    #[test]
+    fn test_plain_progress_prints_one_line_per_target() {
+        let progress = LiveProgress::new(Vec::new(), TerminalStyle::plain());
+        let report = sample_report(1_000.0);
+
+        progress.on_event(&started("forge-cli-parse", 0, 3));
+        progress.on_event(&sample(1, 2, 1_000.0));
+        progress.on_event(&sample(2, 2, 1_000.0));
+        progress.on_event(&RunEvent::target_done(&report.results[0]));
+        progress.on_event(&started("gen", 1, 3));
+        progress.on_event(&RunEvent::TargetRetrying {
+            target_id: "gen".to_string(),
+            attempt: 1,
+            max_attempts: 2,
+            error: "EBUSY".to_string(),
+            at: chrono::Utc::now(),
+        });
+        progress.on_event(&RunEvent::target_done(&report.results[1]));
+        progress.on_event(&started("validate", 2, 3));
+        progress.on_event(&RunEvent::target_done(&BenchmarkResult::skipped(
+            "validate".to_string(),
+            "node_modules does not exist".to_string(),
+        )));
+
+        let output = String::from_utf8(progress.into_inner()).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("[1/3] PASSED forge-cli-parse (2 samples in "));
+        assert_eq!(lines[1], "[2/3] RETRY gen: EBUSY (attempt 1/2)");
+        assert_eq!(lines[2], "[2/3] FAILED gen: npx not found");
+        assert_eq!(lines[3], "[3/3] SKIPPED validate: node_modules does not exist");
+    }
+
+    #[test]
+    fn test_interactive_progress_redraws_line() {
+        let progress = LiveProgress::new(Vec::new(), TerminalStyle::resolve(true, false));
+
+        progress.on_event(&started("forge-cli-parse", 0, 1));
+        progress.on_event(&sample(1, 10, 3_000_000.0));
+
+        let output = String::from_utf8(progress.into_inner()).unwrap();
+        assert!(output.contains("\r\x1b[2K[1/1] forge-cli-parse  iteration 1/10  elapsed "));
+        assert!(output.ends_with("mean 3.00 ms"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration_ns(2_500_000_000.0), "2.50 s");
+        assert_eq!(format_duration_ns(1_500.0), "1.50 µs");
+        assert_eq!(format_duration_ns(12.0), "12 ns");
+    }
+}
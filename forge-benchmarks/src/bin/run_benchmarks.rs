@@ -1,6 +1,6 @@
 //! CLI binary for running LLM-Forge benchmarks.
 //!
-//! This binary invokes `run_all_benchmarks()` and writes results to the
+//! This binary invokes `run_suite_with_events()` and writes the run report to the
 //! canonical output directories.
 //!
 //! # Usage
@@ -16,17 +16,52 @@
 //! cargo run --bin run_benchmarks -- --output ./custom-output
 //! ```
 
-use forge_benchmarks::benchmarks::{io, markdown, run_all_benchmarks};
+use forge_benchmarks::adapters::resolve_forge_root;
+use forge_benchmarks::benchmarks::config::RunConfig;
+use forge_benchmarks::benchmarks::events::{EventBus, JsonLinesObserver, RunObserver};
+use forge_benchmarks::benchmarks::history::{self, HistoryQuery};
+use forge_benchmarks::benchmarks::tabular::{self, TableFormat};
+use forge_benchmarks::benchmarks::terminal::{self, LiveProgress, TerminalStyle};
+use forge_benchmarks::benchmarks::{
+    compare, io, markdown, retention, run_suite_with_events, schema, template,
+};
 use std::env;
+use std::fs::File;
+use std::io::BufWriter;
 use std::path::PathBuf;
-use tracing::{error, info, Level};
+use std::sync::Arc;
+use tracing::{error, info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
+/// Number of recent runs plotted in the HTML report trend charts.
+const HTML_HISTORY_RUNS: usize = 30;
+
+/// Exit code of a run in which nothing failed but some targets were skipped
+/// because a precondition was not met.
+const EXIT_SKIPPED: i32 = 3;
+
 /// CLI arguments (simple parsing without external crate dependency).
 struct Args {
     output_path: PathBuf,
     verbose: bool,
     json_only: bool,
+    print_schema: bool,
+    validate: Option<PathBuf>,
+    config_path: Option<PathBuf>,
+    forge_root: Option<PathBuf>,
+    junit: bool,
+    tables: Vec<TableFormat>,
+    github_benchmark: bool,
+    bmf: bool,
+    baseline: Option<PathBuf>,
+    regression_threshold: f64,
+    require_significance: bool,
+    events: Option<PathBuf>,
+    serve_metrics: Option<String>,
+    pushgateway: Option<String>,
+    otlp_endpoint: Option<String>,
+    no_cache: bool,
+    refresh: bool,
 }
 
 impl Args {
@@ -35,18 +70,79 @@ impl Args {
         let mut output_path = PathBuf::from(".");
         let mut verbose = false;
         let mut json_only = false;
+        let mut print_schema = false;
+        let mut validate = None;
+        let mut config_path = None;
+        let mut forge_root = None;
+        let mut junit = false;
+        let mut tables = Vec::new();
+        let mut github_benchmark = false;
+        let mut bmf = false;
+        let mut baseline = None;
+        let mut regression_threshold = compare::DEFAULT_REGRESSION_THRESHOLD_PCT;
+        let mut require_significance = false;
+        let mut events = None;
+        let mut serve_metrics = None;
+        let mut pushgateway = None;
+        let mut otlp_endpoint = None;
+        let mut no_cache = false;
+        let mut refresh = false;
 
         let mut i = 1;
         while i < args.len() {
             match args[i].as_str() {
-                "--output" | "-o" => {
-                    if i + 1 < args.len() {
-                        output_path = PathBuf::from(&args[i + 1]);
-                        i += 1;
-                    }
+                "--output" | "-o" if i + 1 < args.len() => {
+                    output_path = PathBuf::from(&args[i + 1]);
+                    i += 1;
                 }
                 "--verbose" | "-v" => verbose = true,
                 "--json" => json_only = true,
+                "--print-schema" => print_schema = true,
+                "--validate" if i + 1 < args.len() => {
+                    validate = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                }
+                "--config" | "-c" if i + 1 < args.len() => {
+                    config_path = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                }
+                "--forge-root" if i + 1 < args.len() => {
+                    forge_root = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                }
+                "--junit" => junit = true,
+                "--csv" => tables.push(TableFormat::Csv),
+                "--tsv" => tables.push(TableFormat::Tsv),
+                "--github-benchmark" => github_benchmark = true,
+                "--bmf" => bmf = true,
+                "--baseline" if i + 1 < args.len() => {
+                    baseline = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                }
+                "--regression-threshold" if i + 1 < args.len() => {
+                    regression_threshold = parse_or_exit("--regression-threshold", &args[i + 1])
+                        .unwrap_or(regression_threshold);
+                    i += 1;
+                }
+                "--require-significance" => require_significance = true,
+                "--events" if i + 1 < args.len() => {
+                    events = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                }
+                "--serve-metrics" if i + 1 < args.len() => {
+                    serve_metrics = Some(args[i + 1].clone());
+                    i += 1;
+                }
+                "--pushgateway" if i + 1 < args.len() => {
+                    pushgateway = Some(args[i + 1].clone());
+                    i += 1;
+                }
+                "--otlp-endpoint" if i + 1 < args.len() => {
+                    otlp_endpoint = Some(args[i + 1].clone());
+                    i += 1;
+                }
+                "--no-cache" => no_cache = true,
+                "--refresh" => refresh = true,
                 "--help" | "-h" => {
                     print_help();
                     std::process::exit(0);
@@ -60,7 +156,166 @@ impl Args {
             output_path,
             verbose,
             json_only,
+            print_schema,
+            validate,
+            config_path,
+            forge_root,
+            junit,
+            tables,
+            github_benchmark,
+            bmf,
+            baseline,
+            regression_threshold,
+            require_significance,
+            events,
+            serve_metrics,
+            pushgateway,
+            otlp_endpoint,
+            no_cache,
+            refresh,
+        }
+    }
+}
+
+/// Arguments of the `history` subcommand.
+struct HistoryArgs {
+    output_path: PathBuf,
+    query: HistoryQuery,
+    metric: String,
+    json: bool,
+    table: Option<TableFormat>,
+}
+
+impl HistoryArgs {
+    fn parse(args: &[String]) -> Self {
+        let mut output_path = PathBuf::from(".");
+        let mut query = HistoryQuery {
+            last_n: Some(10),
+            ..HistoryQuery::default()
+        };
+        let mut metric = "ops_per_sec".to_string();
+        let mut json = false;
+        let mut table = None;
+
+        let mut i = 0;
+        while i < args.len() {
+            let value = args.get(i + 1);
+            match (args[i].as_str(), value) {
+                ("--output" | "-o", Some(v)) => output_path = PathBuf::from(v),
+                ("--target" | "-t", Some(v)) => query.target_id = Some(v.clone()),
+                ("--last" | "-n", Some(v)) => query.last_n = parse_or_exit("--last", v),
+                ("--metric" | "-m", Some(v)) => metric = v.clone(),
+                ("--since", Some(v)) => query.since = Some(date_or_exit("--since", v)),
+                ("--until", Some(v)) => query.until = Some(date_or_exit("--until", v)),
+                ("--commit", Some(v)) => query.git_commit = Some(v.clone()),
+                ("--run", Some(v)) => query.run_id = parse_or_exit("--run", v),
+                ("--json", _) => {
+                    json = true;
+                    i += 1;
+                    continue;
+                }
+                ("--csv", _) | ("--tsv", _) => {
+                    table = Some(if args[i] == "--csv" {
+                        TableFormat::Csv
+                    } else {
+                        TableFormat::Tsv
+                    });
+                    i += 1;
+                    continue;
+                }
+                ("--help" | "-h", _) => {
+                    print_history_help();
+                    std::process::exit(0);
+                }
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            }
+            i += 2;
+        }
+
+        Self {
+            output_path,
+            query,
+            metric,
+            json,
+            table,
+        }
+    }
+}
+
+fn parse_or_exit<T: std::str::FromStr>(flag: &str, value: &str) -> Option<T> {
+    match value.parse() {
+        Ok(v) => Some(v),
+        Err(_) => {
+            eprintln!("Invalid value for {}: {}", flag, value);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn date_or_exit(flag: &str, value: &str) -> chrono::DateTime<chrono::Utc> {
+    history::parse_date(value).unwrap_or_else(|| {
+        eprintln!(
+            "Invalid date for {}: {} (expected YYYY-MM-DD or RFC 3339)",
+            flag, value
+        );
+        std::process::exit(2);
+    })
+}
+
+fn print_history_help() {
+    println!(
+        r#"run_benchmarks history - Query the benchmark history log
+
+USAGE:
+    run_benchmarks history [OPTIONS]
+
+OPTIONS:
+    -o, --output <PATH>    Directory containing benchmarks/output (default: current directory)
+    -t, --target <ID>      Only show this target
+    -n, --last <N>         Show the last N matching runs (default: 10)
+    -m, --metric <KEY>     Metric to show (default: ops_per_sec)
+    --since <DATE>         Only runs started at or after DATE (YYYY-MM-DD or RFC 3339)
+    --until <DATE>         Only runs started at or before DATE
+    --commit <SHA>         Only runs whose git commit starts with SHA
+    --run <UUID>           Only the run with this ID
+    --json                 Print the series as JSON instead of a table
+    --csv, --tsv           Print the series as CSV or TSV instead of a table
+    -h, --help             Print help information
+"#
+    );
+}
+
+/// Runs the `history` subcommand.
+fn run_history(args: HistoryArgs) {
+    let base_path = args.output_path.to_str().unwrap_or(".");
+
+    let series = match history::metric_series(base_path, &args.query, &args.metric) {
+        Ok(series) => series,
+        Err(e) => {
+            eprintln!("Failed to read history: {}", e);
+            std::process::exit(1);
         }
+    };
+
+    if let Some(format) = args.table {
+        let stdout = std::io::stdout();
+        if let Err(e) = tabular::write_series_table(&series, &args.metric, format, stdout.lock()) {
+            eprintln!("Failed to write history: {}", e);
+            std::process::exit(1);
+        }
+    } else if args.json {
+        match serde_json::to_string_pretty(&series) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize history: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        print!("{}", history::format_series_table(&series, &args.metric));
     }
 }
 
@@ -70,11 +325,38 @@ fn print_help() {
 
 USAGE:
     run_benchmarks [OPTIONS]
+    run_benchmarks history [OPTIONS]    Query past runs (see `history --help`)
 
 OPTIONS:
     -o, --output <PATH>    Output directory for results (default: current directory)
-    -v, --verbose          Enable verbose logging
+    -c, --config <FILE>    JSON run configuration (iterations, retries, quarantine, retention, ...)
+    --forge-root <DIR>     Root of the llm-forge checkout to benchmark (default: $FORGE_ROOT,
+                           then `forge_root` in the config, then the nearest ancestor of
+                           the working directory whose package.json is llm-forge)
+    -v, --verbose          Enable verbose logging (instead of live progress on a terminal)
     --json                 Output JSON results to stdout only (skip file writes)
+    --print-schema         Print the JSON Schema for results files and exit
+    --validate <FILE>      Validate a results file against the schema and exit
+    --junit                Also write a JUnit XML report (benchmarks/output/junit.xml)
+    --csv, --tsv           Also write the results as a table (benchmarks/output/results.csv|tsv)
+    --github-benchmark     Also write github-action-benchmark files (customSmaller/BiggerIsBetter)
+    --bmf                  Also write Bencher Metric Format (benchmarks/output/bmf.json)
+    --baseline <FILE>      Compare against a previous results.json (or .csv/.tsv table)
+    --regression-threshold <PCT>
+                           Change in ops/sec tolerated against the baseline (default: 5)
+    --require-significance Only report changes that pass Welch's t-test (95%) on the
+                           raw samples as regressions/improvements
+    --events <FILE>        Write progress events as JSON lines (run/target/sample)
+    --serve-metrics <ADDR> Serve Prometheus metrics on http://ADDR/metrics during the run
+    --pushgateway <URL>    Push metrics to a Prometheus Pushgateway after the run,
+                           grouped by run_id and target_id (infra-metrics feature)
+    --otlp-endpoint <URL>  Export run/target/iteration spans and latency histograms
+                           to an OTLP/HTTP collector, e.g. http://localhost:4318
+                           (infra-otel feature)
+    --no-cache             Measure every target and leave cached results untouched
+    --refresh              Measure every target and replace its cached result
+                           (without either, targets whose inputs are unchanged since
+                           the last run reuse its results; infra-cache feature)
     -h, --help             Print help information
 
 EXAMPLES:
@@ -87,22 +369,90 @@ EXAMPLES:
     # Run with verbose logging
     RUST_LOG=debug cargo run --bin run_benchmarks -- -v
 
+    # Show the last 20 runs of a target
+    cargo run --bin run_benchmarks -- history --target forge-cli-parse --last 20
+
+    # Publish a JUnit report with regressions against the main branch run
+    cargo run --bin run_benchmarks -- --junit --baseline main/results.json
+
+    # Expose metrics for scraping while a long run is in progress
+    cargo run --features infra-metrics --bin run_benchmarks -- --serve-metrics 0.0.0.0:9464
+
+    # Check a results file produced by any benchmark-target repository
+    cargo run --bin run_benchmarks -- --validate benchmarks/output/results.json
+
+TERMINAL OUTPUT:
+    On a terminal, progress is redrawn in place and the final table is coloured.
+    Output is plain when stdout is not a TTY or NO_COLOR is set.
+
 OUTPUT FILES:
-    benchmarks/output/results.json    Combined benchmark results
-    benchmarks/output/summary.md      Markdown summary report
+    benchmarks/output/results.json    Run report with all benchmark results
+    benchmarks/output/summary.md      Markdown summary report (layout set by report.template)
+    benchmarks/output/report.html     Self-contained HTML report with charts
+    benchmarks/output/comparison.md   Baseline comparison for PR comments (with --baseline)
+    benchmarks/output/junit.xml       JUnit XML report (with --junit)
+    benchmarks/output/results.csv|tsv Flattened results table (with --csv/--tsv)
+    benchmarks/output/github-benchmark-*.json
+                                      github-action-benchmark input (with --github-benchmark)
+    benchmarks/output/bmf.json        Bencher Metric Format (with --bmf)
     benchmarks/output/raw/*.json      Individual result files
-    benchmarks/output/history.jsonl   Historical results log
+    benchmarks/output/raw/index.json  Target ID to raw file name index
+    benchmarks/output/environment.json  Environment fingerprint of the run
+    benchmarks/output/history.jsonl   Historical run log (one run per line)
+    benchmarks/output/history-*.jsonl[.gz]  Rotated history segments (see retention)
+
+EXIT CODES:
+    0    All benchmarks passed (failures of quarantined targets are ignored)
+    1    A benchmark failed, or the run could not be completed
+    2    Invalid command-line arguments
+    3    No benchmark failed, but some were skipped because a precondition
+         (Node.js, npx, Forge build output, ...) was not met
 "#
     );
 }
 
 #[tokio::main]
 async fn main() {
+    let raw_args: Vec<String> = env::args().collect();
+    if raw_args.get(1).map(String::as_str) == Some("history") {
+        run_history(HistoryArgs::parse(&raw_args[2..]));
+        return;
+    }
+
     let args = Args::parse();
 
+    // Schema utilities run without executing any benchmarks
+    if args.print_schema {
+        match serde_json::to_string_pretty(&schema::results_schema()) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize schema: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(path) = &args.validate {
+        match schema::validate_results_file(path) {
+            Ok(()) => println!("{}: valid", path.display()),
+            Err(e) => {
+                eprintln!("{}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Live progress replaces per-target log lines on an interactive terminal
+    let style = TerminalStyle::detect();
+    let live = style.interactive && !args.json_only;
+
     // Initialize logging
     let log_level = if args.verbose {
         Level::DEBUG
+    } else if live {
+        Level::WARN
     } else {
         Level::INFO
     };
@@ -127,20 +477,212 @@ async fn main() {
         std::process::exit(1);
     }
 
+    // Hold the output lock for the whole run so concurrent runs cannot
+    // interleave their writes; the OS releases it when the process exits
+    let _lock = if args.json_only {
+        None
+    } else {
+        match io::OutputLock::acquire(output_str) {
+            Ok(lock) => Some(lock),
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    };
+
+    let mut config = match &args.config_path {
+        Some(path) => match RunConfig::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Failed to load config {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        },
+        None => RunConfig::default(),
+    };
+
+    // Check the summary template before running so a broken one fails fast
+    if let Some(path) = &config.report.template {
+        if let Err(e) = template::check_template(path) {
+            error!("Invalid summary template {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
+
+    // Resolve the Forge checkout before running so a wrong root fails fast
+    match resolve_forge_root(args.forge_root.as_deref(), config.forge_root.as_deref()) {
+        Ok(root) => {
+            info!("Forge root: {}", root.display());
+            config.forge_root = Some(root);
+        }
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    }
+
+    // Load the baseline before running so a bad path fails fast
+    let baseline = match &args.baseline {
+        Some(path) => match io::read_results_file(path) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                error!("Failed to read baseline {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // Observers of the run's progress events
+    let mut observers: Vec<Arc<dyn RunObserver>> = Vec::new();
+    if !args.json_only {
+        observers.push(Arc::new(LiveProgress::stdout(style)));
+    }
+    if let Some(path) = &args.events {
+        match File::create(path) {
+            Ok(file) => observers.push(Arc::new(JsonLinesObserver::new(BufWriter::new(file)))),
+            Err(e) => {
+                error!("Failed to create event log {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Export spans and histograms to an OpenTelemetry collector
+    #[cfg(feature = "infra-otel")]
+    if let Some(endpoint) = &args.otlp_endpoint {
+        match forge_benchmarks::infra::otel::OtelObserver::otlp(Some(endpoint)) {
+            Ok(observer) => observers.push(Arc::new(observer)),
+            Err(e) => {
+                error!(
+                    "Failed to set up OpenTelemetry export to {}: {}",
+                    endpoint, e
+                );
+                std::process::exit(1);
+            }
+        }
+    }
+    #[cfg(not(feature = "infra-otel"))]
+    if args.otlp_endpoint.is_some() {
+        error!("--otlp-endpoint requires the infra-otel feature");
+        std::process::exit(1);
+    }
+
+    // Serve /metrics for the duration of the run
+    #[cfg(feature = "infra-metrics")]
+    let _metrics_server = match &args.serve_metrics {
+        Some(addr) => {
+            if let Err(e) = forge_benchmarks::infra::metrics::init_metrics() {
+                error!("Failed to initialize metrics: {}", e);
+                std::process::exit(1);
+            }
+            match forge_benchmarks::infra::server::MetricsServer::start(addr) {
+                Ok(server) => {
+                    info!("Serving metrics on http://{}/metrics", server.local_addr());
+                    Some(server)
+                }
+                Err(e) => {
+                    error!("Failed to start metrics server on {}: {}", addr, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => None,
+    };
+    #[cfg(not(feature = "infra-metrics"))]
+    if args.serve_metrics.is_some() || args.pushgateway.is_some() {
+        error!("--serve-metrics and --pushgateway require the infra-metrics feature");
+        std::process::exit(1);
+    }
+
+    #[cfg(not(feature = "infra-cache"))]
+    if args.no_cache || args.refresh {
+        error!("--no-cache and --refresh require the infra-cache feature");
+        std::process::exit(1);
+    }
+
     // Run all benchmarks
     info!("Executing benchmarks...");
-    let results = run_all_benchmarks().await;
+    let bus = EventBus::start(observers);
+    #[cfg(feature = "infra-cache")]
+    let report = if args.no_cache {
+        run_suite_with_events(config, &bus.publisher()).await
+    } else {
+        use forge_benchmarks::benchmarks::{cache::CacheMode, run_suite_cached};
+        use forge_benchmarks::infra::cache::{
+            BenchmarkCache, DEFAULT_DISK_CAPACITY, DEFAULT_RESULT_TTL,
+        };
+
+        let cache = if args.json_only {
+            // No files are written, so only the previous run's results are reusable
+            let cache = BenchmarkCache::new(DEFAULT_RESULT_TTL);
+            if let Ok(previous) = io::read_results(output_str) {
+                match cache.load_report(&previous) {
+                    Ok(loaded) => info!("Loaded {} reusable results", loaded),
+                    Err(e) => warn!("Failed to load cached results: {}", e),
+                }
+            }
+            cache
+        } else {
+            let dir = args.output_path.join(io::CACHE_DIR);
+            let cache = BenchmarkCache::new(DEFAULT_RESULT_TTL);
+            match cache.with_disk_tier(&dir, DEFAULT_DISK_CAPACITY) {
+                Ok(cache) => {
+                    if let Err(e) = cache.cleanup_expired() {
+                        warn!("Failed to prune result cache {}: {}", dir.display(), e);
+                    }
+                    cache
+                }
+                Err(e) => {
+                    // Non-fatal error, every target is measured instead
+                    warn!("Failed to open result cache: {}", e);
+                    BenchmarkCache::new(DEFAULT_RESULT_TTL)
+                }
+            }
+        };
+        let mode = if args.refresh {
+            CacheMode::Refresh
+        } else {
+            CacheMode::Reuse
+        };
+        run_suite_cached(config, &bus.publisher(), &cache, mode).await
+    };
+    #[cfg(not(feature = "infra-cache"))]
+    let report = run_suite_with_events(config, &bus.publisher()).await;
+    bus.finish();
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Push the run's metrics to the Pushgateway
+    #[cfg(feature = "infra-metrics")]
+    if let Some(url) = &args.pushgateway {
+        use forge_benchmarks::infra::pushgateway::{PushgatewayClient, DEFAULT_JOB};
+
+        match PushgatewayClient::new(url, DEFAULT_JOB).push_run(&report) {
+            Ok(()) => info!("Pushed metrics to {}", url),
+            // Non-fatal error, the results are still written below
+            Err(e) => error!("Failed to push metrics to {}: {}", url, e),
+        }
+    }
 
     // Report summary
-    let total = results.len();
-    let failed = results.iter().filter(|r| r.is_failed()).count();
-    let passed = total - failed;
+    let total = report.results.len();
+    let failed = report.blocking_failed_count();
+    let quarantined = report.failed_count() - failed;
+    let skipped = report.skipped_count();
+    let passed = report.passed_count();
 
     info!("Benchmark suite completed: {}/{} passed", passed, total);
 
     // Handle JSON-only output mode
     if args.json_only {
-        match serde_json::to_string_pretty(&results) {
+        match serde_json::to_string_pretty(&report) {
             Ok(json) => println!("{}", json),
             Err(e) => {
                 error!("Failed to serialize results: {}", e);
@@ -153,32 +695,171 @@ async fn main() {
     // Write results to files
     info!("Writing results to {}", output_str);
 
-    if let Err(e) = io::write_results(&results, output_str) {
+    if let Err(e) = io::write_results(&report, output_str) {
         error!("Failed to write results: {}", e);
         std::process::exit(1);
     }
 
     // Append to history
-    if let Err(e) = io::append_to_history(&results, output_str) {
+    if let Err(e) = io::append_to_history(&report, output_str) {
         error!("Failed to append to history: {}", e);
         // Non-fatal error, continue
     }
 
-    // Print CI summary to stdout
-    let ci_summary = markdown::generate_ci_summary(&results);
+    // Apply the history retention policy
+    if let Err(e) = retention::compact_history(output_str, &report.config.retention) {
+        error!("Failed to compact history: {}", e);
+        // Non-fatal error, continue
+    }
+
+    let mut export_paths = Vec::new();
+
+    // Compare against the baseline
+    let mut comparisons = match &baseline {
+        Some(baseline) => compare::compare_reports(
+            baseline,
+            &report,
+            compare::DEFAULT_GATE_METRIC,
+            args.regression_threshold,
+        ),
+        None => Vec::new(),
+    };
+    if args.require_significance {
+        compare::require_significance(&mut comparisons);
+    }
+    for regression in comparisons.iter().filter(|c| c.is_regression()) {
+        warn!(
+            "Regression in {}: {}",
+            regression.target_id,
+            regression.describe()
+        );
+    }
+
+    if let Some(baseline) = &baseline {
+        match io::write_comparison_summary(&report, baseline, &comparisons, output_str) {
+            Ok(path) => export_paths.push(path),
+            Err(e) => {
+                error!("Failed to write comparison summary: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // HTML report with trends over recent history (including this run)
+    let recent = HistoryQuery {
+        last_n: Some(HTML_HISTORY_RUNS),
+        ..HistoryQuery::default()
+    };
+    let history = history::query_history(output_str, &recent).unwrap_or_else(|e| {
+        error!("Failed to read history for the HTML report: {}", e);
+        Vec::new()
+    });
+    for flaky in history::flaky_targets(&history) {
+        warn!(
+            "Flaky target {}: failed in {} and passed after a retry in {} of the last {} runs",
+            flaky.target_id, flaky.failures, flaky.retried, flaky.runs
+        );
+    }
+    if let Err(e) = io::write_html_report(&report, &comparisons, &history, output_str) {
+        error!("Failed to write HTML report: {}", e);
+        std::process::exit(1);
+    }
+
+    // A custom summary template may use comparisons and history
+    if report.config.report.template.is_some() {
+        if let Err(e) = io::write_summary(&report, &comparisons, &history, output_str) {
+            // Non-fatal error, summary.md keeps the built-in layout
+            error!("Failed to render summary template: {}", e);
+        }
+    }
+
+    if args.junit {
+        if let Err(e) = io::write_junit(&report, &comparisons, output_str) {
+            error!("Failed to write JUnit report: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    for format in &args.tables {
+        match io::write_results_table(&report, *format, output_str) {
+            Ok(path) => export_paths.push(path),
+            Err(e) => {
+                error!("Failed to write results table: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let mappings = report.config.export_mappings();
+    if args.github_benchmark {
+        match io::write_github_benchmark(&report, &mappings, output_str) {
+            Ok(paths) => export_paths.extend(paths),
+            Err(e) => {
+                error!("Failed to write github-action-benchmark files: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    if args.bmf {
+        match io::write_bmf(&report, &mappings, output_str) {
+            Ok(path) => export_paths.push(path),
+            Err(e) => {
+                error!("Failed to write BMF file: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Print the results table and CI summary to stdout
+    print!("\n{}", terminal::render_table(&report, &comparisons, style));
+    let ci_summary = markdown::generate_ci_summary(&report.results);
     println!("\n{}", ci_summary);
 
     // Print file locations
-    println!("\nResults written to:");
+    println!("\nRun {} results written to:", report.run_id);
     println!("  - {}/benchmarks/output/results.json", output_str);
     println!("  - {}/benchmarks/output/summary.md", output_str);
+    println!("  - {}/benchmarks/output/report.html", output_str);
+    println!("  - {}/benchmarks/output/environment.json", output_str);
+    if args.junit {
+        println!("  - {}/benchmarks/output/junit.xml", output_str);
+    }
+    for path in &export_paths {
+        println!("  - {}", path.display());
+    }
     println!("  - {}/benchmarks/output/raw/", output_str);
 
-    // Exit with error code if any benchmarks failed
+    if quarantined > 0 {
+        warn!(
+            "{} quarantined benchmark(s) failed; they do not fail the build",
+            quarantined
+        );
+    }
+
+    // Exit with error code if any benchmarks outside the quarantine failed
     if failed > 0 {
-        error!("{} benchmark(s) failed", failed);
+        let environmental = report
+            .results
+            .iter()
+            .filter(|result| result.is_blocking_failure())
+            .filter_map(|result| result.failure())
+            .filter(|failure| failure.is_environmental())
+            .count();
+        if environmental > 0 {
+            error!(
+                "{} benchmark(s) failed, {} of them because of the environment",
+                failed, environmental
+            );
+        } else {
+            error!("{} benchmark(s) failed", failed);
+        }
         std::process::exit(1);
     }
 
+    if skipped > 0 {
+        warn!("{} benchmark(s) skipped because a precondition was not met", skipped);
+        std::process::exit(EXIT_SKIPPED);
+    }
+
     info!("All benchmarks completed successfully");
 }
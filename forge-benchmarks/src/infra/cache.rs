@@ -2,13 +2,31 @@
 //!
 //! Provides multi-tier caching for benchmark results using the LLM-Dev-Ops
 //! Infra caching system with L1 memory cache and optional L2 support.
+//!
+//! The L2 tier persists entries on disk (see `BenchmarkCache::with_disk_tier`)
+//! so they survive restarts. It is bounded in size, evicting the least
+//! recently used entries, and checksums every entry so that corrupt files
+//! are dropped instead of returned. When `infra-metrics` is enabled, hits and
+//! misses are also counted in the metrics registry.
+//!
+//! Results carrying an input hash are keyed on it, which makes the cache
+//! content-addressed: the runner reuses a result only while the target's
+//! inputs are unchanged (see `benchmarks::cache`).
 
+use crate::benchmarks::cache::ResultCache;
+use crate::benchmarks::io;
 use crate::benchmarks::result::BenchmarkResult;
+use crate::benchmarks::run::RunReport;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::RwLock;
-use std::time::{Duration, Instant};
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
+use tracing::warn;
 
 /// Errors that can occur during cache operations
 #[derive(Error, Debug)]
@@ -29,6 +47,16 @@ pub enum CacheError {
     NotFound,
 }
 
+/// Lifetime of reusable results; older results are measured again even if
+/// their inputs are unchanged
+pub const DEFAULT_RESULT_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Default size bound of the disk tier in bytes
+pub const DEFAULT_DISK_CAPACITY: u64 = 64 * 1024 * 1024;
+
+/// Extension of disk tier entry files
+const ENTRY_EXTENSION: &str = "entry";
+
 /// A cached benchmark result with TTL
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct CachedEntry {
@@ -45,10 +73,45 @@ impl CachedEntry {
     }
 }
 
-/// In-memory L1 cache for benchmark results
+/// Hit and miss counts of a cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups served from memory
+    pub l1_hits: u64,
+    /// Lookups served from disk
+    pub l2_hits: u64,
+    /// Lookups that found no valid entry
+    pub misses: u64,
+    /// Entries evicted from disk to stay within its capacity
+    pub evictions: u64,
+}
+
+/// Tier a lookup was served from
+#[derive(Debug, Clone, Copy)]
+enum Tier {
+    Memory,
+    Disk,
+}
+
+impl Tier {
+    #[cfg(feature = "infra-metrics")]
+    fn label(self) -> &'static str {
+        match self {
+            Tier::Memory => "l1",
+            Tier::Disk => "l2",
+        }
+    }
+}
+
+/// Benchmark result cache: in-memory L1 with an optional disk L2
 pub struct BenchmarkCache {
     entries: RwLock<HashMap<String, CachedEntry>>,
     default_ttl: Duration,
+    disk: Option<DiskTier>,
+    l1_hits: AtomicU64,
+    l2_hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
 }
 
 impl BenchmarkCache {
@@ -57,23 +120,121 @@ impl BenchmarkCache {
         Self {
             entries: RwLock::new(HashMap::new()),
             default_ttl,
+            disk: None,
+            l1_hits: AtomicU64::new(0),
+            l2_hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
         }
     }
 
-    /// Get a cached benchmark result by target ID
-    pub fn get(&self, target_id: &str) -> Result<BenchmarkResult, CacheError> {
-        let entries = self
-            .entries
-            .read()
-            .map_err(|e| CacheError::ReadError(e.to_string()))?;
+    /// Add a persistent L2 tier stored in `dir`
+    ///
+    /// Entries are written through to disk and read back on a memory miss,
+    /// keeping their original expiry. Once the files exceed `capacity`
+    /// bytes, the least recently used entries are evicted.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use forge_benchmarks::infra::cache::{BenchmarkCache, DEFAULT_DISK_CAPACITY};
+    /// use std::time::Duration;
+    ///
+    /// let cache = BenchmarkCache::new(Duration::from_secs(3600))
+    ///     .with_disk_tier("benchmarks/output/cache", DEFAULT_DISK_CAPACITY)
+    ///     .unwrap();
+    /// ```
+    pub fn with_disk_tier(
+        mut self,
+        dir: impl Into<PathBuf>,
+        capacity: u64,
+    ) -> Result<Self, CacheError> {
+        self.disk = Some(DiskTier::open(dir.into(), capacity)?);
+        Ok(self)
+    }
 
-        match entries.get(target_id) {
-            Some(entry) if !entry.is_expired() => Ok(entry.result.clone()),
+    /// Hit and miss counts since the cache was created
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            l1_hits: self.l1_hits.load(Ordering::Relaxed),
+            l2_hits: self.l2_hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Get a cached benchmark result by key
+    ///
+    /// The key is the result's input hash, or its target ID for results
+    /// without one.
+    pub fn get(&self, key: &str) -> Result<BenchmarkResult, CacheError> {
+        let outcome = self.get_from_tiers(key);
+        match &outcome {
+            Ok((_, tier)) => self.record_hit(*tier),
+            Err(_) => self.record_miss(),
+        }
+        outcome.map(|(result, _)| result)
+    }
+
+    fn get_from_tiers(&self, key: &str) -> Result<(BenchmarkResult, Tier), CacheError> {
+        let expired_in_memory = {
+            let entries = self
+                .entries
+                .read()
+                .map_err(|e| CacheError::ReadError(e.to_string()))?;
+
+            match entries.get(key) {
+                Some(entry) if !entry.is_expired() => {
+                    if let Some(disk) = &self.disk {
+                        disk.touch(key);
+                    }
+                    return Ok((entry.result.clone(), Tier::Memory));
+                }
+                Some(_) => true,
+                None => false,
+            }
+        };
+
+        let Some(disk) = &self.disk else {
+            return Err(if expired_in_memory {
+                CacheError::Expired
+            } else {
+                CacheError::NotFound
+            });
+        };
+        match disk.read(key)? {
+            Some(entry) if !entry.is_expired() => {
+                let result = entry.result.clone();
+                self.entries
+                    .write()
+                    .map_err(|e| CacheError::WriteError(e.to_string()))?
+                    .insert(key.to_string(), entry);
+                Ok((result, Tier::Disk))
+            }
             Some(_) => Err(CacheError::Expired),
+            None if expired_in_memory => Err(CacheError::Expired),
             None => Err(CacheError::NotFound),
         }
     }
 
+    fn record_hit(&self, tier: Tier) {
+        match tier {
+            Tier::Memory => &self.l1_hits,
+            Tier::Disk => &self.l2_hits,
+        }
+        .fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(feature = "infra-metrics")]
+        super::metrics::record_cache_hit(tier.label());
+    }
+
+    fn record_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(feature = "infra-metrics")]
+        super::metrics::record_cache_miss();
+    }
+
     /// Cache a benchmark result with default TTL
     pub fn set(&self, result: BenchmarkResult) -> Result<(), CacheError> {
         self.set_with_ttl(result, self.default_ttl)
@@ -81,33 +242,75 @@ impl BenchmarkCache {
 
     /// Cache a benchmark result with custom TTL
     pub fn set_with_ttl(&self, result: BenchmarkResult, ttl: Duration) -> Result<(), CacheError> {
+        self.insert(result, chrono::Utc::now(), ttl)
+    }
+
+    /// Load the reusable results of an earlier run
+    ///
+    /// Results that did not pass or have no input hash are skipped. Each entry's
+    /// TTL counts from when the result was measured, not from loading.
+    ///
+    /// Returns the number of results loaded.
+    pub fn load_report(&self, report: &RunReport) -> Result<usize, CacheError> {
+        let mut loaded = 0;
+        for result in &report.results {
+            if !result.is_passed() || result.input_hash.is_none() {
+                continue;
+            }
+            self.insert(result.clone(), result.timestamp, self.default_ttl)?;
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    fn insert(
+        &self,
+        mut result: BenchmarkResult,
+        cached_at: chrono::DateTime<chrono::Utc>,
+        ttl: Duration,
+    ) -> Result<(), CacheError> {
         let mut entries = self
             .entries
             .write()
             .map_err(|e| CacheError::WriteError(e.to_string()))?;
 
+        let key = cache_key(&result).to_string();
+        // The marker describes a reuse, not the stored measurement
+        result.cached = false;
         let entry = CachedEntry {
-            result: result.clone(),
-            cached_at: chrono::Utc::now(),
+            result,
+            cached_at,
             ttl_secs: ttl.as_secs(),
         };
 
-        entries.insert(result.target_id.clone(), entry);
+        if let Some(disk) = &self.disk {
+            let evicted = disk.write(&key, &entry)?;
+            if evicted > 0 {
+                self.evictions.fetch_add(evicted as u64, Ordering::Relaxed);
+
+                #[cfg(feature = "infra-metrics")]
+                super::metrics::record_cache_evictions(evicted);
+            }
+        }
+        entries.insert(key, entry);
         Ok(())
     }
 
-    /// Remove a cached entry
-    pub fn invalidate(&self, target_id: &str) -> Result<(), CacheError> {
+    /// Remove a cached entry from both tiers
+    pub fn invalidate(&self, key: &str) -> Result<(), CacheError> {
         let mut entries = self
             .entries
             .write()
             .map_err(|e| CacheError::WriteError(e.to_string()))?;
 
-        entries.remove(target_id);
+        entries.remove(key);
+        if let Some(disk) = &self.disk {
+            disk.remove(key)?;
+        }
         Ok(())
     }
 
-    /// Clear all cached entries
+    /// Clear all cached entries from both tiers
     pub fn clear(&self) -> Result<(), CacheError> {
         let mut entries = self
             .entries
@@ -115,10 +318,13 @@ impl BenchmarkCache {
             .map_err(|e| CacheError::WriteError(e.to_string()))?;
 
         entries.clear();
+        if let Some(disk) = &self.disk {
+            disk.clear()?;
+        }
         Ok(())
     }
 
-    /// Get the number of cached entries
+    /// Get the number of entries held in memory
     pub fn len(&self) -> usize {
         self.entries.read().map(|e| e.len()).unwrap_or(0)
     }
@@ -128,16 +334,225 @@ impl BenchmarkCache {
         self.len() == 0
     }
 
-    /// Remove expired entries
+    /// Remove expired entries from both tiers
+    ///
+    /// Returns the number of distinct entries removed.
     pub fn cleanup_expired(&self) -> Result<usize, CacheError> {
         let mut entries = self
             .entries
             .write()
             .map_err(|e| CacheError::WriteError(e.to_string()))?;
 
-        let initial_len = entries.len();
-        entries.retain(|_, entry| !entry.is_expired());
-        Ok(initial_len - entries.len())
+        let mut removed = HashSet::new();
+        entries.retain(|key, entry| {
+            let expired = entry.is_expired();
+            if expired {
+                removed.insert(key.clone());
+            }
+            !expired
+        });
+        if let Some(disk) = &self.disk {
+            removed.extend(disk.cleanup_expired()?);
+        }
+        Ok(removed.len())
+    }
+}
+
+impl ResultCache for BenchmarkCache {
+    fn lookup(&self, input_hash: &str) -> Option<BenchmarkResult> {
+        self.get(input_hash).ok()
+    }
+
+    fn store(&self, result: &BenchmarkResult) {
+        if result.input_hash.is_some() {
+            if let Err(e) = self.set(result.clone()) {
+                warn!("Failed to cache result of {}: {}", result.target_id, e);
+            }
+        }
+    }
+}
+
+/// Key of a result: its input hash, or its target ID without one
+fn cache_key(result: &BenchmarkResult) -> &str {
+    result.input_hash.as_deref().unwrap_or(&result.target_id)
+}
+
+/// An entry as stored on disk, with its key to rule out hash collisions
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    entry: CachedEntry,
+}
+
+/// Persistent L2 tier: one file per entry
+///
+/// Files start with the SHA-256 of their body on a line of its own, so
+/// truncated or edited entries are detected. Modification times record the
+/// last use of each entry and drive LRU eviction across restarts.
+struct DiskTier {
+    dir: PathBuf,
+    capacity: u64,
+}
+
+impl DiskTier {
+    fn open(dir: PathBuf, capacity: u64) -> Result<Self, CacheError> {
+        fs::create_dir_all(&dir)
+            .map_err(|e| CacheError::InitError(format!("{}: {}", dir.display(), e)))?;
+        Ok(Self { dir, capacity })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let digest = Sha256::digest(key.as_bytes());
+        self.dir.join(format!("{:x}.{}", digest, ENTRY_EXTENSION))
+    }
+
+    /// Read the entry stored under `key`, expired or not
+    ///
+    /// Corrupt files are removed and reported as missing.
+    fn read(&self, key: &str) -> Result<Option<CachedEntry>, CacheError> {
+        let path = self.path(key);
+        match read_entry_file(&path)? {
+            Some(stored) if stored.key == key => {
+                touch(&path);
+                Ok(Some(stored.entry))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Write `entry` under `key`, then evict down to capacity
+    ///
+    /// Returns the number of entries evicted.
+    fn write(&self, key: &str, entry: &CachedEntry) -> Result<usize, CacheError> {
+        let stored = DiskEntry {
+            key: key.to_string(),
+            entry: entry.clone(),
+        };
+        let body =
+            serde_json::to_vec(&stored).map_err(|e| CacheError::WriteError(e.to_string()))?;
+        let mut contents = format!("{:x}\n", Sha256::digest(&body)).into_bytes();
+        contents.extend_from_slice(&body);
+
+        io::write_atomic(&self.path(key), &contents)
+            .map_err(|e| CacheError::WriteError(e.to_string()))?;
+        self.evict()
+    }
+
+    /// Mark the entry under `key` as used
+    fn touch(&self, key: &str) {
+        touch(&self.path(key));
+    }
+
+    fn remove(&self, key: &str) -> Result<(), CacheError> {
+        match fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(CacheError::WriteError(e.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn clear(&self) -> Result<(), CacheError> {
+        for (path, _) in self.files()? {
+            fs::remove_file(&path).map_err(|e| CacheError::WriteError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Remove expired and corrupt files, returning the keys of expired ones
+    ///
+    /// Files that cannot be read or removed are logged and skipped, so one
+    /// bad entry does not stop the sweep.
+    fn cleanup_expired(&self) -> Result<Vec<String>, CacheError> {
+        let mut removed = Vec::new();
+        for (path, _) in self.files()? {
+            let stored = match read_entry_file(&path) {
+                Ok(Some(stored)) if stored.entry.is_expired() => stored,
+                Ok(_) => continue,
+                Err(e) => {
+                    warn!("Skipping unreadable cache entry {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            match fs::remove_file(&path) {
+                Ok(()) => removed.push(stored.key),
+                Err(e) => warn!(
+                    "Failed to remove expired cache entry {}: {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Remove least recently used files until the tier fits its capacity
+    fn evict(&self) -> Result<usize, CacheError> {
+        let mut files = self.files()?;
+        let mut total: u64 = files.iter().map(|(_, metadata)| metadata.len()).sum();
+        if total <= self.capacity {
+            return Ok(0);
+        }
+
+        files.sort_by_key(|(_, metadata)| metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+        let mut evicted = 0;
+        for (path, metadata) in files {
+            if total <= self.capacity {
+                break;
+            }
+            fs::remove_file(&path).map_err(|e| CacheError::WriteError(e.to_string()))?;
+            total -= metadata.len();
+            evicted += 1;
+        }
+        Ok(evicted)
+    }
+
+    /// Entry files with their metadata
+    fn files(&self) -> Result<Vec<(PathBuf, fs::Metadata)>, CacheError> {
+        let read_dir = fs::read_dir(&self.dir).map_err(|e| CacheError::ReadError(e.to_string()))?;
+        let mut files = Vec::new();
+        for dir_entry in read_dir {
+            let dir_entry = dir_entry.map_err(|e| CacheError::ReadError(e.to_string()))?;
+            let path = dir_entry.path();
+            if path.extension().is_some_and(|ext| ext == ENTRY_EXTENSION) {
+                if let Ok(metadata) = dir_entry.metadata() {
+                    files.push((path, metadata));
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+/// Read and verify an entry file; corrupt files are removed
+fn read_entry_file(path: &std::path::Path) -> Result<Option<DiskEntry>, CacheError> {
+    let contents = match fs::read(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(CacheError::ReadError(e.to_string())),
+    };
+
+    let verified = contents
+        .iter()
+        .position(|b| *b == b'\n')
+        .map(|split| contents.split_at(split))
+        .filter(|(checksum, body)| {
+            let body = &body[1..];
+            checksum == &format!("{:x}", Sha256::digest(body)).as_bytes()
+        })
+        .and_then(|(_, body)| serde_json::from_slice::<DiskEntry>(&body[1..]).ok());
+
+    if verified.is_none() {
+        warn!("Removing corrupt cache entry {}", path.display());
+        let _ = fs::remove_file(path);
+    }
+    Ok(verified)
+}
+
+/// Set a file's modification time to now, recording its use
+fn touch(path: &std::path::Path) {
+    if let Ok(file) = File::options().write(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
     }
 }
 
@@ -151,6 +566,7 @@ impl Default for BenchmarkCache {
 mod tests {
     use super::*;
     use serde_json::json;
+    use tempfile::TempDir;
 
     #[test]
     fn test_cache_set_get() {
@@ -196,4 +612,185 @@ mod tests {
         cache.clear().unwrap();
         assert!(cache.is_empty());
     }
+
+    #[test]
+    fn test_content_addressed_reuse() {
+        let cache = BenchmarkCache::new(DEFAULT_RESULT_TTL);
+        let mut result =
+            BenchmarkResult::new("forge-cli-parse".to_string(), json!({"ops_per_sec": 10}));
+        cache.store(&result);
+        assert!(
+            cache.is_empty(),
+            "results without an input hash are not reusable"
+        );
+
+        result.input_hash = Some("abc".to_string());
+        cache.store(&result);
+        assert_eq!(cache.lookup("abc").unwrap().target_id, "forge-cli-parse");
+        assert!(cache.lookup("def").is_none());
+    }
+
+    #[test]
+    fn test_load_report() {
+        let mut fresh = BenchmarkResult::new("fresh".to_string(), json!({}));
+        fresh.input_hash = Some("fresh-hash".to_string());
+        let mut stale = BenchmarkResult::new("stale".to_string(), json!({}));
+        stale.input_hash = Some("stale-hash".to_string());
+        stale.timestamp = chrono::Utc::now() - chrono::Duration::days(30);
+        let mut failed = BenchmarkResult::failed("failed".to_string(), "exit status 1".to_string());
+        failed.input_hash = Some("failed-hash".to_string());
+        let unhashed = BenchmarkResult::new("unhashed".to_string(), json!({}));
+        let report = RunReport::from_legacy(vec![fresh, stale, failed, unhashed]);
+
+        let cache = BenchmarkCache::new(DEFAULT_RESULT_TTL);
+        assert_eq!(cache.load_report(&report).unwrap(), 2);
+        assert!(cache.lookup("fresh-hash").is_some());
+        assert!(matches!(cache.get("stale-hash"), Err(CacheError::Expired)));
+        assert!(cache.lookup("failed-hash").is_none());
+    }
+
+    fn hashed(target_id: &str) -> BenchmarkResult {
+        let mut result = BenchmarkResult::new(target_id.to_string(), json!({"ops_per_sec": 10}));
+        result.input_hash = Some(format!("{}-hash", target_id));
+        result
+    }
+
+    fn disk_cache(dir: &TempDir, capacity: u64) -> BenchmarkCache {
+        BenchmarkCache::new(DEFAULT_RESULT_TTL)
+            .with_disk_tier(dir.path(), capacity)
+            .unwrap()
+    }
+
+    fn entry_files(dir: &TempDir) -> usize {
+        fs::read_dir(dir.path())
+            .unwrap()
+            .filter(|e| {
+                let path = e.as_ref().unwrap().path();
+                path.extension().is_some_and(|ext| ext == ENTRY_EXTENSION)
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_disk_tier_survives_restart() {
+        let dir = TempDir::new().unwrap();
+        disk_cache(&dir, DEFAULT_DISK_CAPACITY)
+            .set(hashed("forge-cli-parse"))
+            .unwrap();
+
+        let cache = disk_cache(&dir, DEFAULT_DISK_CAPACITY);
+        assert_eq!(cache.len(), 0);
+        assert_eq!(
+            cache.get("forge-cli-parse-hash").unwrap().target_id,
+            "forge-cli-parse"
+        );
+        assert!(cache.get("forge-cli-parse-hash").is_ok());
+        assert!(cache.get("other-hash").is_err());
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                l1_hits: 1,
+                l2_hits: 1,
+                misses: 1,
+                evictions: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_disk_tier_honours_ttl_across_restarts() {
+        let dir = TempDir::new().unwrap();
+        let mut stale = hashed("stale");
+        stale.timestamp = chrono::Utc::now() - chrono::Duration::days(30);
+        let report = RunReport::from_legacy(vec![stale, hashed("fresh")]);
+        disk_cache(&dir, DEFAULT_DISK_CAPACITY)
+            .load_report(&report)
+            .unwrap();
+        assert_eq!(entry_files(&dir), 2);
+
+        let cache = disk_cache(&dir, DEFAULT_DISK_CAPACITY);
+        assert!(matches!(cache.get("stale-hash"), Err(CacheError::Expired)));
+        assert_eq!(cache.cleanup_expired().unwrap(), 1);
+        assert_eq!(entry_files(&dir), 1);
+        assert!(cache.get("fresh-hash").is_ok());
+    }
+
+    #[test]
+    fn test_disk_tier_cleanup_skips_unreadable_entries() {
+        let dir = TempDir::new().unwrap();
+        let mut stale = hashed("stale");
+        stale.timestamp = chrono::Utc::now() - chrono::Duration::days(30);
+        let report = RunReport::from_legacy(vec![stale, hashed("fresh")]);
+        disk_cache(&dir, DEFAULT_DISK_CAPACITY)
+            .load_report(&report)
+            .unwrap();
+        // A directory with the entry extension cannot be read as a file
+        fs::create_dir(dir.path().join(format!("unreadable.{ENTRY_EXTENSION}"))).unwrap();
+
+        let cache = disk_cache(&dir, DEFAULT_DISK_CAPACITY);
+        assert_eq!(cache.cleanup_expired().unwrap(), 1);
+        assert!(matches!(cache.get("stale-hash"), Err(CacheError::NotFound)));
+        assert!(cache.get("fresh-hash").is_ok());
+    }
+
+    #[test]
+    fn test_disk_tier_drops_corrupt_entries() {
+        let dir = TempDir::new().unwrap();
+        disk_cache(&dir, DEFAULT_DISK_CAPACITY)
+            .set(hashed("forge-cli-parse"))
+            .unwrap();
+        let path = fs::read_dir(dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::write(
+            &path,
+            contents.replace("\"ops_per_sec\":10", "\"ops_per_sec\":99"),
+        )
+        .unwrap();
+
+        let cache = disk_cache(&dir, DEFAULT_DISK_CAPACITY);
+        assert!(matches!(
+            cache.get("forge-cli-parse-hash"),
+            Err(CacheError::NotFound)
+        ));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_disk_tier_evicts_least_recently_used() {
+        let dir = TempDir::new().unwrap();
+        let probe = TempDir::new().unwrap();
+        disk_cache(&probe, DEFAULT_DISK_CAPACITY)
+            .set(hashed("a-target"))
+            .unwrap();
+        let entry_size = fs::read_dir(probe.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .metadata()
+            .unwrap()
+            .len();
+
+        // Room for two entries
+        let cache = disk_cache(&dir, entry_size * 5 / 2);
+        let pause = || std::thread::sleep(Duration::from_millis(20));
+        cache.set(hashed("a-target")).unwrap();
+        pause();
+        cache.set(hashed("b-target")).unwrap();
+        pause();
+        cache.get("a-target-hash").unwrap();
+        pause();
+        cache.set(hashed("c-target")).unwrap();
+        assert_eq!(cache.stats().evictions, 1);
+
+        let reopened = disk_cache(&dir, entry_size * 5 / 2);
+        assert!(reopened.get("a-target-hash").is_ok());
+        assert!(reopened.get("b-target-hash").is_err());
+        assert!(reopened.get("c-target-hash").is_ok());
+    }
 }
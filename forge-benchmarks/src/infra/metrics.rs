@@ -3,13 +3,17 @@
 //! Provides Prometheus-based metrics collection for benchmark operations
 //! using the LLM-Dev-Ops Infra metrics system.
 
+use crate::benchmarks::result::BenchmarkResult;
 use lazy_static::lazy_static;
 use prometheus::{
     Counter, CounterVec, Gauge, GaugeVec, Histogram, HistogramOpts, HistogramVec, Opts, Registry,
 };
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use thiserror::Error;
 
+/// Outcome of the one-time registration done by `init_metrics`.
+static INIT: OnceLock<Result<(), String>> = OnceLock::new();
+
 /// Errors that can occur during metrics operations
 #[derive(Error, Debug)]
 pub enum MetricsError {
@@ -21,6 +25,12 @@ pub enum MetricsError {
 
     #[error("Metrics export failed: {0}")]
     ExportError(String),
+
+    #[error("Metrics server failed: {0}")]
+    ServerError(String),
+
+    #[error("Pushgateway push failed: {0}")]
+    PushError(String),
 }
 
 lazy_static! {
@@ -54,6 +64,9 @@ lazy_static! {
     ).unwrap();
 
     /// Gauge for current benchmark operations per second
+    ///
+    /// Holds the value of whichever target was recorded last; use
+    /// `BENCHMARK_OPS_PER_SEC_BY_TARGET` for per-target values.
     pub static ref BENCHMARK_OPS_PER_SEC: Gauge = Gauge::new(
         "forge_benchmark_ops_per_second",
         "Current benchmark operations per second"
@@ -76,38 +89,78 @@ lazy_static! {
         ).buckets(vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0]),
         &["target_id"]
     ).unwrap();
-}
 
-/// Initialize the metrics system
-pub fn init_metrics() -> Result<(), MetricsError> {
-    BENCHMARK_REGISTRY
-        .register(Box::new(BENCHMARKS_TOTAL.clone()))
-        .map_err(|e| MetricsError::RegistrationError(e.to_string()))?;
+    /// Gauge vec for the median iteration latency by target (nanoseconds)
+    pub static ref BENCHMARK_MEDIAN_NS_BY_TARGET: GaugeVec = GaugeVec::new(
+        Opts::new(
+            "forge_benchmark_median_ns",
+            "Median iteration latency by target in nanoseconds"
+        ),
+        &["target_id"]
+    ).unwrap();
 
-    BENCHMARK_REGISTRY
-        .register(Box::new(BENCHMARKS_SUCCESS.clone()))
-        .map_err(|e| MetricsError::RegistrationError(e.to_string()))?;
+    /// Gauge vec for the 95th percentile iteration latency by target (nanoseconds)
+    pub static ref BENCHMARK_P95_NS_BY_TARGET: GaugeVec = GaugeVec::new(
+        Opts::new(
+            "forge_benchmark_p95_ns",
+            "95th percentile iteration latency by target in nanoseconds"
+        ),
+        &["target_id"]
+    ).unwrap();
 
-    BENCHMARK_REGISTRY
-        .register(Box::new(BENCHMARKS_FAILED.clone()))
-        .map_err(|e| MetricsError::RegistrationError(e.to_string()))?;
+    /// Gauge vec for operations per second by target
+    pub static ref BENCHMARK_OPS_PER_SEC_BY_TARGET: GaugeVec = GaugeVec::new(
+        Opts::new(
+            "forge_benchmark_ops_per_second_by_target",
+            "Operations per second by target"
+        ),
+        &["target_id"]
+    ).unwrap();
 
-    BENCHMARK_REGISTRY
-        .register(Box::new(BENCHMARK_DURATION.clone()))
-        .map_err(|e| MetricsError::RegistrationError(e.to_string()))?;
+    /// Counter vec for result cache hits by tier (`l1` memory, `l2` disk)
+    pub static ref CACHE_HITS: CounterVec = CounterVec::new(
+        Opts::new("forge_benchmark_cache_hits_total", "Result cache hits by tier"),
+        &["tier"]
+    ).unwrap();
 
-    BENCHMARK_REGISTRY
-        .register(Box::new(BENCHMARK_OPS_PER_SEC.clone()))
-        .map_err(|e| MetricsError::RegistrationError(e.to_string()))?;
+    /// Counter for result cache misses, including expired and corrupt entries
+    pub static ref CACHE_MISSES: Counter = Counter::new(
+        "forge_benchmark_cache_misses_total",
+        "Result cache misses"
+    ).unwrap();
 
-    BENCHMARK_REGISTRY
-        .register(Box::new(BENCHMARKS_BY_TARGET.clone()))
-        .map_err(|e| MetricsError::RegistrationError(e.to_string()))?;
+    /// Counter for entries evicted from the disk tier to stay within its capacity
+    pub static ref CACHE_EVICTIONS: Counter = Counter::new(
+        "forge_benchmark_cache_evictions_total",
+        "Result cache entries evicted from disk"
+    ).unwrap();
+}
 
-    BENCHMARK_REGISTRY
-        .register(Box::new(BENCHMARK_DURATION_BY_TARGET.clone()))
-        .map_err(|e| MetricsError::RegistrationError(e.to_string()))?;
+/// Initialize the metrics system
+///
+/// Registers all benchmark metrics with `BENCHMARK_REGISTRY`. Safe to call
+/// any number of times: registration happens once and later calls return
+/// its outcome.
+pub fn init_metrics() -> Result<(), MetricsError> {
+    INIT.get_or_init(|| register_all().map_err(|e| e.to_string()))
+        .clone()
+        .map_err(MetricsError::RegistrationError)
+}
 
+fn register_all() -> Result<(), prometheus::Error> {
+    BENCHMARK_REGISTRY.register(Box::new(BENCHMARKS_TOTAL.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(BENCHMARKS_SUCCESS.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(BENCHMARKS_FAILED.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(BENCHMARK_DURATION.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(BENCHMARK_OPS_PER_SEC.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(BENCHMARKS_BY_TARGET.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(BENCHMARK_DURATION_BY_TARGET.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(BENCHMARK_MEDIAN_NS_BY_TARGET.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(BENCHMARK_P95_NS_BY_TARGET.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(BENCHMARK_OPS_PER_SEC_BY_TARGET.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(CACHE_HITS.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(CACHE_MISSES.clone()))?;
+    BENCHMARK_REGISTRY.register(Box::new(CACHE_EVICTIONS.clone()))?;
     Ok(())
 }
 
@@ -133,14 +186,78 @@ pub fn record_ops_per_sec(ops: f64) {
     BENCHMARK_OPS_PER_SEC.set(ops);
 }
 
+/// Record a finished target: its execution, outcome and per-target gauges
+///
+/// Called by the runner for every target that ran when `infra-metrics` is
+/// enabled; skipped targets are not recorded.
+/// Median and p95 are only set when raw samples were recorded.
+pub fn record_result(result: &BenchmarkResult, duration_secs: f64) {
+    let target_id = result.target_id.as_str();
+    record_benchmark(target_id, duration_secs, !result.is_failed());
+
+    if let Some(ops) = result.ops_per_sec() {
+        record_ops_per_sec(ops);
+        BENCHMARK_OPS_PER_SEC_BY_TARGET
+            .with_label_values(&[target_id])
+            .set(ops);
+    }
+    if let Some(summary) = result.metrics.sample_summary() {
+        BENCHMARK_MEDIAN_NS_BY_TARGET
+            .with_label_values(&[target_id])
+            .set(summary.median);
+        BENCHMARK_P95_NS_BY_TARGET
+            .with_label_values(&[target_id])
+            .set(summary.p95);
+    }
+}
+
+/// Record a result cache hit served by `tier` (`l1` or `l2`)
+pub fn record_cache_hit(tier: &str) {
+    CACHE_HITS.with_label_values(&[tier]).inc();
+}
+
+/// Record a result cache miss
+pub fn record_cache_miss() {
+    CACHE_MISSES.inc();
+}
+
+/// Record entries evicted from the result cache
+pub fn record_cache_evictions(count: usize) {
+    CACHE_EVICTIONS.inc_by(count as f64);
+}
+
 /// Export metrics in Prometheus text format
 pub fn export_metrics() -> Result<String, MetricsError> {
+    encode(BENCHMARK_REGISTRY.gather())
+}
+
+/// Export the metrics of one target, or the run-wide metrics
+///
+/// With `Some(target_id)`, only series labelled with that target are
+/// included; with `None`, only series without a `target_id` label. Used to
+/// push each target as its own Pushgateway group.
+pub fn export_target_metrics(target_id: Option<&str>) -> Result<String, MetricsError> {
+    let mut families = BENCHMARK_REGISTRY.gather();
+    for family in &mut families {
+        family.mut_metric().retain(|metric| {
+            let label = metric
+                .get_label()
+                .iter()
+                .find(|l| l.get_name() == "target_id")
+                .map(|l| l.get_value());
+            label == target_id
+        });
+    }
+    families.retain(|family| !family.get_metric().is_empty());
+    encode(families)
+}
+
+fn encode(families: Vec<prometheus::proto::MetricFamily>) -> Result<String, MetricsError> {
     use prometheus::Encoder;
     let encoder = prometheus::TextEncoder::new();
-    let metric_families = BENCHMARK_REGISTRY.gather();
     let mut buffer = Vec::new();
     encoder
-        .encode(&metric_families, &mut buffer)
+        .encode(&families, &mut buffer)
         .map_err(|e| MetricsError::ExportError(e.to_string()))?;
     String::from_utf8(buffer).map_err(|e| MetricsError::ExportError(e.to_string()))
 }
@@ -148,6 +265,7 @@ pub fn export_metrics() -> Result<String, MetricsError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_record_benchmark() {
@@ -160,4 +278,70 @@ mod tests {
         record_ops_per_sec(1_000_000.0);
         // Should be recorded without panic
     }
+
+    #[test]
+    fn test_record_cache_counters() {
+        init_metrics().unwrap();
+        let hits = CACHE_HITS.with_label_values(&["l2"]).get();
+        record_cache_hit("l2");
+        record_cache_miss();
+        record_cache_evictions(2);
+
+        assert_eq!(CACHE_HITS.with_label_values(&["l2"]).get(), hits + 1.0);
+        assert!(export_metrics()
+            .unwrap()
+            .contains("forge_benchmark_cache_hits_total{tier=\"l2\"}"));
+    }
+
+    #[test]
+    fn test_init_metrics_is_idempotent() {
+        assert!(init_metrics().is_ok());
+        assert!(init_metrics().is_ok());
+    }
+
+    #[test]
+    fn test_record_result_sets_per_target_gauges() {
+        init_metrics().unwrap();
+        let fast = BenchmarkResult::new(
+            "gauge-fast".to_string(),
+            json!({"ops_per_sec": 2000.0, "raw_ns": [400000.0, 500000.0, 600000.0]}),
+        );
+        let slow = BenchmarkResult::new("gauge-slow".to_string(), json!({"ops_per_sec": 10.0}));
+
+        record_result(&fast, 0.5);
+        record_result(&slow, 2.0);
+        record_result(
+            &BenchmarkResult::failed("gauge-broken".to_string(), "boom".to_string()),
+            0.1,
+        );
+
+        let ops = |id: &str| {
+            BENCHMARK_OPS_PER_SEC_BY_TARGET
+                .with_label_values(&[id])
+                .get()
+        };
+        assert_eq!(ops("gauge-fast"), 2000.0);
+        assert_eq!(ops("gauge-slow"), 10.0);
+        assert_eq!(
+            BENCHMARK_MEDIAN_NS_BY_TARGET
+                .with_label_values(&["gauge-fast"])
+                .get(),
+            500000.0
+        );
+
+        let text = export_metrics().unwrap();
+        assert!(text.contains("forge_benchmark_p95_ns{target_id=\"gauge-fast\"}"));
+        assert!(text.contains("forge_benchmarks_by_target_total{target_id=\"gauge-broken\"} 1"));
+
+        let group = export_target_metrics(Some("gauge-slow")).unwrap();
+        assert!(
+            group.contains("forge_benchmark_ops_per_second_by_target{target_id=\"gauge-slow\"} 10")
+        );
+        assert!(!group.contains("gauge-fast"));
+        assert!(!group.contains("forge_benchmarks_total "));
+
+        let run = export_target_metrics(None).unwrap();
+        assert!(run.contains("forge_benchmarks_total "));
+        assert!(!run.contains("target_id"));
+    }
 }
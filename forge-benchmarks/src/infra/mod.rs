@@ -2,14 +2,17 @@
 //!
 //! This module provides integration with LLM-Dev-Ops Infra crates for:
 //! - Configuration management (llm-config-core)
-//! - Prometheus metrics (llm-config-metrics)
+//! - Prometheus metrics (llm-config-metrics), served on `/metrics` or pushed
+//!   to a Pushgateway
 //! - Multi-tier caching (llm-config-cache)
+//! - OpenTelemetry traces and metrics over OTLP/HTTP
 //!
 //! Enable with feature flags:
 //! - `infra-full`: All infrastructure modules
 //! - `infra-config`: Configuration management only
 //! - `infra-metrics`: Metrics collection only
 //! - `infra-cache`: Caching support only
+//! - `infra-otel`: OpenTelemetry export only
 //!
 //! ## Usage
 //!
@@ -24,6 +27,15 @@ pub mod config;
 #[cfg(feature = "infra-metrics")]
 pub mod metrics;
 
+#[cfg(feature = "infra-metrics")]
+pub mod pushgateway;
+
+#[cfg(feature = "infra-metrics")]
+pub mod server;
+
+#[cfg(feature = "infra-otel")]
+pub mod otel;
+
 #[cfg(feature = "infra-cache")]
 pub mod cache;
 
@@ -42,7 +54,8 @@ pub fn is_infra_enabled() -> bool {
     cfg!(any(
         feature = "infra-config",
         feature = "infra-metrics",
-        feature = "infra-cache"
+        feature = "infra-cache",
+        feature = "infra-otel"
     ))
 }
 
@@ -59,6 +72,9 @@ pub fn enabled_features() -> Vec<&'static str> {
     #[cfg(feature = "infra-cache")]
     features.push("infra-cache");
 
+    #[cfg(feature = "infra-otel")]
+    features.push("infra-otel");
+
     features
 }
 
@@ -72,7 +88,7 @@ mod tests {
         // Features list should be consistent with cargo features
         for feature in &features {
             assert!(
-                ["infra-config", "infra-metrics", "infra-cache"].contains(feature),
+                ["infra-config", "infra-metrics", "infra-cache", "infra-otel"].contains(feature),
                 "Unknown feature: {}",
                 feature
             );
@@ -0,0 +1,504 @@
+//! OpenTelemetry Export
+//!
+//! Turns run events into OpenTelemetry spans (run → target → iteration) and
+//! an iteration latency histogram, exported over OTLP/HTTP to a collector.
+//!
+//! The observer only sees events after the fact, so spans are created with
+//! the timestamps recorded by the runner rather than the time the event was
+//! delivered.
+
+use crate::benchmarks::error::BenchError;
+use crate::benchmarks::events::{RunEvent, RunObserver};
+use crate::benchmarks::result::BenchmarkResult;
+use crate::benchmarks::tabular::PARAMS_PREFIX;
+use chrono::{DateTime, Utc};
+use opentelemetry::metrics::{Histogram, MeterProvider};
+use opentelemetry::trace::{Status, TraceContextExt, Tracer, TracerProvider};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tracing::warn;
+
+/// Instrumentation scope of the exported spans and metrics
+pub const SCOPE: &str = "forge-benchmarks";
+
+/// Name of the iteration latency histogram
+pub const ITERATION_HISTOGRAM: &str = "forge.benchmark.iteration.duration";
+
+/// Errors that can occur during OpenTelemetry export
+#[derive(Error, Debug)]
+pub enum OtelError {
+    #[error("OTLP exporter setup failed: {0}")]
+    ExporterError(String),
+
+    #[error("OTLP flush failed: {0}")]
+    FlushError(String),
+}
+
+/// Spans that are still open
+#[derive(Default)]
+struct OpenSpans {
+    run: Option<Context>,
+    targets: HashMap<String, Context>,
+}
+
+/// Run observer exporting spans and latency histograms
+///
+/// Spans and metrics are flushed when the run finishes and the providers
+/// are shut down when the observer is dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use forge_benchmarks::benchmarks::events::EventBus;
+/// use forge_benchmarks::infra::otel::OtelObserver;
+/// use std::sync::Arc;
+///
+/// let observer = OtelObserver::otlp(Some("http://localhost:4318")).unwrap();
+/// let bus = EventBus::start(vec![Arc::new(observer)]);
+/// // run_suite_with_events(config, &bus.publisher()).await;
+/// bus.finish();
+/// ```
+pub struct OtelObserver {
+    tracer_provider: SdkTracerProvider,
+    meter_provider: SdkMeterProvider,
+    tracer: SdkTracer,
+    iteration_ns: Histogram<f64>,
+    spans: Mutex<OpenSpans>,
+}
+
+impl OtelObserver {
+    /// Create an observer exporting through the given providers
+    pub fn new(tracer_provider: SdkTracerProvider, meter_provider: SdkMeterProvider) -> Self {
+        let tracer = tracer_provider.tracer(SCOPE);
+        let iteration_ns = meter_provider
+            .meter(SCOPE)
+            .f64_histogram(ITERATION_HISTOGRAM)
+            .with_unit("ns")
+            .with_description("Duration of measured benchmark iterations")
+            .build();
+
+        Self {
+            tracer_provider,
+            meter_provider,
+            tracer,
+            iteration_ns,
+            spans: Mutex::new(OpenSpans::default()),
+        }
+    }
+
+    /// Create an observer exporting over OTLP/HTTP
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Collector base URL (e.g. `http://localhost:4318`); when
+    ///   `None`, the standard `OTEL_EXPORTER_OTLP_*` environment variables apply
+    pub fn otlp(endpoint: Option<&str>) -> Result<Self, OtelError> {
+        let mut spans = SpanExporter::builder().with_http();
+        let mut metrics = MetricExporter::builder().with_http();
+        if let Some(endpoint) = endpoint {
+            let endpoint = endpoint.trim_end_matches('/');
+            spans = spans.with_endpoint(format!("{}/v1/traces", endpoint));
+            metrics = metrics.with_endpoint(format!("{}/v1/metrics", endpoint));
+        }
+        let spans = spans
+            .build()
+            .map_err(|e| OtelError::ExporterError(e.to_string()))?;
+        let metrics = metrics
+            .build()
+            .map_err(|e| OtelError::ExporterError(e.to_string()))?;
+
+        Ok(Self::new(
+            SdkTracerProvider::builder()
+                .with_batch_exporter(spans)
+                .build(),
+            SdkMeterProvider::builder()
+                .with_periodic_exporter(metrics)
+                .build(),
+        ))
+    }
+
+    /// Export everything recorded so far
+    pub fn flush(&self) -> Result<(), OtelError> {
+        self.tracer_provider
+            .force_flush()
+            .map_err(|e| OtelError::FlushError(e.to_string()))?;
+        self.meter_provider
+            .force_flush()
+            .map_err(|e| OtelError::FlushError(e.to_string()))
+    }
+
+    fn start_span(
+        &self,
+        name: &'static str,
+        parent: Option<&Context>,
+        start: SystemTime,
+        attributes: Vec<KeyValue>,
+    ) -> Context {
+        let parent = parent.cloned().unwrap_or_default();
+        let span = self
+            .tracer
+            .span_builder(name)
+            .with_start_time(start)
+            .with_attributes(attributes)
+            .start_with_context(&self.tracer, &parent);
+        parent.with_span(span)
+    }
+
+    fn target_done(
+        &self,
+        target_id: &str,
+        at: SystemTime,
+        outcome: Result<&BenchmarkResult, (&str, Option<&BenchError>)>,
+    ) {
+        let Some(cx) = lock(&self.spans).targets.remove(target_id) else {
+            return;
+        };
+        let span = cx.span();
+        match outcome {
+            Ok(result) => {
+                span.set_attribute(KeyValue::new("forge.status", "passed"));
+                span.set_attribute(KeyValue::new("forge.attempts", i64::from(result.attempts)));
+                span.set_attributes(params(result));
+                span.set_status(Status::Ok);
+            }
+            Err((error, failure)) => {
+                span.set_attribute(KeyValue::new("forge.status", "failed"));
+                span.set_attribute(KeyValue::new("forge.error", error.to_string()));
+                if let Some(failure) = failure {
+                    span.set_attribute(KeyValue::new("forge.error.kind", failure.kind()));
+                }
+                span.set_status(Status::error(error.to_string()));
+            }
+        }
+        span.end_with_timestamp(at);
+    }
+}
+
+impl RunObserver for OtelObserver {
+    fn on_event(&self, event: &RunEvent) {
+        match event {
+            RunEvent::RunStarted { run_id, at } => {
+                let cx = self.start_span(
+                    "benchmark.run",
+                    None,
+                    time(at),
+                    vec![KeyValue::new("forge.run_id", run_id.to_string())],
+                );
+                lock(&self.spans).run = Some(cx);
+            }
+            RunEvent::TargetStarted {
+                target_id,
+                index,
+                total,
+                at,
+            } => {
+                let run = lock(&self.spans).run.clone();
+                let cx = self.start_span(
+                    "benchmark.target",
+                    run.as_ref(),
+                    time(at),
+                    vec![
+                        KeyValue::new("forge.target_id", target_id.clone()),
+                        KeyValue::new("forge.target.index", *index as i64),
+                        KeyValue::new("forge.target.total", *total as i64),
+                    ],
+                );
+                lock(&self.spans).targets.insert(target_id.clone(), cx);
+            }
+            RunEvent::SampleRecorded {
+                target_id,
+                iteration,
+                iterations,
+                sample_ns,
+                at,
+            } => {
+                let target_attr = KeyValue::new("forge.target_id", target_id.clone());
+                self.iteration_ns
+                    .record(*sample_ns, std::slice::from_ref(&target_attr));
+
+                let target = lock(&self.spans).targets.get(target_id).cloned();
+                let end = time(at);
+                let start = end
+                    .checked_sub(Duration::from_nanos(*sample_ns as u64))
+                    .unwrap_or(end);
+                self.start_span(
+                    "benchmark.iteration",
+                    target.as_ref(),
+                    start,
+                    vec![
+                        target_attr,
+                        KeyValue::new("forge.iteration", i64::from(*iteration)),
+                        KeyValue::new("forge.iterations", i64::from(*iterations)),
+                    ],
+                )
+                .span()
+                .end_with_timestamp(end);
+            }
+            RunEvent::TargetFinished { result } => {
+                self.target_done(&result.target_id, time(&result.timestamp), Ok(result));
+            }
+            RunEvent::TargetFailed {
+                target_id,
+                error,
+                failure,
+                at,
+            } => self.target_done(target_id, time(at), Err((error, failure.as_ref()))),
+            RunEvent::TargetRetrying {
+                target_id,
+                attempt,
+                error,
+                at,
+                ..
+            } => {
+                if let Some(cx) = lock(&self.spans).targets.get(target_id) {
+                    cx.span().add_event_with_timestamp(
+                        "benchmark.retry",
+                        time(at),
+                        vec![
+                            KeyValue::new("forge.attempt", i64::from(*attempt)),
+                            KeyValue::new("forge.error", error.clone()),
+                        ],
+                    );
+                }
+            }
+            RunEvent::TargetSkipped {
+                target_id,
+                reason,
+                at,
+            } => {
+                if let Some(cx) = lock(&self.spans).targets.remove(target_id) {
+                    let span = cx.span();
+                    span.set_attribute(KeyValue::new("forge.status", "skipped"));
+                    span.set_attribute(KeyValue::new("forge.skip_reason", reason.clone()));
+                    span.end_with_timestamp(time(at));
+                }
+            }
+            RunEvent::RunFinished {
+                passed,
+                failed,
+                skipped,
+                at,
+                ..
+            } => {
+                if let Some(cx) = lock(&self.spans).run.take() {
+                    let span = cx.span();
+                    span.set_attribute(KeyValue::new("forge.passed", *passed as i64));
+                    span.set_attribute(KeyValue::new("forge.failed", *failed as i64));
+                    span.set_attribute(KeyValue::new("forge.skipped", *skipped as i64));
+                    if *failed > 0 {
+                        span.set_status(Status::error(format!("{} targets failed", failed)));
+                    }
+                    span.end_with_timestamp(time(at));
+                }
+                if let Err(e) = self.flush() {
+                    warn!("{}", e);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for OtelObserver {
+    fn drop(&mut self) {
+        // Shutdown flushes what is left; errors only mean nothing was pending
+        let _ = self.tracer_provider.shutdown();
+        let _ = self.meter_provider.shutdown();
+    }
+}
+
+fn lock(spans: &Mutex<OpenSpans>) -> std::sync::MutexGuard<'_, OpenSpans> {
+    match spans.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn time(at: &DateTime<Utc>) -> SystemTime {
+    SystemTime::from(*at)
+}
+
+/// `params.*` extension metrics as `forge.params.*` attributes
+fn params(result: &BenchmarkResult) -> Vec<KeyValue> {
+    result
+        .metrics
+        .extra
+        .iter()
+        .filter_map(|(key, value)| {
+            let name = key.strip_prefix(PARAMS_PREFIX)?;
+            let key = format!("forge.params.{}", name);
+            Some(match value {
+                Value::Bool(b) => KeyValue::new(key, *b),
+                Value::Number(n) => match n.as_i64() {
+                    Some(i) => KeyValue::new(key, i),
+                    None => KeyValue::new(key, n.as_f64().unwrap_or_default()),
+                },
+                Value::String(s) => KeyValue::new(key, s.clone()),
+                other => KeyValue::new(key, other.to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::events::EventBus;
+    use opentelemetry::Value as OtelValue;
+    use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+    use opentelemetry_sdk::metrics::InMemoryMetricExporter;
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SpanData};
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    fn in_memory() -> (OtelObserver, InMemorySpanExporter, InMemoryMetricExporter) {
+        let spans = InMemorySpanExporter::default();
+        let metrics = InMemoryMetricExporter::default();
+        let observer = OtelObserver::new(
+            SdkTracerProvider::builder()
+                .with_simple_exporter(spans.clone())
+                .build(),
+            SdkMeterProvider::builder()
+                .with_periodic_exporter(metrics.clone())
+                .build(),
+        );
+        (observer, spans, metrics)
+    }
+
+    fn attribute<'a>(span: &'a SpanData, key: &str) -> Option<&'a OtelValue> {
+        span.attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| &kv.value)
+    }
+
+    /// Publish a run with a passing and a failing target; the caller keeps
+    /// `observer` alive, since shutting down clears the in-memory spans
+    fn run(observer: Arc<OtelObserver>) {
+        let bus = EventBus::start(vec![observer]);
+        let publisher = bus.publisher();
+        let now = Utc::now();
+        publisher.publish(RunEvent::RunStarted {
+            run_id: Uuid::nil(),
+            at: now,
+        });
+        for (index, target_id) in ["parse", "generate"].iter().enumerate() {
+            publisher.publish(RunEvent::TargetStarted {
+                target_id: target_id.to_string(),
+                index,
+                total: 2,
+                at: now,
+            });
+        }
+        for iteration in 1..=3 {
+            publisher.publish(RunEvent::SampleRecorded {
+                target_id: "parse".to_string(),
+                iteration,
+                iterations: 3,
+                sample_ns: 1_000.0 * f64::from(iteration),
+                at: now,
+            });
+        }
+        let mut result = BenchmarkResult::new(
+            "parse".to_string(),
+            serde_json::json!({"ops_per_sec": 1000.0, "params.provider": "openai", "params.size": 4}),
+        );
+        result.timestamp = now;
+        publisher.publish(RunEvent::target_done(&result));
+        publisher.publish(RunEvent::target_done(&BenchmarkResult::failed(
+            "generate".to_string(),
+            "exit status 2".to_string(),
+        )));
+        publisher.publish(RunEvent::RunFinished {
+            run_id: Uuid::nil(),
+            passed: 1,
+            failed: 1,
+            skipped: 0,
+            at: Utc::now(),
+        });
+        drop(publisher);
+        bus.finish();
+    }
+
+    #[test]
+    fn test_spans_form_run_tree() {
+        let (observer, exporter, _) = in_memory();
+        let observer = Arc::new(observer);
+        run(observer.clone());
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let named = |name: &'static str| spans.iter().filter(move |s| s.name == name);
+        assert_eq!(named("benchmark.run").count(), 1);
+        assert_eq!(named("benchmark.target").count(), 2);
+        assert_eq!(named("benchmark.iteration").count(), 3);
+
+        let run = named("benchmark.run").next().unwrap();
+        let parse = named("benchmark.target")
+            .find(|s| attribute(s, "forge.target_id") == Some(&"parse".into()))
+            .unwrap();
+        let generate = named("benchmark.target")
+            .find(|s| attribute(s, "forge.target_id") == Some(&"generate".into()))
+            .unwrap();
+
+        assert_eq!(parse.parent_span_id, run.span_context.span_id());
+        assert_eq!(parse.span_context.trace_id(), run.span_context.trace_id());
+        assert!(
+            named("benchmark.iteration").all(|s| s.parent_span_id == parse.span_context.span_id())
+        );
+        let iteration = named("benchmark.iteration")
+            .find(|s| attribute(s, "forge.iteration") == Some(&OtelValue::I64(3)))
+            .unwrap();
+        assert_eq!(
+            iteration
+                .end_time
+                .duration_since(iteration.start_time)
+                .unwrap(),
+            Duration::from_nanos(3_000)
+        );
+
+        assert_eq!(attribute(parse, "forge.status"), Some(&"passed".into()));
+        assert_eq!(
+            attribute(parse, "forge.params.provider"),
+            Some(&"openai".into())
+        );
+        assert_eq!(
+            attribute(parse, "forge.params.size"),
+            Some(&OtelValue::I64(4))
+        );
+        assert_eq!(attribute(generate, "forge.status"), Some(&"failed".into()));
+        assert_eq!(generate.status, Status::error("exit status 2"));
+        assert_eq!(attribute(run, "forge.failed"), Some(&OtelValue::I64(1)));
+    }
+
+    #[test]
+    fn test_iteration_histogram_exported() {
+        let (observer, _, exporter) = in_memory();
+        let observer = Arc::new(observer);
+        run(observer.clone());
+
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let histogram = metrics
+            .iter()
+            .flat_map(|rm| rm.scope_metrics())
+            .flat_map(|sm| sm.metrics())
+            .find(|m| m.name() == ITERATION_HISTOGRAM)
+            .expect("histogram exported");
+        assert_eq!(histogram.unit(), "ns");
+
+        let AggregatedMetrics::F64(MetricData::Histogram(data)) = histogram.data() else {
+            panic!("unexpected data {:?}", histogram.data());
+        };
+        let point = data.data_points().next().unwrap();
+        assert_eq!(point.count(), 3);
+        assert_eq!(point.sum(), 6_000.0);
+        assert!(point
+            .attributes()
+            .any(|kv| kv.key.as_str() == "forge.target_id" && kv.value == "parse".into()));
+    }
+}
@@ -0,0 +1,293 @@
+//! Prometheus Pushgateway Client
+//!
+//! Pushes benchmark metrics to a Pushgateway at the end of a run. Each run
+//! is pushed as one group for the run-wide metrics plus one group per
+//! target, keyed by `run_id` and `target_id`, so concurrent or repeated
+//! runs never overwrite each other.
+
+use super::metrics::{export_target_metrics, MetricsError};
+use crate::benchmarks::run::RunReport;
+use std::thread;
+use std::time::Duration;
+use tracing::warn;
+
+/// Default number of attempts per push
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry; doubled after every attempt
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Default job name
+pub const DEFAULT_JOB: &str = "forge_benchmarks";
+
+/// Timeout of a single push request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Client pushing metrics groups to a Pushgateway
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use forge_benchmarks::infra::pushgateway::PushgatewayClient;
+/// use std::time::Duration;
+///
+/// let client = PushgatewayClient::new("http://pushgateway:9091", "forge_benchmarks")
+///     .with_retries(5, Duration::from_secs(1));
+/// client.push(&[("run_id", "42")], "forge_benchmarks_total 3\n").unwrap();
+/// ```
+pub struct PushgatewayClient {
+    url: String,
+    job: String,
+    max_attempts: u32,
+    backoff: Duration,
+    agent: ureq::Agent,
+}
+
+impl PushgatewayClient {
+    /// Create a client for the Pushgateway at `url` (e.g. `http://localhost:9091`)
+    pub fn new(url: &str, job: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            job: job.to_string(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            backoff: DEFAULT_BACKOFF,
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+        }
+    }
+
+    /// Set the number of attempts per push and the initial retry delay
+    pub fn with_retries(mut self, max_attempts: u32, backoff: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.backoff = backoff;
+        self
+    }
+
+    /// Push `body` (Prometheus text format) as the group identified by
+    /// `grouping` labels, replacing the group's previous metrics
+    ///
+    /// Connection failures and 5xx responses are retried with exponential
+    /// backoff; other errors fail immediately.
+    pub fn push(&self, grouping: &[(&str, &str)], body: &str) -> Result<(), MetricsError> {
+        let url = self.group_url(grouping);
+        let mut delay = self.backoff;
+
+        for attempt in 1..=self.max_attempts {
+            let error = match self
+                .agent
+                .put(&url)
+                .set("Content-Type", "text/plain; version=0.0.4")
+                .send_string(body)
+            {
+                Ok(_) => return Ok(()),
+                Err(ureq::Error::Status(code, response)) if code < 500 => {
+                    let detail = response.into_string().unwrap_or_default();
+                    return Err(MetricsError::PushError(format!(
+                        "{} returned {}: {}",
+                        url,
+                        code,
+                        detail.trim()
+                    )));
+                }
+                Err(e) => e.to_string(),
+            };
+
+            if attempt == self.max_attempts {
+                return Err(MetricsError::PushError(format!(
+                    "{} failed after {} attempts: {}",
+                    url, attempt, error
+                )));
+            }
+            warn!(
+                "Pushgateway attempt {}/{} failed: {}",
+                attempt, self.max_attempts, error
+            );
+            thread::sleep(delay);
+            delay *= 2;
+        }
+        unreachable!("max_attempts is at least 1")
+    }
+
+    /// Push the metrics of a finished run
+    ///
+    /// Pushes the run-wide metrics grouped by `run_id`, then each target's
+    /// metrics grouped by `run_id` and `target_id`.
+    pub fn push_run(&self, report: &RunReport) -> Result<(), MetricsError> {
+        let run_id = report.run_id.to_string();
+        self.push(&[("run_id", &run_id)], &export_target_metrics(None)?)?;
+
+        for result in &report.results {
+            let body = export_target_metrics(Some(&result.target_id))?;
+            if body.is_empty() {
+                continue;
+            }
+            self.push(
+                &[("run_id", &run_id), ("target_id", &result.target_id)],
+                &body,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Build `<url>/metrics/job/<job>/<label>/<value>...`
+    fn group_url(&self, grouping: &[(&str, &str)]) -> String {
+        let mut url = format!("{}/metrics/{}", self.url, path_segment("job", &self.job));
+        for (name, value) in grouping {
+            url.push('/');
+            url.push_str(&path_segment(name, value));
+        }
+        url
+    }
+}
+
+/// Encode a grouping label as a URL path segment
+///
+/// Values that are empty or contain characters other than ASCII
+/// alphanumerics, `-`, `_` and `.` use the Pushgateway's base64 form
+/// (`<name>@base64/<value>`).
+fn path_segment(name: &str, value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if plain {
+        format!("{}/{}", name, value)
+    } else {
+        format!("{}@base64/{}", name, base64_url(value.as_bytes()))
+    }
+}
+
+/// URL-safe base64 with padding, as accepted by the Pushgateway
+fn base64_url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    if bytes.is_empty() {
+        return "=".to_string();
+    }
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | u32::from(*b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::result::BenchmarkResult;
+    use crate::infra::metrics::{init_metrics, record_result};
+    use std::sync::mpsc;
+    use tiny_http::{Response, Server};
+
+    /// Received request: method, URL and body
+    type Received = (String, String, String);
+
+    /// Start a stub Pushgateway answering with `statuses` in order
+    fn stub(statuses: Vec<u16>) -> (String, mpsc::Receiver<Received>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for status in statuses {
+                let Ok(mut request) = server.recv() else {
+                    return;
+                };
+                let mut body = String::new();
+                let _ = request.as_reader().read_to_string(&mut body);
+                let _ = sender.send((
+                    request.method().to_string(),
+                    request.url().to_string(),
+                    body,
+                ));
+                let _ = request.respond(Response::empty(status));
+            }
+        });
+
+        (url, receiver)
+    }
+
+    #[test]
+    fn test_push_groups_run_and_targets() {
+        init_metrics().unwrap();
+        record_result(
+            &BenchmarkResult::new(
+                "push/target".to_string(),
+                serde_json::json!({"ops_per_sec": 42.0}),
+            ),
+            0.1,
+        );
+        let mut report = RunReport::from_legacy(vec![BenchmarkResult::new(
+            "push/target".to_string(),
+            serde_json::json!({"ops_per_sec": 42.0}),
+        )]);
+        report.run_id = uuid::Uuid::new_v4();
+
+        let (url, received) = stub(vec![200, 200]);
+        PushgatewayClient::new(&url, "forge_benchmarks")
+            .push_run(&report)
+            .unwrap();
+
+        let (method, path, body) = received.recv().unwrap();
+        assert_eq!(method, "PUT");
+        assert_eq!(
+            path,
+            format!("/metrics/job/forge_benchmarks/run_id/{}", report.run_id)
+        );
+        assert!(body.contains("forge_benchmarks_total"));
+
+        let (_, path, body) = received.recv().unwrap();
+        assert_eq!(
+            path,
+            format!(
+                "/metrics/job/forge_benchmarks/run_id/{}/target_id@base64/cHVzaC90YXJnZXQ=",
+                report.run_id
+            )
+        );
+        assert!(
+            body.contains("forge_benchmark_ops_per_second_by_target{target_id=\"push/target\"} 42")
+        );
+    }
+
+    #[test]
+    fn test_push_retries_server_errors() {
+        let (url, received) = stub(vec![503, 502, 200]);
+        let client = PushgatewayClient::new(&url, "job").with_retries(3, Duration::from_millis(1));
+
+        client.push(&[("run_id", "1")], "x 1\n").unwrap();
+        assert_eq!(received.try_iter().count(), 3);
+    }
+
+    #[test]
+    fn test_push_gives_up() {
+        let (url, _received) = stub(vec![503, 503]);
+        let client = PushgatewayClient::new(&url, "job").with_retries(2, Duration::from_millis(1));
+        assert!(matches!(
+            client.push(&[], "x 1\n"),
+            Err(MetricsError::PushError(_))
+        ));
+
+        let (url, received) = stub(vec![400]);
+        let client = PushgatewayClient::new(&url, "job").with_retries(3, Duration::from_millis(1));
+        assert!(client.push(&[], "x 1\n").is_err());
+        assert_eq!(received.try_iter().count(), 1);
+    }
+
+    #[test]
+    fn test_base64_url() {
+        assert_eq!(base64_url(b"push/target"), "cHVzaC90YXJnZXQ=");
+        assert_eq!(base64_url(b"ab"), "YWI=");
+        assert_eq!(base64_url(b"abc"), "YWJj");
+        assert_eq!(base64_url(b""), "=");
+        assert_eq!(path_segment("job", "a.b-c_1"), "job/a.b-c_1");
+    }
+}
@@ -0,0 +1,123 @@
+//! Embedded Prometheus Endpoint
+//!
+//! Serves `GET /metrics` in Prometheus text format from a background thread,
+//! so long benchmark runs can be scraped while they are in progress.
+
+use super::metrics::{export_metrics, MetricsError};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use tiny_http::{Header, Response, Server};
+use tracing::{debug, warn};
+
+/// Content type of the Prometheus text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// HTTP server exposing `/metrics`
+///
+/// The server stops when `shutdown` is called or the value is dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use forge_benchmarks::infra::server::MetricsServer;
+///
+/// let server = MetricsServer::start("127.0.0.1:9464").unwrap();
+/// println!("Scrape http://{}/metrics", server.local_addr());
+/// server.shutdown();
+/// ```
+pub struct MetricsServer {
+    server: Arc<Server>,
+    addr: SocketAddr,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// Bind `addr` (e.g. `0.0.0.0:9464`, or port 0 for any free port) and
+    /// start serving
+    pub fn start(addr: &str) -> Result<Self, MetricsError> {
+        let server = Server::http(addr).map_err(|e| MetricsError::ServerError(e.to_string()))?;
+        let addr = server
+            .server_addr()
+            .to_ip()
+            .ok_or_else(|| MetricsError::ServerError(format!("{} is not a TCP address", addr)))?;
+        let server = Arc::new(server);
+
+        let worker = Arc::clone(&server);
+        let handle = thread::spawn(move || {
+            for request in worker.incoming_requests() {
+                debug!("Metrics request: {} {}", request.method(), request.url());
+                let response = match request.url() {
+                    "/metrics" => match export_metrics() {
+                        Ok(body) => {
+                            let header = Header::from_bytes("Content-Type", CONTENT_TYPE)
+                                .expect("static header is valid");
+                            Response::from_string(body).with_header(header)
+                        }
+                        Err(e) => Response::from_string(e.to_string()).with_status_code(500),
+                    },
+                    _ => Response::from_string("Not Found").with_status_code(404),
+                };
+                if let Err(e) = request.respond(response) {
+                    warn!("Failed to answer metrics request: {}", e);
+                }
+            }
+        });
+
+        Ok(Self {
+            server,
+            addr,
+            handle: Some(handle),
+        })
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stop serving and wait for the server thread to exit
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::metrics::{init_metrics, record_benchmark};
+
+    #[test]
+    fn test_serves_metrics() {
+        init_metrics().unwrap();
+        record_benchmark("server-test", 0.2, true);
+
+        let server = MetricsServer::start("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", server.local_addr());
+
+        let response = ureq::get(&format!("{}/metrics", base)).call().unwrap();
+        assert!(response.content_type().starts_with("text/plain"));
+        let body = response.into_string().unwrap();
+        assert!(body.contains("forge_benchmarks_by_target_total{target_id=\"server-test\"}"));
+
+        match ureq::get(&format!("{}/other", base)).call() {
+            Err(ureq::Error::Status(code, _)) => assert_eq!(code, 404),
+            other => panic!("expected 404, got {:?}", other.map(|r| r.status())),
+        }
+
+        server.shutdown();
+    }
+}
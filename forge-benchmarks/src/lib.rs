@@ -16,7 +16,7 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let results = run_all_benchmarks().await;
+//!     let results = run_all_benchmarks().await.expect("llm-forge checkout");
 //!     for result in results {
 //!         println!("{}: {:?}", result.target_id, result.metrics);
 //!     }
@@ -37,23 +37,26 @@
 //! - `infra-config`: Configuration management (llm-config-core)
 //! - `infra-metrics`: Prometheus metrics (llm-config-metrics)
 //! - `infra-cache`: Multi-tier caching (llm-config-cache)
+//! - `infra-otel`: OpenTelemetry traces and metrics over OTLP/HTTP
 
 pub mod adapters;
 pub mod benchmarks;
 
 // Infrastructure integration module (Phase 2B)
-// Enabled via feature flags: infra-config, infra-metrics, infra-cache, infra-full
+// Enabled via feature flags: infra-config, infra-metrics, infra-cache, infra-otel, infra-full
 #[cfg(any(
     feature = "infra-config",
     feature = "infra-metrics",
-    feature = "infra-cache"
+    feature = "infra-cache",
+    feature = "infra-otel"
 ))]
 pub mod infra;
 
 // Re-export key types for convenience
 pub use adapters::BenchTarget;
 pub use benchmarks::result::BenchmarkResult;
-pub use benchmarks::run_all_benchmarks;
+pub use benchmarks::run::RunReport;
+pub use benchmarks::{run_all_benchmarks, run_suite};
 
 /// Version of the forge-benchmarks crate
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -67,7 +70,8 @@ pub fn infra_status() -> &'static str {
         any(
             feature = "infra-config",
             feature = "infra-metrics",
-            feature = "infra-cache"
+            feature = "infra-cache",
+            feature = "infra-otel"
         ),
         not(feature = "infra-full")
     ))]
@@ -76,7 +80,8 @@ pub fn infra_status() -> &'static str {
     #[cfg(not(any(
         feature = "infra-config",
         feature = "infra-metrics",
-        feature = "infra-cache"
+        feature = "infra-cache",
+        feature = "infra-otel"
     )))]
     return "Phase 2B: Infra integration available (enable with features)";
 }